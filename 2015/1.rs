@@ -0,0 +1,63 @@
+use crate::solution::Solution;
+
+fn step(c: char) -> i32 {
+	match c {
+		'(' => 1,
+		')' => -1,
+		_ => 0,
+	}
+}
+
+fn floor(data: &str) -> i32 {
+	data.chars().map(step).sum()
+}
+
+/// The position, counting from one, of the instruction that first takes Santa below ground.
+fn basement(data: &str) -> Option<usize> {
+	data.chars().scan(0, |floor, c| {
+		*floor += step(c);
+		Some(*floor)
+	}).position(|f| f < 0).map(|p| p + 1)
+}
+
+pub struct Day {
+	data: String,
+}
+
+impl Solution for Day {
+	type Part1 = i32;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { data: input.trim().to_string() }
+	}
+
+	fn part1(&self) -> i32 {
+		floor(&self.data)
+	}
+
+	fn part2(&self) -> usize {
+		basement(&self.data).unwrap()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_floor() {
+		assert_eq!(0, floor("(())"));
+		assert_eq!(0, floor("()()"));
+		assert_eq!(3, floor("))((((("));
+		assert_eq!(-1, floor("())"));
+		assert_eq!(-3, floor(")())())"));
+	}
+
+	#[test]
+	fn test_basement() {
+		assert_eq!(Some(1), basement(")"));
+		assert_eq!(Some(5), basement("()())"));
+		assert_eq!(None, basement("(()"));
+	}
+}
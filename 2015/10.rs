@@ -0,0 +1,55 @@
+use crate::solution::Solution;
+
+/// Reads the digits out loud: each run of the same digit becomes its length then the digit.
+fn look_and_say(digits: &[u8]) -> Vec<u8> {
+	let mut said = Vec::with_capacity(digits.len() * 2);
+	for run in digits.chunk_by(|a, b| a == b) {
+		said.push(run.len() as u8);
+		said.push(run[0]);
+	}
+	said
+}
+
+fn length_after(start: &[u8], rounds: usize) -> usize {
+	(0..rounds).fold(start.to_vec(), |d, _| look_and_say(&d)).len()
+}
+
+pub struct Day {
+	digits: Vec<u8>,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { digits: input.trim().bytes().map(|b| b - b'0').collect() }
+	}
+
+	fn part1(&self) -> usize {
+		length_after(&self.digits, 40)
+	}
+
+	fn part2(&self) -> usize {
+		length_after(&self.digits, 50)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_look_and_say() {
+		assert_eq!(vec![1, 1], look_and_say(&[1]));
+		assert_eq!(vec![2, 1], look_and_say(&[1, 1]));
+		assert_eq!(vec![1, 2, 1, 1], look_and_say(&[2, 1]));
+		assert_eq!(vec![1, 1, 1, 2, 2, 1], look_and_say(&[1, 2, 1, 1]));
+		assert_eq!(vec![3, 1, 2, 2, 1, 1], look_and_say(&[1, 1, 1, 2, 2, 1]));
+	}
+
+	#[test]
+	fn test_length_after() {
+		assert_eq!(6, length_after(&[1], 5));
+	}
+}
@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 pub fn to26(s: &str) -> u64 {
 	let mut sum: u64 = 0;
 	let mut place = 0;
@@ -169,12 +171,24 @@ mod tests {
 	}
 }
 
-fn main() {
-	let data1 = "vzbxkghb";
-	let first = get_next(to26(data1));
-	println!("first pass {}", from26(first));
+pub struct Day {
+	password: u64,
+}
+
+impl Solution for Day {
+	type Part1 = String;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Self {
+		Day { password: to26(input.trim()) }
+	}
 
-	let second = get_next(first+1);
+	fn part1(&self) -> String {
+		from26(get_next(self.password))
+	}
 
-	println!("second pass {}", from26(second));
-}
\ No newline at end of file
+	fn part2(&self) -> String {
+		let first = get_next(self.password);
+		from26(get_next(first+1))
+	}
+}
@@ -0,0 +1,169 @@
+use crate::solution::Solution;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+/// Just enough JSON for the accounting elves: integers, strings without
+/// escapes, arrays and objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Json {
+	Number(i64),
+	Str(String),
+	Array(Vec<Json>),
+	Object(Vec<(String, Json)>),
+}
+
+fn expect(chars: &mut Peekable<Chars>, want: char) -> Result<(), String> {
+	match chars.next() {
+		Some(c) if c == want => Ok(()),
+		Some(c) => Err(format!("expected {} but found {}", want, c)),
+		None => Err(format!("expected {} but the input ended", want)),
+	}
+}
+
+fn string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+	expect(chars, '"')?;
+	let mut s = String::new();
+	loop {
+		match chars.next() {
+			Some('"') => return Ok(s),
+			Some('\\') => return Err("escapes are not supported".to_string()),
+			Some(c) => s.push(c),
+			None => return Err("unterminated string".to_string()),
+		}
+	}
+}
+
+/// Reads a comma separated list up to `close`, with `item` reading each entry.
+fn list<T>(chars: &mut Peekable<Chars>, close: char, mut item: impl FnMut(&mut Peekable<Chars>) -> Result<T, String>) -> Result<Vec<T>, String> {
+	let mut items = Vec::new();
+	if chars.peek() == Some(&close) {
+		chars.next();
+		return Ok(items);
+	}
+	loop {
+		items.push(item(chars)?);
+		match chars.next() {
+			Some(',') => {}
+			Some(c) if c == close => return Ok(items),
+			Some(c) => return Err(format!("expected , or {} but found {}", close, c)),
+			None => return Err(format!("expected {} but the input ended", close)),
+		}
+	}
+}
+
+fn value(chars: &mut Peekable<Chars>) -> Result<Json, String> {
+	match chars.peek() {
+		Some('[') => {
+			chars.next();
+			list(chars, ']', value).map(Json::Array)
+		}
+		Some('{') => {
+			chars.next();
+			list(chars, '}', |chars| {
+				let key = string(chars)?;
+				expect(chars, ':')?;
+				Ok((key, value(chars)?))
+			}).map(Json::Object)
+		}
+		Some('"') => string(chars).map(Json::Str),
+		Some(c) if *c == '-' || c.is_ascii_digit() => {
+			let mut n = chars.next().unwrap().to_string();
+			while let Some(d) = chars.next_if(|c| c.is_ascii_digit()) {
+				n.push(d);
+			}
+			n.parse().map(Json::Number).map_err(|_| format!("not a number: {}", n))
+		}
+		Some(c) => Err(format!("unexpected {}", c)),
+		None => Err("the input ended early".to_string()),
+	}
+}
+
+impl FromStr for Json {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Json, String> {
+		let mut chars = s.trim().chars().peekable();
+		let json = value(&mut chars)?;
+		match chars.next() {
+			None => Ok(json),
+			Some(c) => Err(format!("unexpected {} after the end", c)),
+		}
+	}
+}
+
+impl Json {
+	/// Every number added up, skipping objects with a "red" value if asked.
+	fn sum(&self, skip_red: bool) -> i64 {
+		match self {
+			Json::Number(n) => *n,
+			Json::Str(_) => 0,
+			Json::Array(items) => items.iter().map(|i| i.sum(skip_red)).sum(),
+			Json::Object(fields) => {
+				if skip_red && fields.iter().any(|(_, v)| *v == Json::Str("red".to_string())) {
+					0
+				} else {
+					fields.iter().map(|(_, v)| v.sum(skip_red)).sum()
+				}
+			}
+		}
+	}
+}
+
+pub struct Day {
+	document: Json,
+}
+
+impl Solution for Day {
+	type Part1 = i64;
+	type Part2 = i64;
+
+	fn parse(input: &str) -> Self {
+		Day { document: input.parse().unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> i64 {
+		self.document.sum(false)
+	}
+
+	fn part2(&self) -> i64 {
+		self.document.sum(true)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn sum(s: &str, skip_red: bool) -> i64 {
+		s.parse::<Json>().unwrap().sum(skip_red)
+	}
+
+	#[test]
+	fn test_parse() {
+		let expected = Json::Object(vec![("a".to_string(), Json::Array(vec![Json::Number(-1), Json::Str("x".to_string())]))]);
+		assert_eq!(Ok(expected), r#"{"a":[-1,"x"]}"#.parse());
+		assert_eq!(Ok(Json::Array(vec![])), "[]".parse());
+		assert_eq!(Err("expected , or ] but found }".to_string()), "[1}".parse::<Json>());
+		assert_eq!(Err("unexpected ] after the end".to_string()), "[]]".parse::<Json>());
+	}
+
+	#[test]
+	fn test_sum() {
+		assert_eq!(6, sum("[1,2,3]", false));
+		assert_eq!(6, sum(r#"{"a":2,"b":4}"#, false));
+		assert_eq!(3, sum("[[[3]]]", false));
+		assert_eq!(3, sum(r#"{"a":{"b":4},"c":-1}"#, false));
+		assert_eq!(0, sum(r#"{"a":[-1,1]}"#, false));
+		assert_eq!(0, sum(r#"[-1,{"a":1}]"#, false));
+		assert_eq!(0, sum("{}", false));
+	}
+
+	#[test]
+	fn test_skip_red() {
+		assert_eq!(6, sum("[1,2,3]", true));
+		assert_eq!(4, sum(r#"[1,{"c":"red","b":2},3]"#, true));
+		assert_eq!(0, sum(r#"{"d":"red","e":[1,2,3,4],"f":5}"#, true));
+		assert_eq!(6, sum(r#"[1,"red",5]"#, true));
+		assert_eq!(11, sum(r#"{"a":[1,"red",5],"b":{"a":2,"b":[1,2,"red"]}}"#, true));
+	}
+}
@@ -0,0 +1,111 @@
+use crate::solution::Solution;
+use std::str::FromStr;
+
+const RACE: u32 = 2503;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Reindeer {
+	name: String,
+	speed: u32,
+	stamina: u32,
+	rest: u32,
+}
+
+/// Reads lines like "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds."
+impl FromStr for Reindeer {
+	type Err = String;
+
+	fn from_str(line: &str) -> Result<Reindeer, String> {
+		let words: Vec<&str> = line.split_whitespace().collect();
+		match words[..] {
+			[name, "can", "fly", speed, "km/s", "for", stamina, "seconds,", "but", "then", "must", "rest", "for", rest, "seconds."] => {
+				let n = |s: &str| s.parse().map_err(|_| format!("not a number: {}", s));
+				Ok(Reindeer { name: name.to_string(), speed: n(speed)?, stamina: n(stamina)?, rest: n(rest)? })
+			}
+			_ => Err(format!("unknown reindeer: {}", line)),
+		}
+	}
+}
+
+impl Reindeer {
+	/// How far it has flown after `seconds`, every burst of flying followed by a rest.
+	fn distance(&self, seconds: u32) -> u32 {
+		let cycle = self.stamina + self.rest;
+		let flying = seconds / cycle * self.stamina + (seconds % cycle).min(self.stamina);
+		flying * self.speed
+	}
+}
+
+/// The points each reindeer has after `seconds`, scoring one for every
+/// second it is in the lead, ties included.
+fn scores(herd: &[Reindeer], seconds: u32) -> Vec<u32> {
+	let mut scores = vec![0; herd.len()];
+	for t in 1..=seconds {
+		let distances: Vec<u32> = herd.iter().map(|r| r.distance(t)).collect();
+		let lead = *distances.iter().max().unwrap();
+		for (score, d) in scores.iter_mut().zip(distances) {
+			if d == lead {
+				*score += 1;
+			}
+		}
+	}
+	scores
+}
+
+pub struct Day {
+	herd: Vec<Reindeer>,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		let herd = input.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).map(|l| l.parse()).collect::<Result<_, _>>();
+		Day { herd: herd.unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> u32 {
+		self.herd.iter().map(|r| r.distance(RACE)).max().unwrap()
+	}
+
+	fn part2(&self) -> u32 {
+		scores(&self.herd, RACE).into_iter().max().unwrap()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn herd(speeds: [u32; 2]) -> Vec<Reindeer> {
+		let comet = format!("Comet can fly {} km/s for 10 seconds, but then must rest for 127 seconds.", speeds[0]);
+		let dancer = format!("Dancer can fly {} km/s for 11 seconds, but then must rest for 162 seconds.", speeds[1]);
+		vec![comet.parse().unwrap(), dancer.parse().unwrap()]
+	}
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(Reindeer { name: "Comet".to_string(), speed: 14, stamina: 10, rest: 127 }, herd([14, 16])[0]);
+		assert_eq!(Err("unknown reindeer: Comet can run".to_string()), "Comet can run".parse::<Reindeer>());
+	}
+
+	#[test]
+	fn test_distance() {
+		let herd = herd([14, 16]);
+		assert_eq!((14, 16), (herd[0].distance(1), herd[1].distance(1)));
+		assert_eq!((140, 176), (herd[0].distance(11), herd[1].distance(11)));
+		assert_eq!((1120, 1056), (herd[0].distance(1000), herd[1].distance(1000)));
+		let slow: Reindeer = "Comet can fly 2 km/s for 1 seconds, but then must rest for 9 seconds.".parse().unwrap();
+		assert_eq!(vec![2, 2, 2, 4], [1, 2, 10, 11].map(|t| slow.distance(t)).to_vec());
+	}
+
+	#[test]
+	fn test_scores() {
+		assert_eq!(vec![0, 1], scores(&herd([14, 16]), 1));
+		assert_eq!(vec![1, 139], scores(&herd([14, 16]), 140));
+		assert_eq!(vec![312, 689], scores(&herd([14, 16]), 1000));
+		// both lead when tied
+		assert_eq!(vec![1, 1], scores(&herd([14, 14]), 1));
+	}
+}
@@ -1,26 +1,28 @@
+use crate::solution::Solution;
 
-pub fn main() {
-	{
-		let mut max = 0i64;
-
-		for s in (0..=100).rev() {
-		for p in (0..=100-s).rev() {
-		for f in (0..=100-s-p).rev() {
-		for u in (0..=100-s-p-f).rev() {
-			let cap = s*5 + p*-1 + u*-1;
-			if cap < 0 { break; }
-			let dur = s*-1 + p*3 + f*-1;
-			if dur < 0 { break; }
-			let fla = f*4;
-			let tex = u*2;
-			let score = cap*dur*fla*tex;
-			if score > max
-			{
-				max = score;
-			}
-		}}}}
-		println!("sc {}",max);
-	}
+fn best_score() -> i64 {
+	let mut max = 0i64;
+
+	for s in (0..=100).rev() {
+	for p in (0..=100-s).rev() {
+	for f in (0..=100-s-p).rev() {
+	for u in (0..=100-s-p-f).rev() {
+		let cap = s*5 + p*-1 + u*-1;
+		if cap < 0 { break; }
+		let dur = s*-1 + p*3 + f*-1;
+		if dur < 0 { break; }
+		let fla = f*4;
+		let tex = u*2;
+		let score = cap*dur*fla*tex;
+		if score > max
+		{
+			max = score;
+		}
+	}}}}
+	max
+}
+
+fn best_score_at_500_calories() -> i64 {
 	let mut max = 0i64;
 
 	for s in (0..=100).rev() {
@@ -41,6 +43,24 @@ pub fn main() {
 			max = score;
 		}
 	}}}}
-	println!("sc {}",max);
+	max
 }
 
+pub struct Day;
+
+impl Solution for Day {
+	type Part1 = i64;
+	type Part2 = i64;
+
+	fn parse(_input: &str) -> Self {
+		Day
+	}
+
+	fn part1(&self) -> i64 {
+		best_score()
+	}
+
+	fn part2(&self) -> i64 {
+		best_score_at_500_calories()
+	}
+}
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 
 fn find( sum:u32, target:u32, arr:&[u32] ) -> u32 {
 	let mut num_found = 0u32;
@@ -48,6 +49,35 @@ fn find_min( sum:u32, target:u32, depth:u32, min_found:u32, arr:&[u32] ) -> (u32
 	(new_min,num_found)
 }
 
+fn parse(input: &str) -> Vec<u32> {
+	let mut arr: Vec<u32> = input.lines().map(|l| l.parse().unwrap()).collect();
+	arr.sort();
+	arr
+}
+
+pub struct Day {
+	containers: Vec<u32>,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		Day { containers: parse(input) }
+	}
+
+	fn part1(&self) -> u32 {
+		find(0,150,&self.containers)
+	}
+
+	fn part2(&self) -> u32 {
+		let (_,count) = find_min(0,150,0,self.containers.len() as u32,&self.containers);
+		count
+	}
+}
+
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,110 +1,93 @@
+use crate::grid::Grid;
+use crate::solution::Solution;
+
 struct State {
-	field: Vec<bool>,
-	size: usize,
+	field: Grid<bool>,
 	alwayson: bool,
 }
 
 impl State {
 	fn new(data: &str, size: usize, alwayson: bool) -> State {
-		let clean: Vec<u8> = data.replace("\n", "").into_bytes();
-		let mut state = State{ field:vec![false; clean.len()], size, alwayson };
-		for (i, b) in clean.iter().enumerate() {
-			state.field[i] = *b != '.' as u8;
-		}
+		let cells = data.chars().filter(|c| !c.is_whitespace()).map(|c| c != '.').collect();
+		let mut state = State{ field: Grid::from_cells(size, cells), alwayson };
 		state.light_corners();
 		state
 	}
 
 	fn tick(&mut self) {
-		let neighbours:Vec<u8> = (0..self.field.len()).map( |ix| {
-			let (x,y) = self.get_coord(ix);
+		let neighbours = Grid::from_cells(self.field.width(), self.field.positions().map( |(x,y)| {
 			self.count_neighbours(x,y)
-		}).collect();
+		}).collect());
 
-		for (ix,n) in neighbours.iter().enumerate() {
-			if self.field[ix] {
-				self.field[ix] = *n==2u8 || *n == 3u8;
+		for (pos,n) in neighbours.enumerate() {
+			let light = &mut self.field[pos];
+			if *light {
+				*light = *n==2u8 || *n == 3u8;
 			}
 			else
 			{
-				self.field[ix] = *n == 3u8;
+				*light = *n == 3u8;
 			}
 		}
 		self.light_corners();
 	}
 
 	fn as_str(&self) -> String {
-		let dot = '.' as u8;
-		let on = 'o' as u8;
-		let mut strbytes = vec![dot;self.field.len()];
-		for (i,b) in self.field.iter().enumerate() {
-			if *b {
-				strbytes[i] = on;
-			}
-		}
-		String::from_utf8(strbytes).unwrap()
+		self.field.iter().map(|b| if *b { 'o' } else { '.' }).collect()
 	}
 
 	fn count_lights(&self) -> usize {
-		let filtered : Vec<&bool> = self.field.iter().filter( |b| **b ).collect();
-		filtered.len()
+		self.field.iter().filter( |b| **b ).count()
 	}
 
 	fn light_corners(&mut self) {
 		if self.alwayson {
-			let endix = self.size-1;
-			self.field[0] = true;
-			self.field[endix] = true;
-			self.field[endix*self.size] = true;
-			self.field[endix*self.size+endix] = true;
+			let endx = self.field.width()-1;
+			let endy = self.field.height()-1;
+			self.field[(0,0)] = true;
+			self.field[(endx,0)] = true;
+			self.field[(0,endy)] = true;
+			self.field[(endx,endy)] = true;
 		}
 	}
 
-	fn get_index(&self,x:usize,y:usize) -> usize {
-		y*self.size+x
+	fn count_neighbours(&self, x: usize, y:usize ) -> u8 {
+		self.field.neighbours8(x,y).filter(|p| self.field[*p]).count() as u8
+	}
+}
+
+pub struct Day {
+	grid: String,
+}
+
+impl Day {
+	fn animate(&self, stuck: bool) -> usize {
+		let mut data = State::new(&self.grid,100,stuck);
+		for _ in 0..100 {
+			data.tick()
+		}
+		data.count_lights()
 	}
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
 
-	fn get_coord(&self,index:usize) -> (usize,usize) {
-		(
-			index % self.size,
-			index / self.size,
-		)
+	fn parse(input: &str) -> Self {
+		Day { grid: input.trim().to_string() }
 	}
 
-	fn is_on(&self,x:usize, y:usize) -> bool {
-		self.field[self.get_index(x,y)]
+	fn part1(&self) -> usize {
+		self.animate(false)
 	}
-	fn count_neighbours(&self, x: usize, y:usize ) -> u8 {
-		let mut count = 0u8;
-		let size = self.size-1;
-		if x > 0 && y > 0 && self.is_on(x-1,y-1) {
-			count += 1
-		}
-		if y > 0 && self.is_on(x,y-1) {
-			count += 1
-		}
-		if x < size && y > 0 && self.is_on(x+1,y-1) {
-			count += 1
-		}
-		if x > 0 && self.is_on(x-1,y) {
-			count += 1
-		}
-		if x < size && self.is_on(x+1,y) {
-			count += 1
-		}
-		if  x > 0 && y < size && self.is_on(x-1,y+1) {
-			count += 1
-		}
-		if  y < size && self.is_on(x,y+1) {
-			count += 1
-		}
-		if  x < size && y < size && self.is_on(x+1,y+1) {
-			count += 1
-		}
-		count
+
+	fn part2(&self) -> usize {
+		self.animate(true)
 	}
 }
 
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -135,16 +118,11 @@ mod tests {
 	#[test]
 	fn test_getcoord() {
 		let data = State::new(INPUT_DATA,100,false);
+		let coords: Vec<(usize,usize)> = data.field.positions().collect();
 
-		let (x,y) = data.get_coord(0);
-		assert_eq!(0,x);
-		assert_eq!(0,y);
-		let (x,y) = data.get_coord(99);
-		assert_eq!(99,x);
-		assert_eq!(0,y);
-		let (x,y) = data.get_coord(100);
-		assert_eq!(0,x);
-		assert_eq!(1,y);
+		assert_eq!((0,0),coords[0]);
+		assert_eq!((99,0),coords[99]);
+		assert_eq!((0,1),coords[100]);
 	}
 
 	#[test]
@@ -155,8 +133,8 @@ o....o
 ..o...
 o.o..o
 oooo..",6,false);
-		assert!(data.field[1]);
-		assert!(!data.field[6]);
+		assert!(data.field[(1,0)]);
+		assert!(!data.field[(0,1)]);
 	}
 
 	#[test]
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 fn parse<'a>(s: &'a str) -> Vec<(&'a str, &'a str)> {
@@ -49,18 +50,37 @@ fn run(rules: &str, data: &str) -> Vec<String> {
 	keys
 }
 
-fn main() {
-	let rules = std::fs::read_to_string("19-rules.txt").unwrap();
-	let data = std::fs::read_to_string("19.txt").unwrap();
-	let map = run(&rules.replace("\r\n", "\n"), &data);
-	println!("unique combinations: {}",map.len());
+fn split_input(input: &str) -> (&str, &str) {
+	let (rules, data) = input.split_once("\n\n").unwrap();
+	(rules, data.trim())
+}
+
+pub struct Day {
+	rules: String,
+	molecule: String,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = i32;
+
+	fn parse(input: &str) -> Self {
+		let (rules, molecule) = split_input(input);
+		Day { rules: rules.to_string(), molecule: molecule.to_string() }
+	}
+
+	fn part1(&self) -> usize {
+		run(&self.rules, &self.molecule).len()
+	}
 
-	let simplified_rule_set = get_rules();
-	let mut tokenized_data = tokenize(&data);
-	let opcount = run_length_pass(&simplified_rule_set,&mut tokenized_data);
-	println!("operations needed: {}",opcount);
+	fn part2(&self) -> i32 {
+		let simplified_rule_set = get_rules();
+		let mut tokenized_data = tokenize(&self.molecule);
+		run_length_pass(&simplified_rule_set,&mut tokenized_data)
+	}
 }
 
+
 // rule set:
 // convert any atoms not Rn Ar or Y into _
 // _ => __
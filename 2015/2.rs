@@ -0,0 +1,61 @@
+use crate::solution::Solution;
+
+/// The sides of a present, smallest first.
+fn sides(line: &str) -> [u32; 3] {
+	let mut sides: Vec<u32> = line.split('x').map(|n| n.trim().parse().unwrap()).collect();
+	sides.sort();
+	[sides[0], sides[1], sides[2]]
+}
+
+/// Every side's area, plus the smallest side again as slack.
+fn paper([a, b, c]: [u32; 3]) -> u32 {
+	2 * (a * b + a * c + b * c) + a * b
+}
+
+/// Around the smallest face, and as much again as the volume for the bow.
+fn ribbon([a, b, c]: [u32; 3]) -> u32 {
+	2 * (a + b) + a * b * c
+}
+
+pub struct Day {
+	presents: Vec<[u32; 3]>,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		Day { presents: input.lines().filter(|l| !l.trim().is_empty()).map(sides).collect() }
+	}
+
+	fn part1(&self) -> u32 {
+		self.presents.iter().map(|p| paper(*p)).sum()
+	}
+
+	fn part2(&self) -> u32 {
+		self.presents.iter().map(|p| ribbon(*p)).sum()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_sides() {
+		assert_eq!([13, 26, 29], sides("29x13x26"));
+	}
+
+	#[test]
+	fn test_paper() {
+		assert_eq!(58, paper(sides("2x3x4")));
+		assert_eq!(43, paper(sides("1x1x10")));
+	}
+
+	#[test]
+	fn test_ribbon() {
+		assert_eq!(34, ribbon(sides("2x3x4")));
+		assert_eq!(14, ribbon(sides("1x1x10")));
+	}
+}
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 
 fn elfwalk(upto:usize) -> Vec<u64> {
 	let mut houses = vec![10u64;upto];
@@ -33,6 +34,28 @@ fn findfirst(houses:Vec<u64>,limit:u64) -> usize {
 	0
 }
 
+pub struct Day {
+	limit: u64,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { limit: input.trim().parse().unwrap() }
+	}
+
+	fn part1(&self) -> usize {
+		findfirst(elfwalk(self.limit as usize / 10),self.limit)
+	}
+
+	fn part2(&self) -> usize {
+		findfirst(lazyelfwalk(self.limit as usize / 10),self.limit)
+	}
+}
+
+
 #[cfg(test)]
 mod tests {
 	use super::*;
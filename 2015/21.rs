@@ -1,3 +1,31 @@
+use crate::solution::Solution;
+use std::str::FromStr;
+
+const PLAYER_HP: i32 = 100;
+
+const SHOP: &str = "Weapons:    Cost  Damage  Armor
+Dagger        8     4       0
+Shortsword   10     5       0
+Warhammer    25     6       0
+Longsword    40     7       0
+Greataxe     74     8       0
+
+Armor:      Cost  Damage  Armor
+Leather      13     0       1
+Chainmail    31     0       2
+Splintmail   53     0       3
+Bandedmail   75     0       4
+Platemail   102     0       5
+
+Rings:      Cost  Damage  Armor
+Damage +1    25     1       0
+Damage +2    50     2       0
+Damage +3   100     3       0
+Defense +1   20     0       1
+Defense +2   40     0       2
+Defense +3   80     0       3
+";
+
 pub fn attac(damage: i32, armor: i32) -> i32 {
 	let hit = damage - armor;
 	if hit < 1 {
@@ -7,92 +35,193 @@ pub fn attac(damage: i32, armor: i32) -> i32 {
 	}
 }
 
-pub fn battle(damage: i32, armor: i32) -> bool {
-	let mut player_hp = 100i32;
-	let mut boss_hp = 103i32;
-	let boss_damage = 9i32;
-	let boss_armor = 2i32;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fighter {
+	hp: i32,
+	damage: i32,
+	armor: i32,
+}
 
-	loop {
-		boss_hp -= attac(damage, boss_armor);
+impl FromStr for Fighter {
+	type Err = String;
 
-		if boss_hp <= 0 {
-			return true;
+	fn from_str(input: &str) -> Result<Fighter, String> {
+		let mut stats = [None; 3];
+		for line in input.lines().filter(|l| !l.trim().is_empty()) {
+			let (stat, value) = line.split_once(": ").ok_or_else(|| format!("expected \"stat: value\": {}", line))?;
+			let value = value.trim().parse().map_err(|_| format!("not a number: {}", line))?;
+			let ix = ["Hit Points", "Damage", "Armor"].iter().position(|s| *s == stat).ok_or_else(|| format!("unknown stat: {}", stat))?;
+			stats[ix] = Some(value);
 		}
-
-		player_hp -= attac(boss_damage, armor);
-
-		if player_hp <= 0 {
-			return false;
+		match stats {
+			[Some(hp), Some(damage), Some(armor)] => Ok(Fighter { hp, damage, armor }),
+			_ => Err("expected Hit Points, Damage and Armor".to_string()),
 		}
 	}
 }
 
-type Item = (i32, i32, i32);
+/// How many hits it takes the attacker to bring the defender down.
+pub fn turns_to_kill(attacker: &Fighter, defender: &Fighter) -> i32 {
+	let hit = attac(attacker.damage, defender.armor);
+	(defender.hp + hit - 1) / hit
+}
 
-fn get_weapons() -> Vec<Item> {
-	vec![
-		(8, 4, 0),
-		(10, 5, 0),
-		(25, 6, 0),
-		(40, 7, 0),
-		(74, 8, 0),
-	]
+/// The player strikes first, so wins if both need as many hits.
+pub fn battle(player: &Fighter, boss: &Fighter) -> bool {
+	turns_to_kill(player, boss) <= turns_to_kill(boss, player)
 }
 
-fn get_armor() -> Vec<Item> {
-	vec![
-		(0, 0, 0), // armor is optional
-		(13, 0, 1),
-		(31, 0, 2),
-		(53, 0, 3),
-		(75, 0, 4),
-		(102, 0, 5),
-	]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Item {
+	name: String,
+	cost: i32,
+	damage: i32,
+	armor: i32,
 }
 
-fn get_rings() -> Vec<Item> {
-	vec![
-		(0, 0, 0), // can have zero, one
-		(0, 0, 0), // or two rings
-		(25, 1, 0),
-		(50, 2, 0),
-		(100, 3, 0),
-		(20, 0, 1),
-		(40, 0, 2),
-		(80, 0, 3),
-	]
+pub struct Shop {
+	weapons: Vec<Item>,
+	armor: Vec<Item>,
+	rings: Vec<Item>,
 }
 
-fn get_gear(evil_shop: bool, weapons: &Vec<Item>, armor: &Vec<Item>, rings: &Vec<Item>) -> i32 {
-	let mut boundary = if evil_shop { 0 } else { 300 };
-
-	for w in 0..weapons.len() {
-		for a in 0..armor.len() {
-			for r1 in 0..rings.len() {
-				for r2 in (r1 + 1)..rings.len() {
-					let cost = weapons[w].0 + armor[a].0 + rings[r1].0 + rings[r2].0;
-					if evil_shop && cost <= boundary || !evil_shop && cost >= boundary {
-						continue;
-					}
-					let battle = battle(
-						weapons[w].1 + rings[r1].1 + rings[r2].1,
-						armor[a].2 + rings[r1].2 + rings[r2].2,
-					);
-					if battle && !evil_shop || !battle && evil_shop {
-						boundary = cost;
-					}
+/// Reads a table of items in sections headed "Weapons:", "Armor:" and
+/// "Rings:", each line a name followed by cost, damage and armor.
+impl FromStr for Shop {
+	type Err = String;
+
+	fn from_str(input: &str) -> Result<Shop, String> {
+		let mut shop = Shop { weapons: Vec::new(), armor: Vec::new(), rings: Vec::new() };
+		let mut section: Option<&mut Vec<Item>> = None;
+		for line in input.lines().filter(|l| !l.trim().is_empty()) {
+			if let Some((heading, _)) = line.split_once(':') {
+				section = Some(match heading {
+					"Weapons" => &mut shop.weapons,
+					"Armor" => &mut shop.armor,
+					"Rings" => &mut shop.rings,
+					_ => return Err(format!("unknown kind of item: {}", heading)),
+				});
+				continue;
+			}
+			let items = section.as_mut().ok_or_else(|| format!("item before any heading: {}", line))?;
+			let words: Vec<&str> = line.split_whitespace().collect();
+			if words.len() < 4 {
+				return Err(format!("expected a name, cost, damage and armor: {}", line));
+			}
+			let (name, numbers) = words.split_at(words.len() - 3);
+			let numbers = numbers.iter().map(|n| n.parse()).collect::<Result<Vec<i32>, _>>().map_err(|_| format!("not a number: {}", line))?;
+			items.push(Item { name: name.join(" "), cost: numbers[0], damage: numbers[1], armor: numbers[2] });
+		}
+		Ok(shop)
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct Loadout<'a> {
+	items: Vec<&'a Item>,
+	cost: i32,
+	damage: i32,
+	armor: i32,
+}
+
+impl<'a> Loadout<'a> {
+	fn new(items: Vec<&'a Item>) -> Loadout<'a> {
+		Loadout {
+			cost: items.iter().map(|i| i.cost).sum(),
+			damage: items.iter().map(|i| i.damage).sum(),
+			armor: items.iter().map(|i| i.armor).sum(),
+			items,
+		}
+	}
+
+	/// The names of the items, weapon first.
+	pub fn names(&self) -> Vec<&str> {
+		self.items.iter().map(|i| i.name.as_str()).collect()
+	}
+
+	pub fn fighter(&self, hp: i32) -> Fighter {
+		Fighter { hp, damage: self.damage, armor: self.armor }
+	}
+}
+
+impl Shop {
+	/// Everything the shopkeeper allows: one weapon, at most one armor and
+	/// at most two different rings.
+	pub fn loadouts(&self) -> Vec<Loadout<'_>> {
+		let mut armor: Vec<Vec<&Item>> = vec![vec![]];
+		armor.extend(self.armor.iter().map(|a| vec![a]));
+		let mut rings: Vec<Vec<&Item>> = vec![vec![]];
+		for (i, r1) in self.rings.iter().enumerate() {
+			rings.push(vec![r1]);
+			rings.extend(self.rings[i + 1..].iter().map(|r2| vec![r1, r2]));
+		}
+		let mut loadouts = Vec::new();
+		for w in &self.weapons {
+			for a in &armor {
+				for r in &rings {
+					let mut items = vec![w];
+					items.extend(a);
+					items.extend(r);
+					loadouts.push(Loadout::new(items));
 				}
 			}
 		}
+		loadouts
+	}
+
+	/// Every loadout, and whether a player with `hp` wearing it beats the boss.
+	pub fn outcomes(&self, hp: i32, boss: &Fighter) -> Vec<(Loadout<'_>, bool)> {
+		self.loadouts().into_iter().map(|l| {
+			let win = battle(&l.fighter(hp), boss);
+			(l, win)
+		}).collect()
+	}
+
+	fn cheapest_win(&self, hp: i32, boss: &Fighter) -> Option<i32> {
+		self.outcomes(hp, boss).iter().filter(|(_, win)| *win).map(|(l, _)| l.cost).min()
+	}
+
+	fn priciest_loss(&self, hp: i32, boss: &Fighter) -> Option<i32> {
+		self.outcomes(hp, boss).iter().filter(|(_, win)| !*win).map(|(l, _)| l.cost).max()
+	}
+}
+
+pub struct Day {
+	shop: Shop,
+	boss: Fighter,
+}
+
+impl Solution for Day {
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &str) -> Self {
+		Day {
+			shop: SHOP.parse().unwrap(),
+			boss: input.parse().unwrap_or_else(|e| panic!("{}", e)),
+		}
+	}
+
+	fn part1(&self) -> i32 {
+		self.shop.cheapest_win(PLAYER_HP, &self.boss).unwrap()
+	}
+
+	fn part2(&self) -> i32 {
+		self.shop.priciest_loss(PLAYER_HP, &self.boss).unwrap()
 	}
-	return boundary
 }
 
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	const BOSS: Fighter = Fighter { hp: 103, damage: 9, armor: 2 };
+
+	fn player(damage: i32, armor: i32) -> Fighter {
+		Fighter { hp: PLAYER_HP, damage, armor }
+	}
+
 	#[test]
 	fn test_attac() {
 		assert_eq!(5, attac(8, 3));
@@ -101,36 +230,63 @@ mod tests {
 
 	#[test]
 	fn test_battle() {
-		assert!(battle(100, 3));
-		assert!(!battle(0, 0));
-		assert!(!battle(3,8));
+		assert!(battle(&player(100, 3), &BOSS));
+		assert!(!battle(&player(0, 0), &BOSS));
+		assert!(!battle(&player(3, 8), &BOSS));
+	}
+
+	#[test]
+	fn test_turns_to_kill() {
+		let player = Fighter { hp: 8, damage: 5, armor: 5 };
+		let boss = Fighter { hp: 12, damage: 7, armor: 2 };
+		assert_eq!(4, turns_to_kill(&player, &boss));
+		assert_eq!(4, turns_to_kill(&boss, &player));
+		assert!(battle(&player, &boss));
+	}
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(Ok(BOSS), "Hit Points: 103\nDamage: 9\nArmor: 2\n".parse());
+		assert_eq!(Err("unknown stat: Mana".to_string()), "Mana: 3".parse::<Fighter>());
+		let shop: Shop = SHOP.parse().unwrap();
+		assert_eq!((5, 5, 6), (shop.weapons.len(), shop.armor.len(), shop.rings.len()));
+		assert_eq!(Item { name: "Defense +3".to_string(), cost: 80, damage: 0, armor: 3 }, shop.rings[5]);
+		assert_eq!(Err("unknown kind of item: Potions".to_string()), "Potions:\nHealing 10 0 0".parse::<Shop>().map(|_| ()));
+		assert_eq!(Err("item before any heading: Dagger 8 4 0".to_string()), "Dagger 8 4 0".parse::<Shop>().map(|_| ()));
+	}
+
+	#[test]
+	fn test_loadouts() {
+		let shop: Shop = SHOP.parse().unwrap();
+		let loadouts = shop.loadouts();
+		// 5 weapons, 6 armor choices and 22 ring choices
+		assert_eq!(660, loadouts.len());
+		let priciest = loadouts.iter().max_by_key(|l| l.cost).unwrap();
+		assert_eq!(vec!["Greataxe", "Platemail", "Damage +3", "Defense +3"], priciest.names());
+		let outcomes = shop.outcomes(PLAYER_HP, &BOSS);
+		assert_eq!(660, outcomes.len());
+		assert!(outcomes.iter().any(|(_, win)| *win) && outcomes.iter().any(|(_, win)| !*win));
 	}
 
 	#[test]
 	fn test_getgear_something_lose() {
-		let weapons = vec![(0,0,0)];
-		let armor = vec![(0,0,0)];
-		let rings = vec![(0,0,0),(0,0,0),(100,3,0),(300,30,0)];
+		let shop: Shop = "Weapons:\nStick 0 0 0\nRings:\nSmall 100 3 0\nBig 300 30 0".parse().unwrap();
 
-		assert_eq!(100, get_gear(true,&weapons,&armor,&rings));
+		assert_eq!(Some(100), shop.priciest_loss(PLAYER_HP, &BOSS));
 	}
 
 	#[test]
 	fn test_getgear_nothing_lose() {
-		let weapons = vec![(0,0,0)];
-		let armor = vec![(0,0,0)];
-		let rings = vec![(0,0,0),(0,0,0)];
+		let shop: Shop = "Weapons:\nStick 0 0 0".parse().unwrap();
 
-		assert_eq!(0, get_gear(true,&weapons,&armor,&rings));
+		assert_eq!(Some(0), shop.priciest_loss(PLAYER_HP, &BOSS));
 	}
 
 	#[test]
 	fn test_getgear() {
-		let weapons = get_weapons();
-		let armor = get_armor();
-		let rings = get_rings();
+		let shop: Shop = SHOP.parse().unwrap();
 
-		assert_eq!(121, get_gear(false,&weapons, &armor, &rings));
-		assert_eq!(201, get_gear(true, &weapons, &armor, &rings));
+		assert_eq!(Some(121), shop.cheapest_win(PLAYER_HP, &BOSS));
+		assert_eq!(Some(201), shop.priciest_loss(PLAYER_HP, &BOSS));
 	}
 }
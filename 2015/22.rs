@@ -1,3 +1,55 @@
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+enum Spell {
+	Missile,
+	Drain,
+	Shield,
+	Poison,
+	Recharge,
+}
+
+const SPELLS: [Spell; 5] = [Spell::Missile, Spell::Drain, Spell::Shield, Spell::Poison, Spell::Recharge];
+
+impl std::fmt::Display for Spell {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let name = match self {
+			Spell::Missile => "Magic Missile",
+			Spell::Drain => "Drain",
+			Spell::Shield => "Shield",
+			Spell::Poison => "Poison",
+			Spell::Recharge => "Recharge",
+		};
+		write!(f, "{}", name)
+	}
+}
+
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+struct Boss {
+	hp: i32,
+	damage: i32,
+}
+
+impl FromStr for Boss {
+	type Err = String;
+
+	fn from_str(input: &str) -> Result<Boss,String> {
+		let mut hp = None;
+		let mut damage = None;
+		for line in input.lines().filter(|l| !l.trim().is_empty()) {
+			let (stat,value) = line.split_once(": ").ok_or_else(|| format!("expected \"stat: value\": {}", line))?;
+			let value = value.trim().parse().map_err(|_| format!("not a number: {}", line))?;
+			match stat {
+				"Hit Points" => hp = Some(value),
+				"Damage" => damage = Some(value),
+				_ => return Err(format!("unknown boss stat: {}", stat)),
+			}
+		}
+		Ok(Boss { hp: hp.ok_or("no Hit Points for the boss")?, damage: damage.ok_or("no Damage for the boss")? })
+	}
+}
 
 #[derive(Clone,Debug)]
 struct State {
@@ -9,61 +61,117 @@ struct State {
 	poison: i32,
 	recharge: i32,
 	spent: i32,
+	/// What happened, when playing out a battle to look at.
+	log: Option<Vec<String>>,
 }
 
 type PlayerWin = bool;
 
-impl State {
-	fn simulate(&mut self, hard:bool) -> Option<i32> {
-		let mut r = 100000;
-
-		for next_choice in 0..=4 {
-			if let Some(result) = self.whatif(next_choice,r,hard) {
-				if result < r {
-					r = result;
-				}
+/// Adds a line to the battle log, if one is being kept.
+fn note(log: &mut Option<Vec<String>>, line: impl FnOnce() -> String) {
+	if let Some(log) = log {
+		log.push(line());
+	}
+}
+
+/// Looks for the cheapest win depth first, giving up on branches that
+/// already cost as much as the best win so far, or that reach a state seen
+/// before for less mana.
+struct Search {
+	hard: bool,
+	best: Option<(i32,Vec<Spell>)>,
+	seen: HashMap<[i32; 6],i32>,
+	spells: Vec<Spell>,
+}
+
+impl Search {
+	fn explore(&mut self, state: &State) {
+		for spell in SPELLS {
+			let mut next = state.clone();
+			let result = next.do_choice(spell, self.hard);
+			let bound = self.best.as_ref().map_or(i32::MAX, |b| b.0);
+			if result == Some(false) || next.spent >= bound {
+				continue;
+			}
+			self.spells.push(spell);
+			if result == Some(true) {
+				self.best = Some((next.spent, self.spells.clone()));
+			} else if self.seen.get(&next.key()).is_none_or(|&spent| next.spent < spent) {
+				self.seen.insert(next.key(), next.spent);
+				self.explore(&next);
 			}
+			self.spells.pop();
 		}
-		Some(r)
 	}
+}
 
-	fn choice_is_valid(&mut self, choice: i8) -> bool {
-		if self.shield > 1 && choice == 2 {
-			false
-		}
-		else if self.recharge > 1 && choice == 4 {
-			false
-		}
-		else if self.poison > 1 && choice == 3 {
-			false
-		}
-		else {
-			true
+impl State {
+	/// The mana spent and spells cast in the cheapest win.
+	fn cheapest_win(&self, hard: bool) -> Option<(i32,Vec<Spell>)> {
+		let mut search = Search { hard, best: None, seen: HashMap::new(), spells: Vec::new() };
+		search.explore(self);
+		search.best
+	}
+
+	/// Plays the spells out from this state, telling what happens turn by turn.
+	fn battle_log(&self, spells: &[Spell], hard: bool) -> String {
+		let mut s = State { log: Some(Vec::new()), ..self.clone() };
+		for spell in spells {
+			if s.do_choice(*spell, hard).is_some() {
+				break;
+			}
 		}
+		s.log.unwrap().join("\n")
+	}
+
+	/// Everything but the mana spent, which is all that can differ between
+	/// two ways to the same point in a battle.
+	fn key(&self) -> [i32; 6] {
+		[self.boss_hp, self.hp, self.mana, self.shield, self.poison, self.recharge]
+	}
+
+	fn status(&mut self, whose: &str) {
+		let armor = if self.shield > 0 { 7 } else { 0 };
+		note(&mut self.log, || format!("-- {} turn --", whose));
+		note(&mut self.log, || format!("- Player has {} hit points, {} armor, {} mana", self.hp, armor, self.mana));
+		note(&mut self.log, || format!("- Boss has {} hit points", self.boss_hp));
+	}
+
+	fn choice_is_valid(&mut self, choice: Spell) -> bool {
+		!(self.shield > 1 && choice == Spell::Shield
+			|| self.recharge > 1 && choice == Spell::Recharge
+			|| self.poison > 1 && choice == Spell::Poison)
 	}
 
 	fn handle_effects(&mut self) {
 		if self.shield > 0 {
 			self.shield -= 1;
+			note(&mut self.log, || format!("Shield's timer is now {}.", self.shield));
 		}
 		if self.recharge > 0 {
 			self.mana += 101;
 			self.recharge -= 1;
+			note(&mut self.log, || format!("Recharge provides 101 mana; its timer is now {}.", self.recharge));
 		}
 		if self.poison > 0 {
 			self.poison -= 1;
 			self.boss_hp -= 3;
+			note(&mut self.log, || format!("Poison deals 3 damage; its timer is now {}.", self.poison));
 		}
 	}
 
-	fn do_choice(&mut self, choice: i8, hard: bool) -> Option<PlayerWin> {
+	fn do_choice(&mut self, choice: Spell, hard: bool) -> Option<PlayerWin> {
+		self.status("Player");
 		if ! self.choice_is_valid(choice) {
+			note(&mut self.log, || format!("Player can't cast {} while it is still active.", choice));
 			return Some(false)
 		}
 
 		if hard {
 			self.hp -= 1;
+			note(&mut self.log, || "Player loses 1 hit point.".to_string());
 			if self.hp <= 0 {
+				note(&mut self.log, || "This kills the player, and the boss wins.".to_string());
 				return Some(false)
 			}
 		}
@@ -71,52 +179,30 @@ impl State {
 		self.handle_effects();
 
 		if self.boss_hp <= 0 {
+			note(&mut self.log, || "This kills the boss, and the player wins.".to_string());
 			return Some(true)
 		}
 
 		let result = match choice {
-			4 => self.recharge(),
-			1 => self.drain(),
-			2 => self.shield(),
-			3 => self.poison(),
-			0 => self.missile(),
-			_ => panic!("bad choice"),
+			Spell::Recharge => self.recharge(),
+			Spell::Drain => self.drain(),
+			Spell::Shield => self.shield(),
+			Spell::Poison => self.poison(),
+			Spell::Missile => self.missile(),
 		};
 
-		if let Some(win) = result {
-			return Some(win)
+		match result {
+			Some(false) => note(&mut self.log, || format!("Player can't afford {}.", choice)),
+			_ => note(&mut self.log, || format!("Player casts {}.", choice)),
 		}
-
-		self.boss_turn()
-	}
-
-	fn whatif(&mut self, choice: i8, best: i32, hard: bool) -> Option<i32> {
-		let mut next = self.clone();
-
-		let result = next.do_choice(choice, hard);
-
 		if let Some(win) = result {
-			if win && next.spent < best {
-				return Some(next.spent)
-			}
-			else {
-				return None
+			if win {
+				note(&mut self.log, || "This kills the boss, and the player wins.".to_string());
 			}
+			return Some(win)
 		}
-		if next.spent >= best {
-			return None
-		}
-
-		let mut r = best;
 
-		for next_choice in 0..=4 {
-			if let Some(result) = next.whatif(next_choice,r,hard) {
-				if result < r {
-					r = result;
-				}
-			}
-		}
-		Some(r)
+		self.boss_turn()
 	}
 
 	fn missile(&mut self) -> Option<PlayerWin> {
@@ -181,18 +267,20 @@ impl State {
 	}
 
 	fn boss_turn(&mut self) -> Option<PlayerWin> {
-		if self.shield == 1 {
-			panic!("shield should never have this state here")
-		}
-		if self.poison == 1 {
-			panic!("poison should never have this state here")
-		}
+		// every player turn already ticked the effects down once, and they
+		// all start out even, so none can be about to run out here
+		debug_assert!(self.shield != 1 && self.poison != 1, "an effect ends on the boss turn");
+		self.status("Boss");
 		self.handle_effects();
 		if self.boss_hp <= 0 {
+			note(&mut self.log, || "This kills the boss, and the player wins.".to_string());
 			return Some(true)
 		}
-		self.hp -= if self.shield > 0 { self.boss_atk - 7 } else { self.boss_atk };
+		let damage = if self.shield > 0 { (self.boss_atk - 7).max(1) } else { self.boss_atk };
+		self.hp -= damage;
+		note(&mut self.log, || format!("Boss attacks for {} damage.", damage));
 		if self.hp <= 0 {
+			note(&mut self.log, || "This kills the player, and the boss wins.".to_string());
 			Some(false)
 		}
 		else {
@@ -200,33 +288,72 @@ impl State {
 		}
 	}
 
-	fn new() -> State {
+	fn new(boss: Boss) -> State {
 		State {
-			boss_atk : 9,
-			boss_hp : 51,
+			boss_atk : boss.damage,
+			boss_hp : boss.hp,
 			hp : 50,
 			mana : 500,
 			shield : 0,
 			poison : 0,
 			recharge : 0,
 			spent : 0,
+			log : None,
 		}
 	}
 }
 
+/// The cheapest way to beat the boss in `input` and how that battle plays
+/// out, turn by turn.
+pub fn replay(input: &str, hard: bool) -> String {
+	let boss: Boss = input.parse().unwrap_or_else(|e| panic!("{}", e));
+	let start = State::new(boss);
+	match start.cheapest_win(hard) {
+		None => "The player can't win.".to_string(),
+		Some((spent,spells)) => {
+			let names: Vec<String> = spells.iter().map(|s| s.to_string()).collect();
+			format!("{} for {} mana\n\n{}", names.join(", "), spent, start.battle_log(&spells, hard))
+		}
+	}
+}
+
+pub struct Day {
+	boss: Boss,
+}
+
+impl Solution for Day {
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &str) -> Self {
+		Day { boss: input.parse().unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> i32 {
+		State::new(self.boss).cheapest_win(false).unwrap().0
+	}
+
+	fn part2(&self) -> i32 {
+		State::new(self.boss).cheapest_win(true).unwrap().0
+	}
+}
+
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	const BOSS: Boss = Boss { hp: 51, damage: 9 };
+
 	#[test]
 	fn test_poison() {
-		let mut s = State::new();
+		let mut s = State::new(BOSS);
 		let r = s.poison();
 		assert_eq!(None,r);
 		assert_eq!(173,s.spent);
 		assert_eq!(6,s.poison);
 
-		let mut s = State::new();
+		let mut s = State::new(BOSS);
 		s.mana = 172;
 		let r = s.poison();
 		assert_eq!(Some(false),r);
@@ -234,7 +361,7 @@ mod tests {
 		assert_eq!(0,s.poison);
 		assert_eq!(172,s.mana);
 
-		let mut s = State::new();
+		let mut s = State::new(BOSS);
 		s.poison = 1;
 		let r = s.poison();
 		assert_eq!(Some(false),r);
@@ -244,18 +371,78 @@ mod tests {
 
 	#[test]
 	fn test_simulate() {
-		let mut s = State::new();
-		assert_eq!(Some(900),s.simulate(false));
+		let s = State::new(BOSS);
+		let (spent,spells) = s.cheapest_win(false).unwrap();
+		assert_eq!(900,spent);
+		assert!(s.battle_log(&spells,false).ends_with("This kills the boss, and the player wins."));
 	}
 	#[test]
 	fn test_simulate_hard() {
-		let mut s = State::new();
-		assert_eq!(Some(1216),s.simulate(true));
+		let s = State::new(BOSS);
+		let (spent,spells) = s.cheapest_win(true).unwrap();
+		assert_eq!(1216,spent);
+		assert!(s.battle_log(&spells,true).ends_with("This kills the boss, and the player wins."));
+	}
+
+	#[test]
+	fn test_parse_boss() {
+		assert_eq!(Ok(BOSS),"Hit Points: 51\nDamage: 9\n".parse());
+		assert_eq!(Err("unknown boss stat: Armor".to_string()),"Hit Points: 51\nArmor: 2".parse::<Boss>());
+		assert_eq!(Err("no Damage for the boss".to_string()),"Hit Points: 51".parse::<Boss>());
+	}
+
+	#[test]
+	fn test_battle_log() {
+		let mut s = State::new(Boss { hp: 13, damage: 8 });
+		s.hp = 10;
+		s.mana = 250;
+		assert_eq!(Some((226,vec![Spell::Poison,Spell::Missile])),s.cheapest_win(false));
+		assert_eq!(
+			"-- Player turn --
+- Player has 10 hit points, 0 armor, 250 mana
+- Boss has 13 hit points
+Player casts Poison.
+-- Boss turn --
+- Player has 10 hit points, 0 armor, 77 mana
+- Boss has 13 hit points
+Poison deals 3 damage; its timer is now 5.
+Boss attacks for 8 damage.
+-- Player turn --
+- Player has 2 hit points, 0 armor, 77 mana
+- Boss has 10 hit points
+Poison deals 3 damage; its timer is now 4.
+Player casts Magic Missile.
+-- Boss turn --
+- Player has 2 hit points, 0 armor, 24 mana
+- Boss has 3 hit points
+Poison deals 3 damage; its timer is now 3.
+This kills the boss, and the player wins.",
+			s.battle_log(&[Spell::Poison,Spell::Missile],false)
+		);
+	}
+
+	#[test]
+	fn test_weak_boss() {
+		let mut s = State::new(Boss { hp: 100, damage: 5 });
+		s.shield = 2;
+		assert_eq!(None,s.boss_turn());
+		assert_eq!(49,s.hp);
+		let mut s = State::new(Boss { hp: 100, damage: 8 });
+		s.shield = 2;
+		s.boss_turn();
+		assert_eq!(49,s.hp);
+	}
+
+	#[test]
+	fn test_replay() {
+		let replay = replay("Hit Points: 13\nDamage: 8\n", false);
+		assert!(replay.starts_with("Magic Missile, Magic Missile, Magic Missile, Magic Missile for 212 mana\n\n-- Player turn --\n"));
+		assert!(replay.ends_with("This kills the boss, and the player wins."));
 	}
 
 	#[test]
 	fn test_can_clone() {
-		let s = State::new();
+		let s = State::new(BOSS);
 		let mut s2 = s.clone();
 		s2.mana = 3;
 		assert_eq!(500,s.mana);
@@ -263,20 +450,20 @@ mod tests {
 
 	#[test]
 	fn test_final_round() {
-		let mut s = State::new();
+		let mut s = State::new(BOSS);
 		s.hp = 2;
 		s.mana = 77;
 		s.boss_hp = 10;
 		s.poison = 5;
 
-		let r = s.whatif(0, 100000,false);
+		let r = s.cheapest_win(false);
 
-		assert_eq!(Some(53), r);
+		assert_eq!(Some((53,vec![Spell::Missile])), r);
 	}
 
 	#[test]
 	fn test_boss_turn() {
-		let mut s = State::new();
+		let mut s = State::new(BOSS);
 		s.hp = 100;
 		s.boss_hp = 100;
 		s.mana = 0;
@@ -293,7 +480,7 @@ mod tests {
 		assert_eq!(1,s.shield);
 		assert_eq!(0,s.recharge);
 
-		let mut s = State::new();
+		let mut s = State::new(BOSS);
 		s.hp = 1;
 		s.boss_hp = 3;
 		s.mana = 0;
@@ -1,59 +1,114 @@
-fn main() {
-	let mut a: u32 = 1;
-	let mut b: u32 = 0;
-	if a != 1 {
-		a += 1;
-		a *= 3;
-		a += 1;
-		a *= 3;
-		a += 1;
-		a *= 3;
-		a *= 3;
-		a += 1;
-		a += 1;
-		a *= 3;
-		a *= 3;
-		a += 1;
-		a += 1;
-		a *= 3;
-		a += 1;
-		a += 1;
-		a *= 3;
-	}
-	else {
-		a *= 3;
-		a *= 3;
-		a += 1;
-		a += 1;
-		a *= 3;
-		a += 1;
-		a += 1;
-		a *= 3;
-		a += 1;
-		a *= 3;
-		a += 1;
-		a *= 3;
-		a += 1;
-		a *= 3;
-		a += 1;
-		a += 1;
-		a *= 3;
-		a += 1;
-		a += 1;
-		a *= 3;
-		a *= 3;
-		a += 1;
-	}
-
-	while a != 1 {
-		b += 1;
-		if a % 2 == 0 {
-			a /= 2;
+use crate::solution::Solution;
+use crate::vm::{self, Flow, Instruction, Machine};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+	Hlf(usize),
+	Tpl(usize),
+	Inc(usize),
+	Jmp(isize),
+	Jie(usize, isize),
+	Jio(usize, isize),
+}
+
+impl FromStr for Op {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Op, String> {
+		let (name, args) = s.split_once(' ').ok_or_else(|| format!("missing arguments: {}", s))?;
+		let args: Vec<&str> = args.split(", ").collect();
+		let reg = |r: &str| vm::register(r, Op::REGISTERS);
+		match (name, &args[..]) {
+			("hlf", [r]) => Ok(Op::Hlf(reg(r)?)),
+			("tpl", [r]) => Ok(Op::Tpl(reg(r)?)),
+			("inc", [r]) => Ok(Op::Inc(reg(r)?)),
+			("jmp", [by]) => Ok(Op::Jmp(vm::offset(by)?)),
+			("jie", [r, by]) => Ok(Op::Jie(reg(r)?, vm::offset(by)?)),
+			("jio", [r, by]) => Ok(Op::Jio(reg(r)?, vm::offset(by)?)),
+			_ => Err(format!("unknown instruction: {}", s)),
 		}
-		else {
-			a *= 3;
-			a += 1;
+	}
+}
+
+impl Instruction for Op {
+	type Word = u64;
+	const REGISTERS: usize = 2;
+	const OPCODES: &'static [&'static str] = &["hlf", "tpl", "inc", "jmp", "jie", "jio"];
+
+	fn opcode(&self) -> usize {
+		match self {
+			Op::Hlf(_) => 0,
+			Op::Tpl(_) => 1,
+			Op::Inc(_) => 2,
+			Op::Jmp(_) => 3,
+			Op::Jie(..) => 4,
+			Op::Jio(..) => 5,
 		}
 	}
-		println!("b is {}",b);
+
+	fn execute(&mut self, r: &mut [u64]) -> Flow {
+		match *self {
+			Op::Hlf(x) => r[x] /= 2,
+			Op::Tpl(x) => r[x] *= 3,
+			Op::Inc(x) => r[x] += 1,
+			Op::Jmp(by) => return Flow::Jump(by),
+			Op::Jie(x, by) if r[x].is_multiple_of(2) => return Flow::Jump(by),
+			Op::Jio(x, by) if r[x] == 1 => return Flow::Jump(by),
+			Op::Jie(..) | Op::Jio(..) => {}
+		}
+		Flow::Next
+	}
+}
+
+pub struct Day {
+	machine: Machine<Op>,
+}
+
+impl Day {
+	fn run(&self, a: u64) -> u64 {
+		let mut m = self.machine.clone();
+		m.registers[0] = a;
+		m.run();
+		m.registers[1]
+	}
+}
+
+impl Solution for Day {
+	type Part1 = u64;
+	type Part2 = u64;
+
+	fn parse(input: &str) -> Self {
+		Day { machine: Machine::parse(input).unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> u64 {
+		self.run(0)
+	}
+
+	fn part2(&self) -> u64 {
+		self.run(1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_example() {
+		let mut m: Machine<Op> = Machine::parse("inc a\njio a, +2\ntpl a\ninc a").unwrap();
+		m.run();
+		assert_eq!(vec![2, 0], m.registers);
+		assert_eq!(3, m.steps);
+		assert_eq!(Some(("inc", 2)), m.counts().find(|(op, _)| *op == "inc"));
+	}
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(Ok(Op::Jie(0, 4)), "jie a, +4".parse());
+		assert_eq!(Ok(Op::Jmp(-7)), "jmp -7".parse());
+		assert_eq!(Err("no register c".to_string()), "inc c".parse::<Op>());
+		assert_eq!(Err("unknown instruction: jio a".to_string()), "jio a".parse::<Op>());
+	}
 }
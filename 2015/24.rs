@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 
 fn find_group_sum( source: &[i32], n: u8 ) -> i32 {
 	let mut sum = 0;
@@ -99,6 +100,38 @@ fn find_with_size( source: &[i32], size: usize, groups: u8 ) ->Option<(u64,Vec<u
 	brute(&source,0,group_sum,1,std::u64::MAX,0,size,groups,&[])
 }
 
+fn parse(input: &str) -> Vec<i32> {
+	input.lines().map(|l| l.parse().unwrap()).collect()
+}
+
+fn smallest_group(source: &[i32], groups: u8) -> Option<u64> {
+	(1..source.len())
+		.find_map(|size| find_with_size(source,size,groups))
+		.map(|(quantum,_)| quantum)
+}
+
+pub struct Day {
+	packages: Vec<i32>,
+}
+
+impl Solution for Day {
+	type Part1 = u64;
+	type Part2 = u64;
+
+	fn parse(input: &str) -> Self {
+		Day { packages: parse(input) }
+	}
+
+	fn part1(&self) -> u64 {
+		smallest_group(&self.packages,3).unwrap()
+	}
+
+	fn part2(&self) -> u64 {
+		smallest_group(&self.packages,4).unwrap()
+	}
+}
+
+
 #[cfg(test)]
 mod tests {
 	use super::*;
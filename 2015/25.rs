@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 //    | 1   2   3   4   5   6
 // ---+---+---+---+---+---+---+
 //  1 |  1   3   6  10  15  21
@@ -43,6 +45,40 @@ fn get_code(row:usize,col:usize) -> u64 {
 	code
 }
 
+fn parse(input: &str) -> (usize,usize) {
+	let numbers: Vec<usize> = input
+		.split(|c: char| !c.is_ascii_digit())
+		.filter(|s| !s.is_empty())
+		.map(|s| s.parse().unwrap())
+		.collect();
+	(numbers[0],numbers[1])
+}
+
+pub struct Day {
+	row: usize,
+	col: usize,
+}
+
+impl Solution for Day {
+	type Part1 = u64;
+	type Part2 = &'static str;
+
+	fn parse(input: &str) -> Self {
+		let (row,col) = parse(input);
+		Day { row, col }
+	}
+
+	fn part1(&self) -> u64 {
+		get_code(self.row,self.col)
+	}
+
+	// the last day only has the one puzzle
+	fn part2(&self) -> &'static str {
+		"-"
+	}
+}
+
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -0,0 +1,60 @@
+use crate::solution::Solution;
+use std::collections::HashSet;
+
+/// How many houses get a present when `santas` take turns following the moves.
+fn houses(moves: &str, santas: usize) -> usize {
+	let mut at = vec![(0i32, 0i32); santas];
+	let mut visited = HashSet::from([(0, 0)]);
+	for (i, m) in moves.chars().enumerate() {
+		let santa = &mut at[i % santas];
+		match m {
+			'>' => santa.0 += 1,
+			'<' => santa.0 -= 1,
+			'^' => santa.1 += 1,
+			'v' => santa.1 -= 1,
+			_ => continue,
+		}
+		visited.insert(*santa);
+	}
+	visited.len()
+}
+
+pub struct Day {
+	moves: String,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { moves: input.trim().to_string() }
+	}
+
+	fn part1(&self) -> usize {
+		houses(&self.moves, 1)
+	}
+
+	fn part2(&self) -> usize {
+		houses(&self.moves, 2)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_santa() {
+		assert_eq!(2, houses(">", 1));
+		assert_eq!(4, houses("^>v<", 1));
+		assert_eq!(2, houses("^v^v^v^v^v", 1));
+	}
+
+	#[test]
+	fn test_robo_santa() {
+		assert_eq!(3, houses("^v", 2));
+		assert_eq!(3, houses("^>v<", 2));
+		assert_eq!(11, houses("^v^v^v^v^v", 2));
+	}
+}
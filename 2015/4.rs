@@ -0,0 +1,45 @@
+use crate::md5;
+use crate::solution::Solution;
+
+/// The lowest number from `from` up that, after the key, hashes to `zeros` leading zeros.
+fn mine(key: &str, zeros: usize, from: u64) -> u64 {
+	let prefix = md5::Prefix::new(key.as_bytes());
+	let (n, _) = md5::search(&prefix, from, |d| md5::leading_zeros(d, zeros).then_some(())).next().unwrap();
+	n
+}
+
+pub struct Day {
+	key: String,
+}
+
+impl Solution for Day {
+	type Part1 = u64;
+	type Part2 = u64;
+
+	fn parse(input: &str) -> Self {
+		Day { key: input.trim().to_string() }
+	}
+
+	fn part1(&self) -> u64 {
+		mine(&self.key, 5, 1)
+	}
+
+	fn part2(&self) -> u64 {
+		// six zeros are five zeros too, so no need to look before those
+		mine(&self.key, 6, self.part1())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_mine() {
+		// starting close by, as the whole search is slow without optimisations
+		assert_eq!(609043, mine("abcdef", 5, 600000));
+		assert_eq!(1048970, mine("pqrstuv", 5, 1040000));
+		let hash = md5::Prefix::new(b"abcdef").digest_number(609043);
+		assert!(md5::hex(&hash).starts_with("000001dbbfa"));
+	}
+}
@@ -0,0 +1,63 @@
+use crate::solution::Solution;
+
+fn nice(s: &str) -> bool {
+	let b = s.as_bytes();
+	let vowels = b.iter().filter(|c| b"aeiou".contains(c)).count();
+	let double = b.windows(2).any(|w| w[0] == w[1]);
+	let forbidden = ["ab", "cd", "pq", "xy"].iter().any(|f| s.contains(f));
+	vowels >= 3 && double && !forbidden
+}
+
+fn nicer(s: &str) -> bool {
+	let b = s.as_bytes();
+	// a pair that shows up again later, without overlapping itself
+	let pair = (0..b.len().saturating_sub(3)).any(|i| b[i + 2..].windows(2).any(|w| w == &b[i..i + 2]));
+	let sandwich = b.windows(3).any(|w| w[0] == w[2]);
+	pair && sandwich
+}
+
+pub struct Day {
+	strings: Vec<String>,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { strings: input.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect() }
+	}
+
+	fn part1(&self) -> usize {
+		self.strings.iter().filter(|s| nice(s)).count()
+	}
+
+	fn part2(&self) -> usize {
+		self.strings.iter().filter(|s| nicer(s)).count()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_nice() {
+		assert!(nice("ugknbfddgicrmopn"));
+		assert!(nice("aaa"));
+		assert!(!nice("jchzalrnumimnmhp"));
+		assert!(!nice("haegwjzuvuyypxyu"));
+		assert!(!nice("dvszwmarrgswjxmb"));
+	}
+
+	#[test]
+	fn test_nicer() {
+		assert!(nicer("qjhvhtzxzqqjkmpb"));
+		assert!(nicer("xxyxx"));
+		assert!(!nicer("uurcxstgmygtbstg"));
+		assert!(!nicer("ieodomkazucvgmuy"));
+		// the pair may not overlap itself
+		assert!(!nicer("aaa"));
+		assert!(nicer("aaaa"));
+	}
+}
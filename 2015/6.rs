@@ -0,0 +1,123 @@
+use crate::grid::Grid;
+use crate::solution::Solution;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+	On,
+	Off,
+	Toggle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Instruction {
+	action: Action,
+	from: (usize, usize),
+	to: (usize, usize),
+}
+
+fn corner(s: &str) -> Result<(usize, usize), String> {
+	let (x, y) = s.split_once(',').ok_or_else(|| format!("expected x,y: {}", s))?;
+	let n = |v: &str| v.parse().map_err(|_| format!("not a number: {}", v));
+	Ok((n(x)?, n(y)?))
+}
+
+/// Reads lines like "toggle 461,550 through 564,900".
+impl FromStr for Instruction {
+	type Err = String;
+
+	fn from_str(line: &str) -> Result<Instruction, String> {
+		let (action, rest) = [("turn on ", Action::On), ("turn off ", Action::Off), ("toggle ", Action::Toggle)]
+			.iter()
+			.find_map(|(word, action)| line.strip_prefix(word).map(|rest| (*action, rest)))
+			.ok_or_else(|| format!("unknown instruction: {}", line))?;
+		let (from, to) = rest.split_once(" through ").ok_or_else(|| format!("expected a range: {}", line))?;
+		let (from, to) = (corner(from)?, corner(to)?);
+		if from.0 > to.0 || from.1 > to.1 {
+			return Err(format!("corners out of order: {}", line));
+		}
+		Ok(Instruction { action, from, to })
+	}
+}
+
+/// The total brightness after following every instruction, with `light`
+/// saying what an action does to a light of some brightness.
+fn run(instructions: &[Instruction], light: impl Fn(Action, u32) -> u32) -> u32 {
+	let mut grid = Grid::new(1000, 1000, 0u32);
+	for i in instructions {
+		for y in i.from.1..=i.to.1 {
+			for v in &mut grid.row_mut(y)[i.from.0..=i.to.0] {
+				*v = light(i.action, *v);
+			}
+		}
+	}
+	grid.iter().sum()
+}
+
+fn switch(action: Action, v: u32) -> u32 {
+	match action {
+		Action::On => 1,
+		Action::Off => 0,
+		Action::Toggle => 1 - v,
+	}
+}
+
+fn dim(action: Action, v: u32) -> u32 {
+	match action {
+		Action::On => v + 1,
+		Action::Off => v.saturating_sub(1),
+		Action::Toggle => v + 2,
+	}
+}
+
+pub struct Day {
+	instructions: Vec<Instruction>,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		let instructions = input.lines().filter(|l| !l.trim().is_empty()).map(|l| l.trim().parse()).collect::<Result<_, _>>();
+		Day { instructions: instructions.unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> u32 {
+		run(&self.instructions, switch)
+	}
+
+	fn part2(&self) -> u32 {
+		run(&self.instructions, dim)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(s: &str) -> Vec<Instruction> {
+		s.lines().map(|l| l.parse().unwrap()).collect()
+	}
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(Ok(Instruction { action: Action::Toggle, from: (461, 550), to: (564, 900) }), "toggle 461,550 through 564,900".parse());
+		assert_eq!(Err("unknown instruction: turn up 0,0 through 1,1".to_string()), "turn up 0,0 through 1,1".parse::<Instruction>());
+		assert_eq!(Err("corners out of order: turn on 5,0 through 1,1".to_string()), "turn on 5,0 through 1,1".parse::<Instruction>());
+	}
+
+	#[test]
+	fn test_switch() {
+		assert_eq!(1000000, run(&parse("turn on 0,0 through 999,999"), switch));
+		assert_eq!(1000, run(&parse("toggle 0,0 through 999,0"), switch));
+		assert_eq!(998996, run(&parse("turn on 0,0 through 999,999\ntoggle 0,0 through 999,0\nturn off 499,499 through 500,500"), switch));
+	}
+
+	#[test]
+	fn test_dim() {
+		assert_eq!(1, run(&parse("turn on 0,0 through 0,0"), dim));
+		assert_eq!(2000000, run(&parse("toggle 0,0 through 999,999"), dim));
+		assert_eq!(0, run(&parse("turn off 0,0 through 0,0"), dim));
+	}
+}
@@ -0,0 +1,146 @@
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Input {
+	Signal(u16),
+	Wire(String),
+}
+
+impl Input {
+	fn parse(s: &str) -> Result<Input, String> {
+		if s.bytes().all(|b| b.is_ascii_digit()) {
+			s.parse().map(Input::Signal).map_err(|_| format!("signal out of range: {}", s))
+		} else if s.bytes().all(|b| b.is_ascii_lowercase()) && !s.is_empty() {
+			Ok(Input::Wire(s.to_string()))
+		} else {
+			Err(format!("not a wire or signal: {}", s))
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Gate {
+	Direct(Input),
+	Not(Input),
+	And(Input, Input),
+	Or(Input, Input),
+	LShift(Input, Input),
+	RShift(Input, Input),
+}
+
+#[derive(Debug, Clone)]
+struct Circuit {
+	wires: HashMap<String, Gate>,
+}
+
+/// Reads one wire per line, like "x AND y -> d".
+impl FromStr for Circuit {
+	type Err = String;
+
+	fn from_str(input: &str) -> Result<Circuit, String> {
+		let mut wires = HashMap::new();
+		for line in input.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+			let (gate, wire) = line.split_once(" -> ").ok_or_else(|| format!("expected \"gate -> wire\": {}", line))?;
+			let words: Vec<&str> = gate.split_whitespace().collect();
+			let gate = match words[..] {
+				[a] => Gate::Direct(Input::parse(a)?),
+				["NOT", a] => Gate::Not(Input::parse(a)?),
+				[a, "AND", b] => Gate::And(Input::parse(a)?, Input::parse(b)?),
+				[a, "OR", b] => Gate::Or(Input::parse(a)?, Input::parse(b)?),
+				[a, "LSHIFT", b] => Gate::LShift(Input::parse(a)?, Input::parse(b)?),
+				[a, "RSHIFT", b] => Gate::RShift(Input::parse(a)?, Input::parse(b)?),
+				_ => return Err(format!("unknown gate: {}", gate)),
+			};
+			if wires.insert(wire.to_string(), gate).is_some() {
+				return Err(format!("wire {} has two inputs", wire));
+			}
+		}
+		Ok(Circuit { wires })
+	}
+}
+
+impl Circuit {
+	/// The signal on a wire, remembering every wire worked out on the way.
+	fn signal(&self, wire: &str, known: &mut HashMap<String, u16>) -> u16 {
+		if let Some(s) = known.get(wire) {
+			return *s;
+		}
+		let gate = self.wires.get(wire).unwrap_or_else(|| panic!("no wire {}", wire));
+		let mut input = |i: &Input| match i {
+			Input::Signal(s) => *s,
+			Input::Wire(w) => self.signal(w, known),
+		};
+		let s = match gate {
+			Gate::Direct(a) => input(a),
+			Gate::Not(a) => !input(a),
+			Gate::And(a, b) => input(a) & input(b),
+			Gate::Or(a, b) => input(a) | input(b),
+			Gate::LShift(a, b) => input(a).checked_shl(input(b) as u32).unwrap_or(0),
+			Gate::RShift(a, b) => input(a).checked_shr(input(b) as u32).unwrap_or(0),
+		};
+		known.insert(wire.to_string(), s);
+		s
+	}
+}
+
+pub struct Day {
+	circuit: Circuit,
+}
+
+impl Solution for Day {
+	type Part1 = u16;
+	type Part2 = u16;
+
+	fn parse(input: &str) -> Self {
+		Day { circuit: input.parse().unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> u16 {
+		self.circuit.signal("a", &mut HashMap::new())
+	}
+
+	fn part2(&self) -> u16 {
+		// wire b is overridden with what a was, and everything else reset
+		let mut known = HashMap::from([("b".to_string(), self.part1())]);
+		self.circuit.signal("a", &mut known)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EXAMPLE: &str = "123 -> x
+456 -> y
+x AND y -> d
+x OR y -> e
+x LSHIFT 2 -> f
+y RSHIFT 2 -> g
+NOT x -> h
+NOT y -> i";
+
+	#[test]
+	fn test_example() {
+		let circuit: Circuit = EXAMPLE.parse().unwrap();
+		let mut known = HashMap::new();
+		let signals: Vec<u16> = ["d", "e", "f", "g", "h", "i", "x", "y"].iter().map(|w| circuit.signal(w, &mut known)).collect();
+		assert_eq!(vec![72, 507, 492, 114, 65412, 65079, 123, 456], signals);
+	}
+
+	#[test]
+	fn test_parse() {
+		let circuit: Circuit = "1 AND cx -> cy".parse().unwrap();
+		assert_eq!(Some(&Gate::And(Input::Signal(1), Input::Wire("cx".to_string()))), circuit.wires.get("cy"));
+		assert_eq!(Err("unknown gate: x XOR y".to_string()), "x XOR y -> z".parse::<Circuit>().map(|_| ()));
+		assert_eq!(Err("wire a has two inputs".to_string()), "1 -> a\n2 -> a".parse::<Circuit>().map(|_| ()));
+	}
+
+	#[test]
+	fn test_chain() {
+		// wires may be listed after the ones they feed
+		let circuit: Circuit = "b -> a\nNOT c -> b\n65535 -> c".parse().unwrap();
+		assert_eq!(0, circuit.signal("a", &mut HashMap::new()));
+	}
+}
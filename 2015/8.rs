@@ -0,0 +1,61 @@
+use crate::solution::Solution;
+
+/// How many characters a string literal stands for.
+fn decoded_len(literal: &str) -> usize {
+	let b = literal.as_bytes();
+	let mut len = 0;
+	let mut i = 1;
+	while i < b.len() - 1 {
+		i += match (b[i], b.get(i + 1)) {
+			(b'\\', Some(b'x')) => 4,
+			(b'\\', _) => 2,
+			_ => 1,
+		};
+		len += 1;
+	}
+	len
+}
+
+/// How long the string literal is once written as a literal itself.
+fn encoded_len(literal: &str) -> usize {
+	2 + literal.chars().map(|c| if c == '"' || c == '\\' { 2 } else { 1 }).sum::<usize>()
+}
+
+pub struct Day {
+	literals: Vec<String>,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { literals: input.lines().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()).collect() }
+	}
+
+	fn part1(&self) -> usize {
+		self.literals.iter().map(|l| l.len() - decoded_len(l)).sum()
+	}
+
+	fn part2(&self) -> usize {
+		self.literals.iter().map(|l| encoded_len(l) - l.len()).sum()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EXAMPLE: [&str; 4] = [r#""""#, r#""abc""#, r#""aaa\"aaa""#, r#""\x27""#];
+
+	#[test]
+	fn test_decoded() {
+		assert_eq!(vec![0, 3, 7, 1], EXAMPLE.map(decoded_len).to_vec());
+		assert_eq!(1, decoded_len(r#""\\""#));
+	}
+
+	#[test]
+	fn test_encoded() {
+		assert_eq!(vec![6, 9, 16, 11], EXAMPLE.map(encoded_len).to_vec());
+	}
+}
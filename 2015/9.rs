@@ -0,0 +1,99 @@
+use crate::solution::Solution;
+use crate::tsp::{shortest_tour, Shape};
+use std::str::FromStr;
+
+/// The distance between every pair of places.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Atlas {
+	places: Vec<String>,
+	dist: Vec<Vec<i64>>,
+}
+
+/// Reads lines like "London to Dublin = 464".
+impl FromStr for Atlas {
+	type Err = String;
+
+	fn from_str(input: &str) -> Result<Atlas, String> {
+		let mut places: Vec<String> = Vec::new();
+		let mut legs = Vec::new();
+		for line in input.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+			let (route, dist) = line.split_once(" = ").ok_or_else(|| format!("expected \"a to b = distance\": {}", line))?;
+			let (a, b) = route.split_once(" to ").ok_or_else(|| format!("expected \"a to b = distance\": {}", line))?;
+			let dist: i64 = dist.parse().map_err(|_| format!("not a distance: {}", dist))?;
+			let mut id = |name: &str| places.iter().position(|p| p == name).unwrap_or_else(|| {
+				places.push(name.to_string());
+				places.len() - 1
+			});
+			legs.push((id(a), id(b), dist));
+		}
+		let mut dist = vec![vec![0; places.len()]; places.len()];
+		for (a, b, d) in legs {
+			dist[a][b] = d;
+			dist[b][a] = d;
+		}
+		Ok(Atlas { places, dist })
+	}
+}
+
+impl Atlas {
+	fn shortest(&self) -> i64 {
+		shortest_tour(&self.dist, None, Shape::Open).cost
+	}
+
+	fn longest(&self) -> i64 {
+		let negated: Vec<Vec<i64>> = self.dist.iter().map(|row| row.iter().map(|d| -d).collect()).collect();
+		-shortest_tour(&negated, None, Shape::Open).cost
+	}
+}
+
+pub struct Day {
+	atlas: Atlas,
+}
+
+impl Solution for Day {
+	type Part1 = i64;
+	type Part2 = i64;
+
+	fn parse(input: &str) -> Self {
+		Day { atlas: input.parse().unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> i64 {
+		self.atlas.shortest()
+	}
+
+	fn part2(&self) -> i64 {
+		self.atlas.longest()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EXAMPLE: &str = "London to Dublin = 464
+London to Belfast = 518
+Dublin to Belfast = 141";
+
+	#[test]
+	fn test_parse() {
+		let atlas: Atlas = EXAMPLE.parse().unwrap();
+		assert_eq!(vec!["London", "Dublin", "Belfast"], atlas.places);
+		assert_eq!(141, atlas.dist[2][1]);
+		assert_eq!(Err("not a distance: far".to_string()), "a to b = far".parse::<Atlas>());
+	}
+
+	#[test]
+	fn test_example() {
+		let atlas: Atlas = EXAMPLE.parse().unwrap();
+		assert_eq!(605, atlas.shortest());
+		assert_eq!(982, atlas.longest());
+	}
+
+	#[test]
+	fn test_triangle() {
+		let atlas: Atlas = "a to b = 2\na to c = 3\nb to c = 5".parse().unwrap();
+		assert_eq!(5, atlas.shortest());
+		assert_eq!(8, atlas.longest());
+	}
+}
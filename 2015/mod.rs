@@ -0,0 +1,50 @@
+#[path = "1.rs"]
+pub mod day1;
+#[path = "2.rs"]
+pub mod day2;
+#[path = "3.rs"]
+pub mod day3;
+#[path = "4.rs"]
+pub mod day4;
+#[path = "5.rs"]
+pub mod day5;
+#[path = "6.rs"]
+pub mod day6;
+#[path = "7.rs"]
+pub mod day7;
+#[path = "8.rs"]
+pub mod day8;
+#[path = "9.rs"]
+pub mod day9;
+#[path = "10.rs"]
+pub mod day10;
+#[path = "11.rs"]
+pub mod day11;
+#[path = "12.rs"]
+pub mod day12;
+#[path = "thirteen/13.rs"]
+pub mod day13;
+#[path = "14.rs"]
+pub mod day14;
+#[path = "15.rs"]
+pub mod day15;
+#[path = "sixteen/16.rs"]
+pub mod day16;
+#[path = "17.rs"]
+pub mod day17;
+#[path = "18.rs"]
+pub mod day18;
+#[path = "19.rs"]
+pub mod day19;
+#[path = "20.rs"]
+pub mod day20;
+#[path = "21.rs"]
+pub mod day21;
+#[path = "22.rs"]
+pub mod day22;
+#[path = "23.rs"]
+pub mod day23;
+#[path = "24.rs"]
+pub mod day24;
+#[path = "25.rs"]
+pub mod day25;
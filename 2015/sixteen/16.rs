@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use regex::Regex;
 
 pub struct Entry {
@@ -24,8 +25,11 @@ pub fn get_match(cap:&regex::Captures,name:&str) -> Option<u8> {
 }
 
 pub fn get_data() -> Result<Vec<Entry>, std::io::Error> {
-	let data = std::fs::read_to_string("16.txt")?;
+	let data = std::fs::read_to_string("2015/sixteen/16.txt")?;
+	Ok(parse(&data))
+}
 
+pub fn parse(data: &str) -> Vec<Entry> {
 	let re = Regex::new(r"(?x)
 		Sue\s(?P<id>\d+):(\s(
 			children:\s(?P<children>\d+)|
@@ -40,7 +44,7 @@ pub fn get_data() -> Result<Vec<Entry>, std::io::Error> {
 			perfumes:\s(?P<perfumes>\d+)
 		),?){3}(\r\n)?").unwrap();
 	let mut v: Vec<Entry> = Vec::new();
-	for cap in re.captures_iter(&data) {
+	for cap in re.captures_iter(data) {
 		let id: u16 = cap.name("id").unwrap().as_str().parse().unwrap();
 		let children = get_match(&cap,"children");
 		let cats = get_match(&cap,"cats");
@@ -67,7 +71,7 @@ pub fn get_data() -> Result<Vec<Entry>, std::io::Error> {
 			perfumes
 		});
 	}
-	Ok(v)
+	v
 }
 
 pub fn is_valid( entry: &Entry, outdated:bool ) -> bool {
@@ -124,20 +128,34 @@ pub fn is_valid( entry: &Entry, outdated:bool ) -> bool {
 	return true
 }
 
-fn main() {
-	let data = get_data().unwrap();
-	for sue in &data {
-		if is_valid(&sue,false) {
-			println!("Sue {}", &sue.id);
-		}
+pub struct Day {
+	sues: Vec<Entry>,
+}
+
+impl Day {
+	fn find_sue(&self, outdated: bool) -> u16 {
+		self.sues.iter().find(|sue| is_valid(sue,outdated)).unwrap().id
 	}
-	for sue in &data {
-		if is_valid(&sue,true) {
-			println!("outdated Sue {}", &sue.id);
-		}
+}
+
+impl Solution for Day {
+	type Part1 = u16;
+	type Part2 = u16;
+
+	fn parse(input: &str) -> Self {
+		Day { sues: parse(input) }
+	}
+
+	fn part1(&self) -> u16 {
+		self.find_sue(false)
+	}
+
+	fn part2(&self) -> u16 {
+		self.find_sue(true)
 	}
 }
 
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1,6 +1,7 @@
+use crate::solution::Solution;
+use crate::tsp::{shortest_tour, Shape};
 use regex::Regex;
 use std::collections::HashMap;
-use permutohedron::heap_recursive;
 
 pub struct Entry {
 	name: String,
@@ -9,20 +10,27 @@ pub struct Entry {
 }
 
 pub fn get_data() -> Result<Vec<Entry>,std::io::Error> {
-	let data = std::fs::read_to_string("13.txt")?;
-	let re = Regex::new(r"([^ ]+) would (lose|gain) ([^ ]+) happiness units by sitting next to ([^.]+).(\r\n)?").unwrap();
+	let data = std::fs::read_to_string("2015/thirteen/13.txt")?;
+	Ok(parse(&data))
+}
+
+pub fn parse(data: &str) -> Vec<Entry> {
+	let re = Regex::new(r"([^ ]+) would (lose|gain) ([^ ]+) happiness units by sitting next to ([^.]+).(\r?\n)?").unwrap();
 	let mut v: Vec<Entry> = Vec::new();
-	for cap in re.captures_iter(&data) {
+	for cap in re.captures_iter(data) {
 		let name: String = cap[1].to_string();
 		let gain: i32 = &cap[3].parse().unwrap() * if &cap[2]=="lose" { -1 } else { 1 };
 		let next: String = cap[4].to_string();
 		v.push(Entry{ name, next, gain });
 	}
-	Ok(v)
+	v
 }
 
 pub fn combine_data() -> HashMap<String,HashMap<String,i32>> {
-	let mut entries = get_data().unwrap();
+	combine(get_data().unwrap())
+}
+
+pub fn combine(mut entries: Vec<Entry>) -> HashMap<String,HashMap<String,i32>> {
 	let mut h :HashMap<String,HashMap<String,i32>> = HashMap::new();
 
 	loop {
@@ -61,16 +69,39 @@ pub fn get_cost(map:&HashMap<String,HashMap<String,i32>>,list:&mut [&str]) -> i3
 	sum
 }
 
-pub fn do_perm(map:&HashMap<String,HashMap<String,i32>>,mut list:&mut [&str]) -> i32 {
-	let mut highscore = -10000i32;
-	heap_recursive(&mut list, |permutation| {
-		let score = get_cost(&map,permutation);
-		if score > highscore {
-			highscore = score
-		}
-	});
-	highscore
+pub fn best_seating(map:&HashMap<String,HashMap<String,i32>>,list:&[&str]) -> i32 {
+	// the cheapest tour on negated happiness is the happiest table
+	let dist: Vec<Vec<i64>> = list.iter().map(|a| list.iter().map(|b| {
+		if a == b { 0 } else { -(lookup(map,a,b).unwrap() + lookup(map,b,a).unwrap()) as i64 }
+	}).collect()).collect();
+	-shortest_tour(&dist,None,Shape::Closed).cost as i32
 }
+
+pub struct Day {
+	map: HashMap<String,HashMap<String,i32>>,
+}
+
+impl Solution for Day {
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &str) -> Self {
+		Day { map: combine(parse(input)) }
+	}
+
+	fn part1(&self) -> i32 {
+		let names: Vec<&str> = self.map.keys().map(|k| k.as_str()).collect();
+		best_seating(&self.map,&names)
+	}
+
+	fn part2(&self) -> i32 {
+		let mut names: Vec<&str> = self.map.keys().map(|k| k.as_str()).collect();
+		names.push("You");
+		best_seating(&self.map,&names)
+	}
+}
+
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -82,8 +113,8 @@ mod tests {
 		assert_eq!(2,lookup(&map,"Alice","Bob").unwrap());
 		assert_eq!(-89,lookup(&map,"Mallory","George").unwrap());
 		assert_eq!(65i32,get_cost(&map,&mut ["Alice","Bob","Carol","David","Eric","Frank","George","Mallory"]));
-		assert_eq!(733,do_perm(&map,&mut ["Alice","Bob","Carol","David","Eric","Frank","George","Mallory"]));
+		assert_eq!(733,best_seating(&map,&["Alice","Bob","Carol","David","Eric","Frank","George","Mallory"]));
 		assert_eq!(0,lookup(&map,"Alice","You").unwrap());
-		assert_eq!(725,do_perm(&map,&mut ["Alice","Bob","Carol","David","Eric","Frank","George","Mallory","You"]));
+		assert_eq!(725,best_seating(&map,&["Alice","Bob","Carol","David","Eric","Frank","George","Mallory","You"]));
 	}
 }
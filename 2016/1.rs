@@ -0,0 +1,93 @@
+use crate::solution::Solution;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Turn {
+	Left,
+	Right,
+}
+
+fn parse_steps(input: &str) -> Result<Vec<(Turn, i32)>, String> {
+	input.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| {
+		let turn = match s.as_bytes()[0] {
+			b'L' => Turn::Left,
+			b'R' => Turn::Right,
+			_ => return Err(format!("expected L or R: {}", s)),
+		};
+		let blocks = s[1..].parse().map_err(|_| format!("not a number of blocks: {}", s))?;
+		Ok((turn, blocks))
+	}).collect()
+}
+
+/// Every block walked through, one at a time, starting out facing north.
+fn walk(steps: &[(Turn, i32)]) -> impl Iterator<Item = (i32, i32)> + '_ {
+	let mut facing = (0, 1);
+	let mut at = (0, 0);
+	steps.iter().flat_map(move |&(turn, blocks)| {
+		facing = match turn {
+			Turn::Left => (-facing.1, facing.0),
+			Turn::Right => (facing.1, -facing.0),
+		};
+		let (dx, dy) = facing;
+		let from = at;
+		at = (at.0 + dx * blocks, at.1 + dy * blocks);
+		(1..=blocks).map(move |n| (from.0 + dx * n, from.1 + dy * n))
+	})
+}
+
+fn distance((x, y): (i32, i32)) -> i32 {
+	x.abs() + y.abs()
+}
+
+fn first_revisit(steps: &[(Turn, i32)]) -> Option<(i32, i32)> {
+	let mut seen = HashSet::from([(0, 0)]);
+	walk(steps).find(|at| !seen.insert(*at))
+}
+
+pub struct Day {
+	steps: Vec<(Turn, i32)>,
+}
+
+impl Solution for Day {
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &str) -> Self {
+		Day { steps: parse_steps(input).unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> i32 {
+		distance(walk(&self.steps).last().unwrap_or((0, 0)))
+	}
+
+	fn part2(&self) -> i32 {
+		distance(first_revisit(&self.steps).unwrap())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn end(s: &str) -> i32 {
+		distance(walk(&parse_steps(s).unwrap()).last().unwrap())
+	}
+
+	#[test]
+	fn test_parse() {
+		assert_eq!(Ok(vec![(Turn::Right, 2), (Turn::Left, 194)]), parse_steps("R2, L194\n"));
+		assert_eq!(Err("expected L or R: U2".to_string()), parse_steps("R2, U2"));
+	}
+
+	#[test]
+	fn test_walk() {
+		assert_eq!(5, end("R2, L3"));
+		assert_eq!(2, end("R2, R2, R2"));
+		assert_eq!(12, end("R5, L5, R5, R3"));
+	}
+
+	#[test]
+	fn test_revisit() {
+		assert_eq!(Some((4, 0)), first_revisit(&parse_steps("R8, R4, R4, R8").unwrap()));
+	}
+}
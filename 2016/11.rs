@@ -1,6 +1,6 @@
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-use std::collections::{HashSet};
+use crate::search;
+use crate::solution::Solution;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, Hash, Eq, Ord, PartialEq, PartialOrd)]
 struct Gen {
@@ -21,14 +21,16 @@ struct State {
 	ev: i8,
 }
 
-const CLOOKUP: [char; 7] = ['s','p','t','r','c','e','d'];
+fn element(id: u8) -> char {
+	(b'a' + id) as char
+}
 
 impl std::fmt::Display for State {
 	fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		for f in 1..=4 {
 			let e = if self.ev == f { "E" } else {"."};
-			let cid : Vec<char> = self.chips(f).iter().map(|c| CLOOKUP[c.id as usize]).collect();
-			let gid : Vec<char> = self.gens(f).iter().map(|g| CLOOKUP[g.id as usize].to_ascii_uppercase()).collect();
+			let cid : Vec<char> = self.chips(f).iter().map(|c| element(c.id)).collect();
+			let gid : Vec<char> = self.gens(f).iter().map(|g| element(g.id).to_ascii_uppercase()).collect();
 
 			let _ = writeln!(fmt, "F{} {} {:?} {:?}", f, e, cid, gid );
 		}
@@ -36,30 +38,96 @@ impl std::fmt::Display for State {
 	}
 }
 
+const FLOORS: [&str; 4] = ["first","second","third","fourth"];
+
+/// One thing said to be on a floor.
+fn parse_item(item: &str) -> Result<(&str,bool),String> {
+	let thing = item.strip_prefix("a ").ok_or_else(|| format!("expected \"a ...\": {}", item))?;
+	if let Some(element) = thing.strip_suffix("-compatible microchip") {
+		Ok((element,false))
+	} else if let Some(element) = thing.strip_suffix(" generator") {
+		Ok((element,true))
+	} else {
+		Err(format!("neither a generator nor a microchip: {}", item))
+	}
+}
+
+/// Reads lines like "The first floor contains a thulium generator, and a
+/// thulium-compatible microchip." Elements are numbered in order of first mention.
+impl FromStr for State {
+	type Err = String;
+
+	fn from_str(input: &str) -> Result<State,String> {
+		let mut names: Vec<&str> = Vec::new();
+		let mut gens: Vec<Option<i8>> = Vec::new();
+		let mut chips: Vec<Option<i8>> = Vec::new();
+		let mut seen_floors = [false; 4];
+		for (n,line) in input.lines().enumerate().filter(|(_,l)| !l.trim().is_empty()) {
+			let err = |e: String| format!("line {}: {}", n + 1, e);
+			let rest = line.trim().strip_prefix("The ").and_then(|l| l.strip_suffix('.'))
+				.ok_or_else(|| err(format!("expected \"The ... floor contains ....\": {}", line)))?;
+			let (floor,items) = rest.split_once(" floor contains ")
+				.ok_or_else(|| err(format!("expected \"The ... floor contains ....\": {}", line)))?;
+			let floor = FLOORS.iter().position(|f| *f == floor).ok_or_else(|| err(format!("unknown floor: {}", floor)))?;
+			if std::mem::replace(&mut seen_floors[floor], true) {
+				return Err(err(format!("{} floor described twice", FLOORS[floor])));
+			}
+			if items == "nothing relevant" {
+				continue;
+			}
+			for item in items.split(", ").flat_map(|i| i.split(" and ")).map(|i| i.strip_prefix("and ").unwrap_or(i)) {
+				let (element,is_gen) = parse_item(item).map_err(err)?;
+				let id = names.iter().position(|n| *n == element).unwrap_or_else(|| {
+					names.push(element);
+					gens.push(None);
+					chips.push(None);
+					names.len() - 1
+				});
+				let slot = if is_gen { &mut gens[id] } else { &mut chips[id] };
+				if slot.replace(floor as i8 + 1).is_some() {
+					return Err(err(format!("second {} {}", element, if is_gen { "generator" } else { "microchip" })));
+				}
+			}
+		}
+		if let Some(missing) = seen_floors.iter().position(|s| !s) {
+			return Err(format!("the {} floor is not described", FLOORS[missing]));
+		}
+		let mut chip_floors = Vec::new();
+		let mut gen_floors = Vec::new();
+		for (id,name) in names.iter().enumerate() {
+			chip_floors.push(chips[id].ok_or_else(|| format!("{} generator without a microchip", name))?);
+			gen_floors.push(gens[id].ok_or_else(|| format!("{}-compatible microchip without a generator", name))?);
+		}
+		Ok(State::new(&chip_floors,&gen_floors))
+	}
+}
+
 impl State {
 	fn new(chip_floors:&[i8],gen_floors:&[i8]) -> State {
 		let chips = chip_floors.iter().enumerate().map(|(i,f)|Chip{id:i as u8,floor:*f}).collect();
 		let gens = gen_floors.iter().enumerate().map(|(i,f)|Gen{id:i as u8,floor:*f}).collect();
 		State{chips,gens,ev:1}
 	}
-	fn data() -> State {
-		let chips = vec![1,1,3,2,2];
-		let gens = vec![1,1,2,2,2];
-		State::new(&chips,&gens)
-	}
-	fn data2() -> State {
-		let chips = vec![1,1,3,2,2,1,1];
-		let gens = vec![1,1,2,2,2,1,1];
-		State::new(&chips,&gens)
+	fn add_pair(&mut self, floor: i8) {
+		let id = self.chips.len() as u8;
+		self.chips.push(Chip{id,floor});
+		self.gens.push(Gen{id,floor});
 	}
 	fn test() -> State {
 		State::new(&[1,1],&[2,3])
 	}
 
-	fn get_hash(&self) -> u64 {
-		let mut s = DefaultHasher::new();
-		self.hash(&mut s);
-		s.finish()
+	/// The same for states that only differ in which element is which,
+	/// as the way to the top from either is just as long.
+	fn canonical(&self) -> (i8, Vec<(i8,i8)>) {
+		let mut pairs : Vec<(i8,i8)> = self.chips.iter().zip(&self.gens).map(|(c,g)| (c.floor,g.floor)).collect();
+		pairs.sort_unstable();
+		(self.ev, pairs)
+	}
+
+	fn finished(&self) -> State {
+		let top = vec![4; self.chips.len()];
+		State{ev:4,..State::new(&top,&top)}
 	}
 
 	fn is_done(&self) -> bool {
@@ -142,80 +210,68 @@ impl State {
 		}
 	}
 
-	fn get_moves(&self) -> Vec<State> {
-		// only do down moves with one item
+	/// Every state one trip of the elevator away. Items go alone either
+	/// way, but two at once only up, or only down when searching back from
+	/// the top.
+	fn moves(&self, pairs_up: bool) -> Vec<State> {
 		let mut moves = Vec::new();
 		let chips = self.chips(self.ev);
 		let gens = self.gens(self.ev);
 		for (i,chip) in chips.iter().enumerate() {
 			for chip2 in &chips[i..] {
-				if let Some(s) = self.movechip(*chip,*chip2,true) {
-					moves.push(s);
-				}
-				if chip.id == chip2.id {
-					if let Some(s) = self.movechip(*chip,*chip2,false) {
-						moves.push(s);
+				let alone = chip.id == chip2.id;
+				for up in [true,false] {
+					if alone || up == pairs_up {
+						moves.extend(self.movechip(*chip,*chip2,up));
 					}
 				}
 			}
 		}
 		for (i,gen) in gens.iter().enumerate() {
 			for gen2 in &gens[i..] {
-				if let Some(s) = self.movegen(*gen,*gen2,true) {
-					moves.push(s);
-				}
-				if gen.id == gen2.id {
-					if let Some(s) = self.movegen(*gen,*gen2,false) {
-						moves.push(s);
+				let alone = gen.id == gen2.id;
+				for up in [true,false] {
+					if alone || up == pairs_up {
+						moves.extend(self.movegen(*gen,*gen2,up));
 					}
 				}
 			}
 			for chip in &chips {
-				if let Some(s) = self.moveboth(*chip,*gen,true) {
-					moves.push(s);
-				}
+				moves.extend(self.moveboth(*chip,*gen,pairs_up));
 			}
 		}
 		moves
 	}
 }
 
-fn depth_search(mut current : Vec<State>, seen: &mut HashSet<u64>) -> u32 {
-	let mut v = Vec::new();
-	for o in current.drain(..) {
-
-		let mut new_moves : Vec<State> = o.get_moves().into_iter().filter(|m| !seen.contains(&m.get_hash())).collect();
-
-		for nm in &new_moves {
-			if nm.is_done() {
-				return 1
-			}
-
-			seen.insert(nm.get_hash());
-		}
-		v.append(&mut new_moves);
-	}
-	depth_search(v,seen) + 1
+fn count( start: State ) -> u32 {
+	let goal = start.finished();
+	search::bidirectional(start, goal, |s| s.moves(true), |s| s.moves(false), State::canonical).unwrap() as u32
 }
 
-fn count( start: State ) -> u32 {
-	let mut seen : HashSet<u64> = HashSet::new();
-	seen.insert(start.get_hash());
-	depth_search(vec![start],&mut seen)
+pub struct Day {
+	start: State,
 }
 
-fn main() {
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		Day { start: input.parse().unwrap_or_else(|e| panic!("{}", e)) }
+	}
 
-	let now = std::time::SystemTime::now();
-	let moves = count(State::test());
-	println!("test done at count {}, took {}ms",moves,now.elapsed().unwrap().as_millis());
-	let now = std::time::SystemTime::now();
-	let moves = count(State::data());
-	println!("part 1 done at count {}, took {}ms",moves,now.elapsed().unwrap().as_millis());
-	let now = std::time::SystemTime::now();
-	let moves = count(State::data2());
-	println!("part 2 done at count {}, took {}ms",moves,now.elapsed().unwrap().as_millis());
+	fn part1(&self) -> u32 {
+		count(self.start.clone())
+	}
 
+	fn part2(&self) -> u32 {
+		// the elerium and dilithium found on the first floor
+		let mut start = self.start.clone();
+		start.add_pair(1);
+		start.add_pair(1);
+		count(start)
+	}
 }
 
 #[cfg(test)]
@@ -240,7 +296,7 @@ mod tests {
 		assert!(t.is_valid());
 		t.gens[0].floor = 1;
 		assert!(!t.is_valid());
-		assert!(State::data().is_valid());
+		assert!(input().is_valid());
 	}
 
 	#[test]
@@ -258,6 +314,55 @@ mod tests {
 	}
 	#[test]
 	fn test_can_count_part1(){
-		assert_eq!(37,count(State::data()));
+		assert_eq!(37,count(input()));
+	}
+
+	fn input() -> State {
+		std::fs::read_to_string("2016/11.txt").unwrap().parse().unwrap()
+	}
+
+	#[test]
+	fn test_parse(){
+		let s = input();
+		assert_eq!(vec![1,1,3,2,2],s.chips.iter().map(|c| c.floor).collect::<Vec<i8>>());
+		assert_eq!(vec![1,1,2,2,2],s.gens.iter().map(|g| g.floor).collect::<Vec<i8>>());
+		assert_eq!(1,s.ev);
+		assert!(s.finished().is_done());
+	}
+
+	const EXAMPLE: &str = "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.
+";
+
+	#[test]
+	fn test_parse_example(){
+		assert_eq!(Ok(State::test()),EXAMPLE.parse());
+	}
+
+	#[test]
+	fn test_parse_errors(){
+		let parse = |s: &str| s.parse::<State>().unwrap_err();
+		assert_eq!("line 1: unknown floor: fifth",parse("The fifth floor contains nothing relevant."));
+		assert_eq!("line 2: expected \"The ... floor contains ....\": The second floor has a hydrogen generator.",
+			parse(&EXAMPLE.replace("second floor contains","second floor has")));
+		assert_eq!("line 3: neither a generator nor a microchip: a lithium reactor",
+			parse(&EXAMPLE.replace("lithium generator","lithium reactor")));
+		assert_eq!("line 4: expected \"a ...\": nothing much",
+			parse(&EXAMPLE.replace("nothing relevant","nothing much")));
+		assert_eq!("lithium-compatible microchip without a generator",
+			parse(&EXAMPLE.replace("a lithium generator","nothing relevant")));
+		assert_eq!("line 3: second hydrogen generator",
+			parse(&EXAMPLE.replace("a lithium generator","a hydrogen generator")));
+		assert_eq!("the fourth floor is not described",parse(EXAMPLE.lines().take(3).collect::<Vec<_>>().join("\n").as_str()));
+	}
+
+	#[test]
+	fn test_canonical_ignores_names(){
+		let a = State::new(&[1,2],&[1,3]);
+		let b = State::new(&[2,1],&[3,1]);
+		assert_eq!(a.canonical(),b.canonical());
+		assert_ne!(a.canonical(),State::new(&[1,2],&[3,1]).canonical());
 	}
 }
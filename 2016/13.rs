@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use crate::search;
+use crate::solution::Solution;
 
 type Node = (i32, i32);
 
@@ -61,43 +62,10 @@ fn add_path(grid: &[&[bool]], path: &[Node], signal: Option<&[Node]>) -> String
 	output.into_iter().collect()
 }
 
-fn heuristic(node: Node, goal: Node) -> i32 {
-	let dx = node.0 - goal.0;
-	let dy = node.1 - goal.1;
-	dx * dx + dy * dy
+fn heuristic(node: Node, goal: Node) -> usize {
+	((node.0 - goal.0).abs() + (node.1 - goal.1).abs()) as usize
 }
 
-fn pop_current(open: &mut Vec<Node>, score: &HashMap<Node, i32>) -> Node {
-	let mut lowest: (usize, i32) = (0, std::i32::MAX);
-	for (i, n) in open.iter().enumerate() {
-		if let Some(f) = score.get(n) {
-			if f <= &lowest.1 {
-				lowest = (i, *f);
-			}
-		} else {
-			panic!("missing f score");
-		}
-	}
-	open.remove(lowest.0)
-}
-fn depth_first(current: Vec<Node>, mut visited: Vec<Node>, depth: i32, fav: u32) -> Vec<Node> {
-	let mut new = Vec::new();
-
-	for node in current.into_iter() {
-		let ns: Vec<Node> = neighbours(node)
-			.to_vec()
-			.into_iter()
-			.filter(|&n| !is_wall(fav, n) && !visited.contains(&n))
-			.collect();
-		new.extend_from_slice(&ns);
-		visited.extend_from_slice(&ns);
-	}
-
-	if depth == 1 {
-		return visited;
-	}
-	depth_first(new, visited, depth - 1, fav)
-}
 fn neighbours(node: Node) -> [Node; 4] {
 	[
 		(node.0 + 1, node.1),
@@ -107,86 +75,48 @@ fn neighbours(node: Node) -> [Node; 4] {
 	]
 }
 
-fn a_star(start: Node, goal: Node, fav: u32, walls: Option<&[&[bool]]>) -> Vec<Node> {
-	let mut open = vec![start];
-	let mut parent: HashMap<Node, Node> = HashMap::new();
-	let mut g_score: HashMap<Node, i32> = HashMap::new();
-	g_score.insert(start, 0);
-
-	let mut f_score: HashMap<Node, i32> = HashMap::new();
-	f_score.insert(start, heuristic(start, goal));
-
-	while !open.is_empty() {
-		let current = pop_current(&mut open, &f_score);
-		if current == goal {
-			let mut path = vec![goal];
-			loop {
-				let p = parent.get(&path.last().unwrap()).unwrap();
-				path.push(*p);
-				if p == &start {
-					return path;
-				}
-			}
-		}
+fn open_neighbours(fav: u32, node: Node) -> impl Iterator<Item = Node> {
+	neighbours(node).into_iter().filter(move |&n| !is_wall(fav, n))
+}
 
-		for neighbor in neighbours(current).iter().filter(|&n| !is_wall(fav, *n)) {
-			let tentative_g = g_score.get(&current).unwrap() + 1;
-			if !g_score.contains_key(&neighbor) || tentative_g < *g_score.get(&neighbor).unwrap() {
-				parent.insert(*neighbor, current);
-				g_score.insert(*neighbor, tentative_g);
-				f_score.insert(*neighbor, tentative_g + heuristic(*neighbor, goal));
-				if open.iter().all(|n| n != neighbor) {
-					open.push(*neighbor);
-				}
-			}
-		}
+/// The shortest path from start to goal, both included.
+fn shortest_path(start: Node, goal: Node, fav: u32) -> Vec<Node> {
+	search::a_star(
+		start,
+		|&n| open_neighbours(fav, n).map(|n| (n, 1)),
+		|&n| heuristic(n, goal),
+		|&n| n == goal,
+	)
+	.expect("no path found")
+	.states
+}
 
-		if let Some(w) = walls {
-			let keys: Vec<Node> = parent.keys().map(|k| k.clone()).collect();
-			let output = add_path(&w, &keys, Some(&open));
-			println!(
-				"{}{}{}",
-				termion::clear::All,
-				termion::cursor::Goto(1,1),
-				output
-			);
-			std::thread::sleep(std::time::Duration::from_millis(50));
-		}
-	}
+/// Every location at most `steps` steps away from the start.
+fn reachable(start: Node, steps: usize, fav: u32) -> Vec<Node> {
+	search::breadth_first(start, |&n| open_neighbours(fav, n))
+		.take_while(|&(_, depth)| depth <= steps)
+		.map(|(n, _)| n)
+		.collect()
+}
 
-	panic!("no path found")
+pub struct Day {
+	fav: u32,
 }
-fn main() {
-	let fav = 1352;
 
-	let walls = get_walls(fav, (0, 0), (42, 42));
-	let slicedwalls = wallslice(&walls);
-	let path = a_star((1, 1), (31, 39), fav, Some(&slicedwalls));
-	let part1path = add_path(&slicedwalls, &path, Some(&[(1, 1), (31, 39)]));
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
 
-	let fill = depth_first(vec![(1, 1)], vec![(1, 1)], 50, fav);
-	let part2path = add_path(&slicedwalls, &fill, None);
+	fn parse(input: &str) -> Self {
+		Day { fav: input.trim().parse().unwrap() }
+	}
 
-	println!(
-		"{}{}part 1 total {} nodes, {} moves{}part 2 total {} nodes\n",
-		termion::clear::All,
-		termion::cursor::Goto(1, 2),
-		&path.len(),
-		&path.len() - 1,
-		termion::cursor::Right(20),
-		fill.len()
-	);
+	fn part1(&self) -> usize {
+		shortest_path((1, 1), (31, 39), self.fav).len() - 1
+	}
 
-	let mut part2lines = part2path.lines();
-	for line in part1path.lines() {
-			if let Some(b) = part2lines.next() {
-				println!(
-					"{}{}{}",
-					line,
-					termion::cursor::Right(5),
-					b
-				);
-			}
+	fn part2(&self) -> usize {
+		reachable((1, 1), 50, self.fav).len()
 	}
 }
 
@@ -214,7 +144,7 @@ mod tests {
 	fn test_draw_path() {
 		let w = get_walls(10, (0, 0), (10, 4));
 		let walls = wallslice(&w);
-		let r = a_star((1, 1), (7, 4), 10, None);
+		let r = shortest_path((1, 1), (7, 4), 10);
 		assert_eq!(
 			" # #### ##\n .#  #   #\n#... ##   \n###.# ### \n",
 			add_path(&walls, &r, None)
@@ -225,7 +155,7 @@ mod tests {
 	fn test_depth_search() {
 		let w = get_walls(10, (0, 0), (10, 4));
 		let walls = wallslice(&w);
-		let r = depth_first(vec![(1, 1)], vec![(1, 1)], 1, 10);
+		let r = reachable((1, 1), 1, 10);
 		assert_eq!(3, r.len());
 		assert_eq!(
 			" # #### ##\n..#  #   #\n#.   ##   \n### # ### \n",
@@ -236,7 +166,7 @@ mod tests {
 	fn test_depth_search2() {
 		let w = get_walls(10, (0, 0), (10, 4));
 		let walls = wallslice(&w);
-		let r = depth_first(vec![(1, 1)], vec![(1, 1)], 3, 10);
+		let r = reachable((1, 1), 3, 10);
 		assert_eq!(6, r.len());
 		assert_eq!(
 			".# #### ##\n..#  #   #\n#... ##   \n### # ### \n",
@@ -246,23 +176,23 @@ mod tests {
 
 	#[test]
 	fn test_astar() {
-		let r = a_star((1, 1), (7, 4), 10, None);
+		let r = shortest_path((1, 1), (7, 4), 10);
 
 		assert_eq!(12, r.len());
 		assert_eq!(
 			vec![
-				(7, 4),
-				(6, 4),
-				(6, 5),
-				(5, 5),
-				(4, 5),
-				(4, 4),
-				(3, 4),
-				(3, 3),
-				(3, 2),
-				(2, 2),
+				(1, 1),
 				(1, 2),
-				(1, 1)
+				(2, 2),
+				(3, 2),
+				(3, 3),
+				(3, 4),
+				(4, 4),
+				(4, 5),
+				(5, 5),
+				(6, 5),
+				(7, 5),
+				(7, 4)
 			],
 			r
 		);
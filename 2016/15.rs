@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 
 struct Disc {
 	index: u32,
@@ -10,15 +11,13 @@ impl Disc {
 		Disc{index,size,start}
 	}
 
-	fn init() -> Vec<Disc> {
-		vec![
-			Disc::new(1,13,1),
-			Disc::new(2,19,10),
-			Disc::new(3,3,2),
-			Disc::new(4,7,1),
-			Disc::new(5,5,3),
-			Disc::new(6,17,5),
-		]
+	fn parse(line: &str) -> Disc {
+		let numbers: Vec<u32> = line
+			.split(|c: char| !c.is_ascii_digit())
+			.filter(|s| !s.is_empty())
+			.map(|s| s.parse().unwrap())
+			.collect();
+		Disc::new(numbers[0],numbers[1],numbers[3])
 	}
 
 	fn test() -> Vec<Disc> {
@@ -42,13 +41,27 @@ fn get_first(discs:&[Disc]) -> u32 {
 	panic!("found end of ints");
 }
 
-fn main() {
-	let mut discs = Disc::init();
+pub struct Day {
+	discs: Vec<Disc>,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
 
-	println!("first {}",get_first(&discs));
+	fn parse(input: &str) -> Self {
+		Day { discs: input.lines().map(Disc::parse).collect() }
+	}
+
+	fn part1(&self) -> u32 {
+		get_first(&self.discs)
+	}
 
-	discs.push(Disc{index:7,size:11,start:0});
-	println!("second {}",get_first(&discs));
+	fn part2(&self) -> u32 {
+		let mut discs: Vec<Disc> = self.discs.iter().map(|d| Disc::new(d.index,d.size,d.start)).collect();
+		discs.push(Disc::new(discs.len() as u32 + 1,11,0));
+		get_first(&discs)
+	}
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 // ditch the arithmetic for now
 
 struct Data {
@@ -76,12 +78,31 @@ mod tests {
 	}
 }
 
-fn main() {
-	let mut d = Data::make("00101000101111010");
-	d.expand_to(272);
-	println!("checksum: {}",d.chksm(272));
+pub struct Day {
+	seed: String,
+}
+
+impl Day {
+	fn fill(&self, size: usize) -> String {
+		let mut d = Data::make(&self.seed);
+		d.expand_to(size);
+		d.chksm(size)
+	}
+}
+
+impl Solution for Day {
+	type Part1 = String;
+	type Part2 = String;
 
-	let mut d = Data::make("00101000101111010");
-	d.expand_to(35651584);
-	println!("checksum 2: {}",d.chksm(35651584));
-}
\ No newline at end of file
+	fn parse(input: &str) -> Self {
+		Day { seed: input.trim().to_string() }
+	}
+
+	fn part1(&self) -> String {
+		self.fill(272)
+	}
+
+	fn part2(&self) -> String {
+		self.fill(35651584)
+	}
+}
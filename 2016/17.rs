@@ -1,7 +1,8 @@
-extern crate crypto;
-use crypto::digest::Digest;
-use crypto::md5::Md5;
+use crate::md5;
+use crate::search;
+use crate::solution::Solution;
 
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Path {
 	node: (i8, i8),
 	steps: String,
@@ -28,12 +29,7 @@ impl Path {
 	}
 
 	fn next(&self, key: &str) -> Vec<Path> {
-		let mut data = key.to_owned();
-		data.push_str(&self.steps);
-		let mut digest = Md5::new();
-		digest.input_str(&data);
-		let md5 = digest.result_str();
-		let b: Vec<u8> = md5.bytes().collect();
+		let b = md5::hex_bytes(&md5::Prefix::new(key.as_bytes()).digest(self.steps.as_bytes()));
 		let mut ret = Vec::new();
 		if b[0] > 97 && self.node.1 > 0 {
 			ret.push(Path::new(
@@ -72,35 +68,21 @@ impl Path {
 	}
 }
 
-fn depth_longest(mut current: Vec<Path>, key: &str, mut longest: Option<Path>) -> Path {
-	let mut next = Vec::new();
-
-	if current.is_empty() {
-		return longest.unwrap();
-	}
-
-	for p in current.into_iter() {
-		if p.is_done() {
-			longest = Some(p);
-		} else {
-			next.extend(p.next(key));
-		}
-	}
-
-	depth_longest(next, key, longest)
+/// Every way to the vault, shortest first.
+fn routes(key: &str) -> impl Iterator<Item = Path> + '_ {
+	search::breadth_first(Path::start(), move |p| if p.is_done() { Vec::new() } else { p.next(key) })
+		.map(|(p, _)| p)
+		.filter(Path::is_done)
 }
-fn depth_first(current: Vec<Path>, key: &str) -> Path {
-	let mut next = Vec::new();
 
-	for p in current.into_iter() {
-		if p.is_done() {
-			return p;
-		}
-		next.extend(p.next(key));
-	}
+fn shortest(key: &str) -> Path {
+	routes(key).next().unwrap()
+}
 
-	depth_first(next, key)
+fn longest(key: &str) -> Path {
+	routes(key).last().unwrap()
 }
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -129,15 +111,15 @@ mod tests {
 	fn test_demopasses() {
 		assert_eq!(
 			"DDRRRD",
-			depth_first(vec![Path::start()], &"ihgpwlah").steps
+			shortest("ihgpwlah").steps
 		);
 		assert_eq!(
 			"DDUDRLRRUDRD",
-			depth_first(vec![Path::start()], &"kglvqrro").steps
+			shortest("kglvqrro").steps
 		);
 		assert_eq!(
 			"DRURDRUDDLLDLUURRDULRLDUUDDDRR",
-			depth_first(vec![Path::start()], &"ulqzkmiv").steps
+			shortest("ulqzkmiv").steps
 		);
 	}
 
@@ -145,18 +127,32 @@ mod tests {
 	fn test_longest() {
 		assert_eq!(
 			370,
-			depth_longest(vec![Path::start()], &"ihgpwlah", None).steps.len()
+			longest("ihgpwlah").steps.len()
 		);
 		assert_eq!(
 			492,
-			depth_longest(vec![Path::start()], &"kglvqrro", None).steps.len()
+			longest("kglvqrro").steps.len()
 		);
 	}
 }
 
-fn main() {
-	let p = depth_first(vec![Path::start()], &"hhhxzeay");
-	println!("depth first: {}", p.steps);
-	let p = depth_longest(vec![Path::start()], &"hhhxzeay", None);
-	println!("longest: {} steps", p.steps.len());
+pub struct Day {
+	passcode: String,
+}
+
+impl Solution for Day {
+	type Part1 = String;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { passcode: input.trim().to_string() }
+	}
+
+	fn part1(&self) -> String {
+		shortest(&self.passcode).steps
+	}
+
+	fn part2(&self) -> usize {
+		longest(&self.passcode).steps.len()
+	}
 }
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 
 struct Floor {
 	rows : Vec<Row>
@@ -110,6 +111,27 @@ impl Row {
 	// }
 }
 
+pub struct Day {
+	first_row: String,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { first_row: input.trim().to_string() }
+	}
+
+	fn part1(&self) -> usize {
+		Floor::new(&self.first_row, 40).count_safe()
+	}
+
+	fn part2(&self) -> usize {
+		Row::new(&self.first_row).count_smart(400000)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
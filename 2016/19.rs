@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 // 1
 // 1x 1
 // 123 1x3 xx3
@@ -77,6 +79,27 @@ fn log_2(x: u32) -> u32 {
 	num_bits::<u32>() as u32 - x.leading_zeros() - 1
 }
 
+pub struct Day {
+	elves: u32,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		Day { elves: input.trim().parse().unwrap() }
+	}
+
+	fn part1(&self) -> u32 {
+		get_winner(self.elves)
+	}
+
+	fn part2(&self) -> u32 {
+		calc_threes(self.elves)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -1,6 +1,4 @@
-fn file() -> String {
-	std::fs::read_to_string("2.txt").unwrap()
-}
+use crate::solution::Solution;
 
 fn coord_to_nr(x:i32,y:i32) -> i32 {
 	y*3+x + 1
@@ -135,18 +133,31 @@ fn get_complex(data: &str) -> Vec<char> {
 	complex
 }
 
-fn main() {
-	let data : &str = &file();
-	let mut simple : Vec<i32> = Vec::new();
-	for line in data.lines(){
-		let coords = get_coords(line).unwrap();
-		let button =coord_to_nr(coords.0, coords.1);
-		simple.push(button);
+pub struct Day {
+	instructions: String,
+}
+
+impl Solution for Day {
+	type Part1 = String;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Self {
+		Day { instructions: input.to_string() }
 	}
-	println!("1: {:?}",simple);
 
-	let complex = get_complex(data);
-	println!("2: {:?}",complex);
+	fn part1(&self) -> String {
+		self.instructions
+			.lines()
+			.map(|line| {
+				let coords = get_coords(line).unwrap();
+				coord_to_nr(coords.0, coords.1).to_string()
+			})
+			.collect()
+	}
+
+	fn part2(&self) -> String {
+		get_complex(&self.instructions).into_iter().collect()
+	}
 }
 
 #[cfg(test)]
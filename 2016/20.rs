@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 
 struct Range {
 	from: u32,
@@ -26,18 +27,35 @@ fn first(blacklist:&[Range])->u32{
 	panic!("no open ranges found");
 }
 
-fn main(){
-	let mut blacklist = std::fs::read_to_string("20.txt").unwrap()
+fn blacklist(input: &str) -> Vec<Range> {
+	let mut blacklist = input
 		.lines()
 		.map(|l| Range::new(l) )
 		.collect::<Vec<Range>>();
 
-		blacklist.sort_by(|a,b| a.from.partial_cmp(&b.from).unwrap() );
+	blacklist.sort_by(|a,b| a.from.partial_cmp(&b.from).unwrap() );
+	blacklist
+}
+
+pub struct Day {
+	blacklist: Vec<Range>,
+}
 
-		println!("first open: {}", first(&blacklist));
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
 
-		println!("total count: {}",count(&blacklist));
+	fn parse(input: &str) -> Self {
+		Day { blacklist: blacklist(input) }
+	}
 
+	fn part1(&self) -> u32 {
+		first(&self.blacklist)
+	}
+
+	fn part2(&self) -> u32 {
+		count(&self.blacklist)
+	}
 }
 
 #[cfg(test)]
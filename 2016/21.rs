@@ -1,5 +1,4 @@
-#[macro_use]
-extern crate lazy_static;
+use crate::solution::Solution;
 use regex::Regex;
 use std::collections::VecDeque;
 
@@ -259,17 +258,31 @@ move position 3 to position 0"
 	}
 }
 
-fn main() {
-	let mut s = Scramble::new( "abcdefgh" );
-	let filestr = std::fs::read_to_string("21.txt").unwrap();
-	for instr in filestr.lines() {
-		s.parse_apply(instr,false);
+pub struct Day {
+	instructions: String,
+}
+
+impl Solution for Day {
+	type Part1 = String;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Self {
+		Day { instructions: input.to_string() }
 	}
-	println!("final scramble: {}",s);
 
-	let mut s = Scramble::new( "fbgdceah" );
-	for instr in filestr.lines().rev() {
-		s.parse_apply(instr,true);
+	fn part1(&self) -> String {
+		let mut s = Scramble::new( "abcdefgh" );
+		for instr in self.instructions.lines() {
+			s.parse_apply(instr,false);
+		}
+		s.to_string()
 	}
-	println!("final unscramble: {}",s);
-}
\ No newline at end of file
+
+	fn part2(&self) -> String {
+		let mut s = Scramble::new( "fbgdceah" );
+		for instr in self.instructions.lines().rev() {
+			s.parse_apply(instr,true);
+		}
+		s.to_string()
+	}
+}
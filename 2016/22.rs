@@ -1,5 +1,5 @@
-#[macro_use]
-extern crate lazy_static;
+use crate::grid::Grid;
+use crate::solution::Solution;
 use regex::Regex;
 
 struct Node {
@@ -53,41 +53,34 @@ impl Node {
 	}
 }
 
-struct Grid {
-	nodes: Vec<Node>,
-	index: usize,
-	row_size: usize,
+struct Cluster {
+	nodes: Grid<Node>,
+	empty: (usize,usize),
 	steps: u32,
 }
 
-impl std::fmt::Display for Grid {
+impl std::fmt::Display for Cluster {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-		let mut grid = String::new();
-
-		for row in self.nodes.chunks(self.row_size) {
-			for n in row{
-				grid += &n.to_string();
-			}
-			grid += "\n";
-		}
-		write!(f,"{}{}Steps: {}\n{}",termion::clear::All,termion::cursor::Goto(1,1),self.steps,grid)
+		// clear the screen and go back to the top
+		writeln!(f,"\x1b[2J\x1b[1;1HSteps: {}\n{}",self.steps,self.nodes)
 	}
 }
 
-impl Grid {
+impl Cluster {
 	fn step(&mut self, dir:char){
-		let newindex = match dir {
-			'L' => self.index - 1,
-			'R' => self.index + 1,
-			'U' => self.index - self.row_size,
-			'D' => self.index + self.row_size,
+		let (x,y) = self.empty;
+		let next = match dir {
+			'L' => (x - 1, y),
+			'R' => (x + 1, y),
+			'U' => (x, y - 1),
+			'D' => (x, y + 1),
 			_ => panic!("bad direction"),
 		};
-		self.nodes.swap(self.index,newindex);
-		self.index = newindex;
+		self.nodes.swap(self.empty,next);
+		self.empty = next;
 		self.steps += 1;
 	}
-	fn new(data:&str)->Grid{
+	fn new(data:&str)->Cluster{
 		let mut nodes = data.lines().skip(2).map(Node::new).collect::<Vec<Node>>();
 
 		let mut row_size = 0u8;
@@ -107,21 +100,19 @@ impl Grid {
 			}
 		});
 
-		let index : &usize = &nodes.iter().enumerate()
-			.filter(|(_,n)| n.used == 0)
-			.map(|(i,_)|i)
-			.next().unwrap();
-		nodes[row_size as usize].marker = Some('X');
+		let mut nodes = Grid::from_cells(row_size as usize + 1, nodes);
+		let empty = nodes.find(|n| n.used == 0).unwrap();
+		nodes[(row_size as usize,0)].marker = Some('X');
 
-		Grid{nodes,row_size:row_size as usize + 1,steps:0,index: *index}
+		Cluster{nodes,steps:0,empty}
 	}
 
 	fn count_viable(&self) -> u32 {
 
 		let mut counter = 0u32;
-		for a in 0..self.nodes.len(){
-			for b in 0..self.nodes.len() {
-				if self.nodes[a].viable(&self.nodes[b]) {
+		for a in self.nodes.iter(){
+			for b in self.nodes.iter() {
+				if a.viable(b) {
 					counter += 1;
 				}
 			}
@@ -130,26 +121,33 @@ impl Grid {
 	}
 }
 
-fn animate(grid:&mut Grid,steps:&str){
-	for c in steps.chars() {
-		grid.step(c);
-		println!("{}",grid.to_string());
-
-		std::thread::sleep(std::time::Duration::from_millis(50));
-	}
+pub struct Day {
+	df: String,
 }
 
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
 
-fn main() {
-	let mut grid = Grid::new(&std::fs::read_to_string("22.txt").unwrap());
-
-	animate(&mut grid,"LLLLUUUUUUURRRRRRRRRRUUUUUR");
-	for _ in 0..=32 {
-		animate(&mut grid,"DLLUR");
+	fn parse(input: &str) -> Self {
+		Day { df: input.to_string() }
 	}
 
-	println!("found {} pairs",grid.count_viable());
+	fn part1(&self) -> u32 {
+		Cluster::new(&self.df).count_viable()
+	}
 
+	// the route was worked out by printing the grid after each step: bring
+	// the empty node next to the goal data, then shuffle the goal one step
+	// left per cycle
+	fn part2(&self) -> u32 {
+		let mut grid = Cluster::new(&self.df);
+		let route = "LLLLUUUUUUURRRRRRRRRRUUUUUR".to_string() + &"DLLUR".repeat(33);
+		for c in route.chars() {
+			grid.step(c);
+		}
+		grid.steps
+	}
 }
 
 #[cfg(test)]
@@ -1,11 +1,24 @@
+use crate::solution::Solution;
+
 fn check(v:&mut Vec<i32>) -> bool {
 	v.sort();
 	v[0] + v[1] > v[2]
 }
 
-fn main() {
-	let input = std::fs::read_to_string("3.txt").unwrap();
+fn count_rows(input: &str) -> u32 {
 	let mut count = 0;
+
+	for line in input.lines() {
+		let mut nr :Vec<i32> = line.split(' ').filter(|t| t != &"" ).map(|t| t.parse::<i32>().unwrap() ).collect();
+		if check(&mut nr) {
+			count+=1;
+		}
+	}
+
+	count
+}
+
+fn count_columns(input: &str) -> u32 {
 	let mut count2 = 0;
 
 	let mut c1 : Vec<i32> = Vec::new();
@@ -13,13 +26,10 @@ fn main() {
 	let mut c3 : Vec<i32> = Vec::new();
 
 	for line in input.lines() {
-		let mut nr :Vec<i32> = line.split(' ').filter(|t| t != &"" ).map(|t| t.parse::<i32>().unwrap() ).collect();
+		let nr :Vec<i32> = line.split(' ').filter(|t| t != &"" ).map(|t| t.parse::<i32>().unwrap() ).collect();
 		c1.push(nr[0]);
 		c2.push(nr[1]);
 		c3.push(nr[2]);
-		if check(&mut nr) {
-			count+=1;
-		}
 		if c1.len() == 3 {
 			if check(&mut c1) {
 				count2 +=1;
@@ -36,7 +46,26 @@ fn main() {
 		}
 	}
 
+	count2
+}
+
+pub struct Day {
+	input: String,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		Day { input: input.to_string() }
+	}
+
+	fn part1(&self) -> u32 {
+		count_rows(&self.input)
+	}
 
-	println!("1 possible: {}",count);
-	println!("2 possible: {}",count2);
-}
\ No newline at end of file
+	fn part2(&self) -> u32 {
+		count_columns(&self.input)
+	}
+}
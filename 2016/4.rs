@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 // Finally, you come across an information kiosk with a list of rooms. Of course, the list is encrypted and full of
 // decoy data, but the instructions to decode the list are barely hidden nearby. Better remove the decoy data first.
 
@@ -92,18 +94,29 @@ fn parse(data: &str) -> Vec<Room> {
 	v
 }
 
-fn main() {
-	let data = std::fs::read_to_string("4.txt").unwrap();
-	let r: Vec<Room> = parse(&data);
-	let mut sum = 0u32;
-	for room in r.iter().filter(|r| r.real() ) {
-		sum += room.id;
-		let d = room.decrypt();
-		if d.contains("north") {
-			println!("{} -> {}",room.id,d);
-		}
+pub struct Day {
+	rooms: Vec<Room>,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		Day { rooms: parse(input) }
+	}
+
+	fn part1(&self) -> u32 {
+		self.rooms.iter().filter(|r| r.real() ).map(|r| r.id).sum()
+	}
+
+	fn part2(&self) -> u32 {
+		self.rooms.iter()
+			.filter(|r| r.real() )
+			.find(|r| r.decrypt().contains("north"))
+			.unwrap()
+			.id
 	}
-	println!("sum is {}",sum);
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 struct Collector {
@@ -44,14 +45,34 @@ impl Collector {
 	}
 }
 
-fn main() {
+fn collect(input: &str) -> Collector {
 	let mut c = Collector::new(8);
 
-	for l in std::fs::read_to_string("6.txt").unwrap().lines() {
+	for l in input.lines() {
 		c.add(&l);
 	}
-	println!("common {}",c.read_word(true));
-	println!("uncommon {}",c.read_word(false));
+	c
+}
+
+pub struct Day {
+	messages: String,
+}
+
+impl Solution for Day {
+	type Part1 = String;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Self {
+		Day { messages: input.to_string() }
+	}
+
+	fn part1(&self) -> String {
+		collect(&self.messages).read_word(true)
+	}
+
+	fn part2(&self) -> String {
+		collect(&self.messages).read_word(false)
+	}
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 fn is_tls(line: &str) -> bool {
 	let mut bracket = false;
 	let mut found = false;
@@ -62,19 +64,31 @@ fn is_ssl(line: &str) -> bool {
 	false
 }
 
-fn main() {
-	let count = std::fs::read_to_string("7.txt")
-		.unwrap()
-		.lines()
-		.filter(|l| is_tls(&l))
-		.count();
-	println!("count tls {}", count);
-	let count = std::fs::read_to_string("7.txt")
-		.unwrap()
-		.lines()
-		.filter(|l| is_ssl(&l))
-		.count();
-	println!("count ssl {}", count);
+pub struct Day {
+	addresses: String,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { addresses: input.to_string() }
+	}
+
+	fn part1(&self) -> usize {
+		self.addresses
+			.lines()
+			.filter(|l| is_tls(l))
+			.count()
+	}
+
+	fn part2(&self) -> usize {
+		self.addresses
+			.lines()
+			.filter(|l| is_ssl(l))
+			.count()
+	}
 }
 
 #[cfg(test)]
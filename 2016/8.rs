@@ -1,3 +1,6 @@
+use crate::grid::Grid;
+use crate::solution::Solution;
+
 struct Instr {
 	command: Command,
 	x: usize,
@@ -34,39 +37,36 @@ impl Instr {
 }
 
 struct Screen {
-	size: (usize, usize),
-	data: Vec<Vec<bool>>,
+	data: Grid<bool>,
 }
 
 impl Screen {
 
 	fn pxls(&self) -> usize {
-		self.data.iter().flat_map(|v| v.iter()).filter(|&b| *b).collect::<Vec<&bool>>().len()
+		self.data.iter().filter(|&b| *b).count()
 	}
 	fn new() -> Screen {
-		let size = (50, 6);
 		Screen {
-			size,
-			data: vec![ vec![false;size.0] ; size.1],
+			data: Grid::new(50, 6, false),
 		}
 	}
 
 	fn rect(&mut self, instr: &Instr) {
 		for x in 0..instr.x {
 			for y in 0..instr.y {
-				self.data[y][x]=true;
+				self.data[(x,y)]=true;
 			}
 		}
 	}
 	fn colrot(&mut self, instr: &Instr) {
-		let mut cpy : Vec<bool> = self.data.iter().map(|v| v[instr.x]).collect();
+		let mut cpy : Vec<bool> = self.data.column(instr.x).copied().collect();
 		cpy.rotate_right(instr.y);
-		for ix in 0..self.size.1 {
-			self.data[ix][instr.x] = cpy[ix];
+		for (y,b) in cpy.into_iter().enumerate() {
+			self.data[(instr.x,y)] = b;
 		}
 	}
 	fn rowrot(&mut self, instr: &Instr) {
-		self.data[instr.x].rotate_right(instr.y);
+		self.data.row_mut(instr.x).rotate_right(instr.y);
 	}
 
 	fn apply(&mut self, instr: &Instr) {
@@ -80,23 +80,36 @@ impl Screen {
 
 impl std::fmt::Display for Screen {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-		for row in &self.data {
-			let s =
-				String::from_utf8(row.iter().map(|&b| if b { 35 } else { 46 }).collect()).unwrap();
-			if let Err(e) = writeln!(f, "{}", s) {
-				return Err(e)
-			}
-		}
-		Ok(())
+		writeln!(f, "{}", self.data.map(|&b| if b { '#' } else { '.' }))
 	}
 }
 
-fn main() {
-	let input = std::fs::read_to_string("8.txt").unwrap();
+fn run(input: &str) -> Screen {
 	let mut screen = Screen::new();
 	for instr in input.lines().map(|l|Instr::new(&l)) {
 		screen.apply(&instr);
-		println!("{} c: {}",screen,screen.pxls());
+	}
+	screen
+}
+
+pub struct Day {
+	instructions: String,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Self {
+		Day { instructions: input.to_string() }
+	}
+
+	fn part1(&self) -> usize {
+		run(&self.instructions).pxls()
+	}
+
+	fn part2(&self) -> String {
+		run(&self.instructions).to_string()
 	}
 }
 
@@ -170,14 +183,14 @@ assert_eq!(r".#..#.#...........................................
 	#[test]
 	fn test_screen_has_data() {
 		let s = Screen::new();
-		assert_eq!(6, s.data.len());
+		assert_eq!(6, s.data.height());
 	}
 
 	#[test]
 	fn test_screen_has_tostring() {
 		let mut s = Screen::new();
-		s.data[0][33] = true;
-		s.data[0][34] = true;
+		s.data[(33,0)] = true;
+		s.data[(34,0)] = true;
 		assert_eq!(r".................................##...............
 ..................................................
 ..................................................
@@ -1,5 +1,4 @@
-#[macro_use]
-extern crate lazy_static;
+use crate::solution::Solution;
 
 fn count_recurse(input: &str, deep: bool) -> usize {
 	lazy_static! {
@@ -27,10 +26,26 @@ fn count_recurse(input: &str, deep: bool) -> usize {
 		input.len()
 	}
 }
-fn main() {
-	let inp = std::fs::read_to_string("9.txt").unwrap();
-	println!("simple {}", count_recurse(&inp, false));
-	println!("full {}", count_recurse(&inp, true));
+
+pub struct Day {
+	file: String,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { file: input.trim().to_string() }
+	}
+
+	fn part1(&self) -> usize {
+		count_recurse(&self.file, false)
+	}
+
+	fn part2(&self) -> usize {
+		count_recurse(&self.file, true)
+	}
 }
 
 #[cfg(test)]
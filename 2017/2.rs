@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 
 #[cfg(test)]
 mod tests {
@@ -10,10 +12,25 @@ mod tests {
 	}
 }
 
-fn main() {
-	let s = Sheet::new(&std::fs::read_to_string("2017/2.txt").unwrap());
-	println!("part 1: {}",s.chk());
-	println!("part 2: {}",s.div());
+pub struct Day {
+	sheet: Sheet,
+}
+
+impl Solution for Day {
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &str) -> Self {
+		Day { sheet: Sheet::new(input) }
+	}
+
+	fn part1(&self) -> i32 {
+		self.sheet.chk()
+	}
+
+	fn part2(&self) -> i32 {
+		self.sheet.div()
+	}
 }
 
 struct Sheet {
@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 fn path_length(ix:usize) -> usize {
 	if ix <= 1 {
 		return 0
@@ -103,7 +105,7 @@ fn toix(x:usize,y:usize)->usize{
 	y*1000+x
 }
 
-fn main() {
+fn first_larger(target: u32) -> u32 {
 	let mut grid = vec![0u32;1_000_000];
 	let mut x = 500;
 	let mut y = 500;
@@ -122,9 +124,8 @@ fn main() {
 		grid[toix(x,y-1)] +
 		grid[toix(x,y+1)];
 
-		if current > 265149 {
-			println!( "{}", current );
-			break;
+		if current > target {
+			return current;
 		}
 
 		grid[toix(x,y)] = current;
@@ -161,4 +162,25 @@ fn main() {
 			panic!("oh oh");
 		}
 	}
-}
\ No newline at end of file
+}
+
+pub struct Day {
+	square: u32,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		Day { square: input.trim().parse().unwrap() }
+	}
+
+	fn part1(&self) -> usize {
+		path_length(self.square as usize)
+	}
+
+	fn part2(&self) -> u32 {
+		first_larger(self.square)
+	}
+}
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 
 #[cfg(test)]
 mod tests {
@@ -28,8 +29,7 @@ fn isvalid(pw:Vec<&str>) -> bool {
 //For added security, yet another system policy has been put in place. Now, a valid passphrase must contain no two words that are anagrams of each other - that is, a passphrase is invalid if any word's letters can be rearranged to form any other word in the passphrase.
 
 
-fn main() {
-	let data = std::fs::read_to_string("./2017/4.txt").unwrap();
+fn count_unique(data: &str) -> u32 {
 	let mut valid = 0u32;
 	'mainline: for line in data.lines() {
 		let mut set = std::collections::HashSet::new();
@@ -43,14 +43,36 @@ fn main() {
 		valid += 1;
 	}
 
-	println!("part 1: {}",valid);
+	valid
+}
 
+fn count_no_anagrams(data: &str) -> u32 {
 	let mut part2 = 0;
 	for line in data.lines() {
 		if isvalid(line.split_whitespace().into_iter().collect()) {
 			part2 += 1;
 		}
 	}
-	println!("part 2: {}",part2);
+	part2
+}
+
+pub struct Day {
+	input: String,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
 
-}
\ No newline at end of file
+	fn parse(input: &str) -> Self {
+		Day { input: input.to_string() }
+	}
+
+	fn part1(&self) -> u32 {
+		count_unique(&self.input)
+	}
+
+	fn part2(&self) -> u32 {
+		count_no_anagrams(&self.input)
+	}
+}
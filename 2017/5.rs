@@ -1,54 +1,89 @@
-fn count( mut prg: Vec<i32> ) -> u32 {
-	let mut px = 0isize;
-	let mut counter = 0;
+use crate::solution::Solution;
+use crate::vm::{self, Flow, Instruction, Machine};
+use std::str::FromStr;
 
-	while px >= 0 && px < prg.len() as isize {
-		let jmp = prg[px as usize];
-		prg[px as usize] += 1;
-		counter += 1;
-		px += jmp as isize;
+/// A jump that changes its own offset every time it is taken.
+#[derive(Debug, Clone, Copy)]
+struct Offset {
+	jump: isize,
+	/// Large offsets shrink instead of growing.
+	strange: bool,
+}
+
+impl FromStr for Offset {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Offset, String> {
+		Ok(Offset { jump: vm::offset(s)?, strange: false })
 	}
-	return counter;
 }
-fn count2( mut prg: Vec<i32> ) -> u32 {
-	let mut px = 0isize;
-	let mut counter = 0;
-
-	while px >= 0 && px < prg.len() as isize {
-		let jmp = prg[px as usize];
-		if jmp >= 3 {
-			prg[px as usize] -= 1;
+
+impl Instruction for Offset {
+	type Word = ();
+	const REGISTERS: usize = 0;
+	const OPCODES: &'static [&'static str] = &["jump"];
+
+	fn opcode(&self) -> usize {
+		0
+	}
+
+	fn execute(&mut self, _: &mut [()]) -> Flow {
+		let jump = self.jump;
+		if self.strange && jump >= 3 {
+			self.jump -= 1;
 		}
 		else {
-			prg[px as usize] += 1;
+			self.jump += 1;
 		}
-		counter += 1;
-		px += jmp as isize;
+		Flow::Jump(jump)
+	}
+}
+
+fn steps(mut m: Machine<Offset>, strange: bool) -> u32 {
+	for offset in m.program.iter_mut() {
+		offset.strange = strange;
 	}
-	return counter;
+	m.run();
+	m.steps as u32
 }
 
-fn main() {
-	let data = std::fs::read_to_string("./2017/5.txt").unwrap();
+pub struct Day {
+	machine: Machine<Offset>,
+}
 
-	let prg : Vec<i32> = data.lines().map(|l| l.parse::<i32>().unwrap()).collect();
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
 
-	println!("part 1: {}",count(prg));
+	fn parse(input: &str) -> Self {
+		Day { machine: Machine::parse(input).unwrap_or_else(|e| panic!("{}", e)) }
+	}
+
+	fn part1(&self) -> u32 {
+		steps(self.machine.clone(), false)
+	}
 
-	let prg2 : Vec<i32> = data.lines().map(|l| l.parse::<i32>().unwrap()).collect();
-	println!("part 2: {}",count2(prg2));
+	fn part2(&self) -> u32 {
+		steps(self.machine.clone(), true)
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	const EXAMPLE: &str = "0\n3\n0\n1\n-3\n";
+
 	#[test]
 	fn test_part1() {
-		assert_eq!(5,count(vec![0,3,0,1,-3]));
+		assert_eq!(5,Day::parse(EXAMPLE).part1());
+	}
+	#[test]
+	fn test_parse() {
+		assert_eq!(Err("line 2: bad offset: x".to_string()),Machine::<Offset>::parse("0\nx").map(|_| ()));
 	}
 	#[test]
 	fn test_part2() {
-		assert_eq!(10,count2(vec![0,3,0,1,-3]));
+		assert_eq!(10,Day::parse(EXAMPLE).part2());
 	}
 }
@@ -0,0 +1,10 @@
+#[path = "1.rs"]
+pub mod day1;
+#[path = "2.rs"]
+pub mod day2;
+#[path = "3.rs"]
+pub mod day3;
+#[path = "4.rs"]
+pub mod day4;
+#[path = "5.rs"]
+pub mod day5;
@@ -1,5 +1,10 @@
+use crate::solution::Solution;
+
 fn file() -> Vec<i32> {
-	std::fs::read_to_string("2018/1.txt").unwrap().lines().map(|t| t.parse::<i32>().unwrap()).collect()
+	parse(&std::fs::read_to_string("2018/1.txt").unwrap())
+}
+fn parse(data: &str) -> Vec<i32> {
+	data.lines().map(|t| t.parse::<i32>().unwrap()).collect()
 }
 fn first(track: Vec<i32>)->i32{
 	let mut seen : std::collections::HashSet<i32> = std::collections::HashSet::new();
@@ -15,13 +20,25 @@ fn first(track: Vec<i32>)->i32{
 	}
 }
 
-fn main() {
-	let r = file();
-	let s : i32 = r.iter().sum();
-	println!("part 1: {}",s);
+pub struct Day {
+	changes: Vec<i32>,
+}
 
-	let t = first(r);
-	println!("part 2: {}",t);
+impl Solution for Day {
+	type Part1 = i32;
+	type Part2 = i32;
+
+	fn parse(input: &str) -> Self {
+		Day { changes: parse(input) }
+	}
+
+	fn part1(&self) -> i32 {
+		self.changes.iter().sum()
+	}
+
+	fn part2(&self) -> i32 {
+		first(self.changes.clone())
+	}
 }
 
 #[cfg(test)]
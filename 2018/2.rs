@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 fn tokenize(line:&str) -> HashMap<u8,u8> {
@@ -39,32 +40,42 @@ fn count(list:&[HashMap<u8,u8>]) -> (u32,u32) {
 		}
 		misses == 1
 	}
-fn main(){
-	let dstring = std::fs::read_to_string("2018/2.txt").unwrap();
-	let data : Vec<HashMap<u8,u8>> = dstring.clone().lines().map(|l| tokenize(l) ).collect();
-	let (t2,t3) = count(&data);
 
-	println!("part 1: {} * {} = {}",t2,t3,t2*t3);
+pub struct Day {
+	ids: String,
+}
+
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = String;
 
-	let mut d2 = Vec::new();
-	for l in dstring.lines() {
-		d2.push( l.as_bytes() );
+	fn parse(input: &str) -> Self {
+		Day { ids: input.to_string() }
 	}
 
-	for i in 0..d2.len()-1 {
-		for j in i..d2.len() {
-			if equalenough(&d2[i], &d2[j]) {
-				println!("part2: {:?} {:?}",
-					std::str::from_utf8(&d2[i]),
-					std::str::from_utf8(&d2[j]));
-			}
-		}
+	fn part1(&self) -> u32 {
+		let data : Vec<HashMap<u8,u8>> = self.ids.lines().map(tokenize).collect();
+		let (t2,t3) = count(&data);
+		t2*t3
 	}
 
+	fn part2(&self) -> String {
+		let d2 : Vec<&[u8]> = self.ids.lines().map(|l| l.as_bytes()).collect();
 
+		for i in 0..d2.len()-1 {
+			for j in i..d2.len() {
+				if equalenough(d2[i], d2[j]) {
+					return d2[i].iter().zip(d2[j].iter())
+						.filter(|(a,b)| a == b)
+						.map(|(&a,_)| a as char)
+						.collect();
+				}
+			}
+		}
+		panic!("no matching ids")
+	}
 }
 
-
 #[cfg(test)]
 mod tests{
 	use super::*;
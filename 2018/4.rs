@@ -1,16 +1,31 @@
+use crate::solution::Solution;
 use std::collections::HashMap;
 use regex::Regex;
 
-fn main(){
-	let guards = parse(read());
-	let r = guards.values().enumerate().max_by_key(|p| p.1.sum() ).unwrap();
-	println!("part1: {}", (r.1.id as i32) * r.1.max_ix()as i32);
-	let r = guards.values().enumerate().max_by_key(|p| p.1.max_val() ).unwrap();
-	println!("part2: {}", r.1.id*r.1.max_ix());
+pub struct Day {
+	guards: HashMap<u16,Guard>,
 }
 
-fn read()-> Vec<String>{
-	let data = std::fs::read_to_string("2018/4.txt").unwrap();
+impl Solution for Day {
+	type Part1 = u32;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		Day { guards: parse(read(input)) }
+	}
+
+	fn part1(&self) -> u32 {
+		let r = self.guards.values().max_by_key(|g| g.sum() ).unwrap();
+		r.id as u32 * r.max_ix() as u32
+	}
+
+	fn part2(&self) -> u32 {
+		let r = self.guards.values().max_by_key(|g| g.max_val() ).unwrap();
+		r.id as u32 * r.max_ix() as u32
+	}
+}
+
+fn read(data: &str)-> Vec<String>{
 	let mut lines : Vec<&str> = data.lines().collect();
 	lines.sort_by(|a, b| a.partial_cmp(b).unwrap());
 	let mut s : Vec<String> = Vec::new();
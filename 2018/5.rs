@@ -1,21 +1,36 @@
-fn main(){
-	let data = std::fs::read_to_string("2018/5.txt").unwrap();
-	let result = minimize_str(&data);
-	println!("part 1: {}",result.len());
+use crate::solution::Solution;
 
-	let mut shortest = usize::MAX;
+pub struct Day {
+	polymer: String,
+}
 
-	for x in 65..91 {
-		let tmp = data.as_bytes().to_owned();
-		let t2 = extract(&tmp,&x);
-		let f = minimize(t2);
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
 
-		if f.len() < shortest {
-			shortest = f.len();
-		}
+	fn parse(input: &str) -> Self {
+		Day { polymer: input.trim().to_string() }
 	}
 
-	println!("part 2: {}",shortest);
+	fn part1(&self) -> usize {
+		minimize_str(&self.polymer).len()
+	}
+
+	fn part2(&self) -> usize {
+		let mut shortest = usize::MAX;
+
+		for x in 65..91 {
+			let tmp = self.polymer.as_bytes().to_owned();
+			let t2 = extract(&tmp,&x);
+			let f = minimize(t2);
+
+			if f.len() < shortest {
+				shortest = f.len();
+			}
+		}
+
+		shortest
+	}
 }
 
 fn extract( input:&[u8], target:&u8 ) -> Vec<u8> {
@@ -1,5 +1,7 @@
+use crate::grid::Grid;
+use crate::solution::Solution;
 use console::Term;
-use std::{collections::HashMap, fs::File, io::Write};
+use std::collections::HashMap;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum MarkState {
@@ -30,75 +32,65 @@ impl Mark {
     }
 }
 
+/// The coordinates laid out on a grid reaching one step past them on every
+/// side. Claims spreading further out than that are dropped; reaching the
+/// border is enough to know an area goes on forever.
 struct Field {
     limit_top: isize,
     limit_bottom: isize,
     limit_left: isize,
     limit_right: isize,
-    rows: HashMap<isize, HashMap<isize, Mark>>,
+    sources: Vec<(isize, isize)>,
+    marks: Grid<Option<Mark>>,
 }
 impl Field {
     fn new(data: &str) -> Field {
-        let mut rows: HashMap<isize, HashMap<isize, Mark>> = HashMap::new();
-        let mut index = 0;
-        let mut limit_top = isize::MAX;
-        let mut limit_bottom = isize::MIN;
-        let mut limit_left = isize::MAX;
-        let mut limit_right = isize::MIN;
-        for line in data.lines() {
-            let spl: Vec<isize> = line
-                .split(", ")
-                .map(|a| a.parse().unwrap())
-                .collect::<Vec<isize>>();
-            let coord: isize = *spl.get(0).unwrap();
-            let r: isize = *spl.get(1).unwrap();
-            let row: &mut HashMap<isize, Mark> = rows.entry(r).or_insert_with(|| HashMap::new());
-            if coord > limit_right {
-                limit_right = coord;
-            }
-            if coord < limit_left {
-                limit_left = coord;
-            }
-            if r > limit_bottom {
-                limit_bottom = r;
-            }
-            if r < limit_top {
-                limit_top = r;
-            }
-            row.insert(
-                coord,
-                Mark {
-                    owner: Some(index),
-                    state: MarkState::Source,
-                },
-            );
-            index += 1;
-        }
-        Field {
-            rows,
+        let sources: Vec<(isize, isize)> = data
+            .lines()
+            .map(|line| {
+                let spl: Vec<isize> = line.split(", ").map(|a| a.parse().unwrap()).collect();
+                (spl[1], spl[0])
+            })
+            .collect();
+        let limit_top = sources.iter().map(|s| s.0).min().unwrap();
+        let limit_bottom = sources.iter().map(|s| s.0).max().unwrap();
+        let limit_left = sources.iter().map(|s| s.1).min().unwrap();
+        let limit_right = sources.iter().map(|s| s.1).max().unwrap();
+        let width = (limit_right - limit_left + 3) as usize;
+        let height = (limit_bottom - limit_top + 3) as usize;
+        let mut field = Field {
+            limit_top,
             limit_bottom,
             limit_left,
             limit_right,
-            limit_top,
+            marks: Grid::new(width, height, None),
+            sources,
+        };
+        for (index, (row, coord)) in field.sources.clone().into_iter().enumerate() {
+            let pos = field.position(row, coord).unwrap();
+            field.marks[pos] = Some(Mark {
+                owner: Some(index as u8),
+                state: MarkState::Source,
+            });
         }
+        field
+    }
+    /// Where on the grid a point is, if it is kept at all.
+    fn position(&self, row: isize, coord: isize) -> Option<(usize, usize)> {
+        self.marks
+            .inside(coord - self.limit_left + 1, row - self.limit_top + 1)
     }
     fn weight(&self, row: isize, coord: isize) -> isize {
         let mut sum = 0;
-        for (r, map) in &self.rows {
-            for (c, _) in map {
-                let dy = row - r;
-                let dx = coord - c;
-                sum += dx.abs() + dy.abs();
-            }
+        for (r, c) in &self.sources {
+            let dy = row - r;
+            let dx = coord - c;
+            sum += dx.abs() + dy.abs();
         }
         sum
     }
     fn len(&self) -> usize {
-        let mut sum = 0;
-        for r in &self.rows {
-            sum += r.1.len();
-        }
-        sum
+        self.marks.iter().flatten().count()
     }
     fn at_edge(&self, row: isize, coord: isize) -> bool {
         row < self.limit_top
@@ -107,56 +99,52 @@ impl Field {
             || coord > self.limit_right
     }
     fn get(&mut self, row: isize, coord: isize) -> Option<&mut Mark> {
-        match self.rows.get_mut(&row) {
-            Some(r) => match r.get_mut(&coord) {
-                Some(m) => Some(m),
-                None => None,
-            },
-            None => None,
-        }
+        let (x, y) = self.position(row, coord)?;
+        self.marks.get_mut(x, y)?.as_mut()
     }
     fn add(&mut self, row: isize, coord: isize, owner: Option<u8>) {
         let owner = match owner {
             Some(o) => o,
             None => panic!("invalid add"),
         };
-        //  if !self.at_edge(row, coord) {
-        match self.get(row, coord) {
+        let Some(pos) = self.position(row, coord) else {
+            return;
+        };
+        match &mut self.marks[pos] {
             Some(s) => match s.state {
                 MarkState::Source => (),
                 MarkState::Claimed => {
                     if s.owner.unwrap() != owner {
-                        (*s).state = MarkState::Tied;
-                        (*s).owner = None;
+                        s.state = MarkState::Tied;
+                        s.owner = None;
                     }
                 }
                 MarkState::Empty => panic!("illegal operation"),
                 MarkState::Tied => (),
                 MarkState::Owned => (),
             },
-            None => {
-                let rr: &mut HashMap<isize, Mark> =
-                    self.rows.entry(row).or_insert_with(|| HashMap::new());
-                rr.insert(
-                    coord,
-                    Mark {
-                        owner: Some(owner),
-                        state: MarkState::Claimed,
-                    },
-                );
+            empty => {
+                *empty = Some(Mark {
+                    owner: Some(owner),
+                    state: MarkState::Claimed,
+                });
             }
         }
-        //  }
+    }
+    /// Every mark on the grid along with its row and coordinate.
+    fn marks(&self) -> impl Iterator<Item = (isize, isize, &Mark)> {
+        self.marks.enumerate().filter_map(|((x, y), m)| {
+            let row = y as isize + self.limit_top - 1;
+            let coord = x as isize + self.limit_left - 1;
+            m.as_ref().map(|m| (row, coord, m))
+        })
     }
     fn step(&mut self, steptype: MarkState) {
-        let mut coords: Vec<(isize, isize, Option<u8>)> = Vec::new();
-        for (rowline, rowdata) in &self.rows {
-            for (coord, entry) in rowdata {
-                if entry.state == steptype {
-                    coords.push((*rowline, *coord, entry.owner));
-                }
-            }
-        }
+        let coords: Vec<(isize, isize, Option<u8>)> = self
+            .marks()
+            .filter(|(_, _, entry)| entry.state == steptype)
+            .map(|(row, coord, entry)| (row, coord, entry.owner))
+            .collect();
         for (x, y, owner) in coords {
             self.add(x - 1, y, owner);
             self.add(x + 1, y, owner);
@@ -166,31 +154,21 @@ impl Field {
     }
     fn commit(&mut self) {
         // commit all claims into owned tiles
-        for (_, rowdata) in self.rows.iter_mut() {
-            for (_, entry) in rowdata.iter_mut() {
-                if entry.state == MarkState::Claimed {
-                    entry.state = MarkState::Owned;
-                }
+        for entry in self.marks.iter_mut().flatten() {
+            if entry.state == MarkState::Claimed {
+                entry.state = MarkState::Owned;
             }
         }
     }
     fn to_string(&self, x: isize, y: isize, w: usize, h: usize) -> String {
         let mut lines = Vec::with_capacity(h);
         for row in y..(y + h as isize) {
-            let mut rowdata = vec!['.'; w];
-            //            rowdata.fill('.');
-            match self.rows.get(&row) {
-                None => {}
-                Some(r) => {
-                    for (index, entry) in r {
-                        let delta = index - x;
-                        if delta >= 0 && delta < w as isize {
-                            rowdata[delta as usize] = entry.to_char();
-                        }
-                    }
-                }
-            }
-            let linedata = rowdata.iter().collect::<String>();
+            let linedata: String = (x..x + w as isize)
+                .map(|coord| match self.position(row, coord) {
+                    Some(pos) => self.marks[pos].map_or('.', |m| m.to_char()),
+                    None => '.',
+                })
+                .collect();
             lines.push(linedata);
         }
         return lines.join(&"\n");
@@ -222,19 +200,17 @@ struct Counter {
 }
 fn count_max(field: &Field) -> u64 {
     let mut counters: HashMap<u8, Counter> = HashMap::new();
-    for (row, map) in &field.rows {
-        for (coord, entry) in map {
-            if let Some(e) = entry.owner {
-                if entry.state == MarkState::Owned || entry.state == MarkState::Source {
-                    let counter = counters.entry(e).or_insert(Counter {
-                        count: 0,
-                        infinite: false,
-                    });
-                    if field.at_edge(*row, *coord) {
-                        counter.infinite = true;
-                    }
-                    counter.count += 1;
+    for (row, coord, entry) in field.marks() {
+        if let Some(e) = entry.owner {
+            if entry.state == MarkState::Owned || entry.state == MarkState::Source {
+                let counter = counters.entry(e).or_insert(Counter {
+                    count: 0,
+                    infinite: false,
+                });
+                if field.at_edge(row, coord) {
+                    counter.infinite = true;
                 }
+                counter.count += 1;
             }
         }
     }
@@ -254,48 +230,57 @@ fn count_max(field: &Field) -> u64 {
     // sum
 }
 
-fn main() {
-    let data = std::fs::read_to_string("2018/6.txt").unwrap();
-    let mut field = Field::new(&data);
+pub struct Day {
+    coordinates: String,
+}
 
-    field.step(MarkState::Source);
-    field.commit();
-    let mut lastmax = 0;
+impl Solution for Day {
+    type Part1 = u64;
+    type Part2 = u32;
 
-    loop {
-        field.step(MarkState::Owned);
-        field.commit();
-        let max = count_max(&field);
-        if max == lastmax {
-            break;
+    fn parse(input: &str) -> Self {
+        Day {
+            coordinates: input.to_string(),
         }
-        lastmax = max;
     }
 
-    let printed = field.to_string(0, 0, 1000, 1000);
-    let mut buffer = File::create("./result.txt").unwrap();
-    buffer.write_all(printed.as_bytes()).unwrap();
+    fn part1(&self) -> u64 {
+        let mut field = Field::new(&self.coordinates);
 
-    let max = count_max(&field);
+        field.step(MarkState::Source);
+        field.commit();
+        let mut lastmax = 0;
 
-    println!("max: {}", max);
-    // 97189 is too high
-    // 5820 is too high
+        loop {
+            field.step(MarkState::Owned);
+            field.commit();
+            let max = count_max(&field);
+            if max == lastmax {
+                break;
+            }
+            lastmax = max;
+        }
+
+        count_max(&field)
+        // 97189 is too high
+        // 5820 is too high
+    }
 
-    let data = std::fs::read_to_string("2018/6.txt").unwrap();
-    let field = Field::new(&data);
-    let weightlimit = 10000;
-    let mut counter = 0;
-    for row in 1..500 {
-        for coord in 100..500 {
-            let w = field.weight(row, coord);
-            let inside = w < weightlimit;
-            if inside {
-                counter += 1;
+    fn part2(&self) -> u32 {
+        let field = Field::new(&self.coordinates);
+        let weightlimit = 10000;
+        let mut counter = 0;
+        for row in 1..500 {
+            for coord in 100..500 {
+                let w = field.weight(row, coord);
+                let inside = w < weightlimit;
+                if inside {
+                    counter += 1;
+                }
             }
         }
+        counter
     }
-    println!("inside count: {}", counter);
 }
 
 #[cfg(test)]
@@ -305,7 +290,8 @@ mod tests {
     #[test]
     fn test_can_parse_field() {
         let c = Field::new("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9");
-        assert_eq!(1, c.rows.get(&6).unwrap().len());
+        assert_eq!(1, c.marks().filter(|m| m.0 == 6).count());
+        assert_eq!(6, c.len());
     }
     #[test]
     fn test_can_print_field() {
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::collections::{HashMap, VecDeque};
 
 struct Dependency {
@@ -120,7 +121,6 @@ fn crunch2(d: &Dependencies, worker_count: u8, task_time: u32) -> u32 {
             if busy_worker_count >= worker_count {
                 break;
             }
-            println!("starting task {} at {}", next, time);
             in_progress.push(Task::new(next, task_time, time));
             busy_worker_count += 1;
         }
@@ -139,7 +139,6 @@ fn crunch2(d: &Dependencies, worker_count: u8, task_time: u32) -> u32 {
             if !t.is_done(time) {
                 true
             } else {
-                println!("task {} complete at {}", t.id, time);
                 busy_worker_count -= 1;
                 done.push(t.id);
                 false
@@ -149,13 +148,28 @@ fn crunch2(d: &Dependencies, worker_count: u8, task_time: u32) -> u32 {
 
     time
 }
-fn main() {
-    let data = std::fs::read_to_string("2018/7.txt").unwrap();
-    let d = Dependencies::new(&data);
-    let result = crunch(&d);
-    println!("order: {}", &result);
-    let seconds = crunch2(&d, 6, 60);
-    println!("seconds: {}", &seconds); // 1683 is too high
+
+pub struct Day {
+    dependencies: Dependencies,
+}
+
+impl Solution for Day {
+    type Part1 = String;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            dependencies: Dependencies::new(input),
+        }
+    }
+
+    fn part1(&self) -> String {
+        crunch(&self.dependencies)
+    }
+
+    fn part2(&self) -> u32 {
+        crunch2(&self.dependencies, 6, 60) // 1683 is too high
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 struct Node {
@@ -97,11 +98,27 @@ impl Nodes {
     //  }
 }
 
-fn main() {
-    let data = std::fs::read_to_string("2018/8.txt").unwrap();
-    let mut basenode = Node::new(&data);
-    println!("metadata: {}", &basenode.sum());
-    println!("part2: {}", &basenode.part2());
+pub struct Day {
+    license: String,
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            license: input.to_string(),
+        }
+    }
+
+    fn part1(&self) -> u32 {
+        Node::new(&self.license).sum()
+    }
+
+    fn part2(&self) -> u32 {
+        Node::new(&self.license).part2()
+    }
 }
 
 #[cfg(test)]
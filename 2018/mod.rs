@@ -0,0 +1,14 @@
+#[path = "1.rs"]
+pub mod day1;
+#[path = "2.rs"]
+pub mod day2;
+#[path = "4.rs"]
+pub mod day4;
+#[path = "5.rs"]
+pub mod day5;
+#[path = "6.rs"]
+pub mod day6;
+#[path = "7.rs"]
+pub mod day7;
+#[path = "8.rs"]
+pub mod day8;
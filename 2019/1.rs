@@ -1,15 +1,28 @@
-fn main () {
-	let data = std::fs::read_to_string("2019/1.txt").unwrap();
+use crate::solution::Solution;
 
-	let modulemass = &data.lines().map(|l| l.parse::<u64>().unwrap()).collect::<Vec<u64>>();
+fn parse(data: &str) -> Vec<u64> {
+	data.lines().map(|l| l.parse::<u64>().unwrap()).collect()
+}
+
+pub struct Day {
+	masses: Vec<u64>,
+}
 
-	let modulefuel : u64 = modulemass.iter().map( |m| fuel(*m) ).sum();
+impl Solution for Day {
+	type Part1 = u64;
+	type Part2 = u64;
 
-	let totalfuel : u64 = modulemass.iter().map( |m| fuelforfuel(*m) ).sum();
+	fn parse(input: &str) -> Self {
+		Day { masses: parse(input) }
+	}
 
-	println!("module fuel cost: {}", modulefuel);
+	fn part1(&self) -> u64 {
+		self.masses.iter().map( |m| fuel(*m) ).sum()
+	}
 
-	println!("total fuel cost: {}", totalfuel);
+	fn part2(&self) -> u64 {
+		self.masses.iter().map( |m| fuelforfuel(*m) ).sum()
+	}
 }
 
 fn fuel(mass:u64)->u64{
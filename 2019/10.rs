@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::f64::consts::PI;
 use std::fmt;
 use std::collections::{HashMap, HashSet};
@@ -617,48 +618,57 @@ fn transpose_coords( coords:Vec<Coord>,base:&Coord) -> Vec<AsteroidInfo> {
 	}
 	v
 }
-fn main() {
-	let s = std::fs::read_to_string("2019/10.txt").unwrap();
-
-	let a = stat_astr(&s);
-
-	let winner = get_best(&a);
 
+pub struct Day {
+	map: Map,
+}
 
-	println!("row,col,score: {},{},{}", winner.0,winner.1,winner.2);
+impl Solution for Day {
+	type Part1 = u16;
+	type Part2 = isize;
 
-	let coords = get_coords(&a);
-	let infos = transpose_coords(coords,&(winner.0,winner.1));
-	let mut set : HashMap<i32,Vec<AsteroidInfo>> = HashMap::new();
-	for info in infos{
-		let list:&mut Vec<AsteroidInfo> = set.entry(info.angle).or_insert_with(||Vec::new());
-		list.push(info);
+	fn parse(input: &str) -> Self {
+		Day { map: stat_astr(input) }
 	}
-	for k in set.values_mut(){
-		k.sort_by(|a, b| b.magnitude.cmp(&a.magnitude));
+
+	fn part1(&self) -> u16 {
+		get_best(&self.map).2
 	}
-	let mut angles : Vec<i32> = set.keys().cloned().collect();
-	angles.sort_by(|a, b| a.cmp(&b));
 
-	let mut hit = 0;
-	while hit < 100 {
-		for angle in angles.iter() {
-			if let Some(v) = set.get_mut(angle) {
-				if let Some(a) = v.pop(){
-					hit += 1;
-					println!("{}: {},{}",hit,a.absolute_coord.0,a.absolute_coord.1);
-					if hit == 200 {
-						println!("x * 100 + y = {}",a.absolute_coord.0*100+a.absolute_coord.1);
+	fn part2(&self) -> isize {
+		let winner = get_best(&self.map);
+
+		let coords = get_coords(&self.map);
+		let infos = transpose_coords(coords,&(winner.0,winner.1));
+		let mut set : HashMap<i32,Vec<AsteroidInfo>> = HashMap::new();
+		for info in infos{
+			let list:&mut Vec<AsteroidInfo> = set.entry(info.angle).or_default();
+			list.push(info);
+		}
+		for k in set.values_mut(){
+			k.sort_by_key(|a| std::cmp::Reverse(a.magnitude));
+		}
+		let mut angles : Vec<i32> = set.keys().cloned().collect();
+		angles.sort();
+
+		let mut hit = 0;
+		loop {
+			for angle in angles.iter() {
+				if let Some(v) = set.get_mut(angle) {
+					if let Some(a) = v.pop(){
+						hit += 1;
+						if hit == 200 {
+							return a.absolute_coord.0*100+a.absolute_coord.1;
+						}
 					}
+				} else {
+					panic!("aaaa");
 				}
-			} else {
-				panic!("aaaa");
 			}
 		}
 	}
-
-
 }
+
 impl AsteroidInfo {
 	fn new(absolute: Coord, base: &Coord) -> AsteroidInfo {
 		let relative = (absolute.0-base.0,absolute.1-base.1);
@@ -1,5 +1,6 @@
+use crate::solution::Solution;
 use regex::Regex;
-use std::{collections::HashMap, fs, time::SystemTime};
+use std::collections::HashMap;
 use primal::Sieve;
 #[cfg(test)]
 mod tests {
@@ -240,7 +241,7 @@ impl Moon1D {
     }
 }
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Clone)]
 struct Moon {
     pos: Vec3,
     vel: Vec3,
@@ -302,13 +303,26 @@ impl Moon {
         }
     }
 }
-fn get_moons() -> Vec<Moon> {
-    vec![
-        Moon::new(MoonLabel::Io, -4, 3, 15),
-        Moon::new(MoonLabel::Ganymede, -11, -10, 13),
-        Moon::new(MoonLabel::Callisto, 2, 2, 18),
-        Moon::new(MoonLabel::Europa, 7, -1, 0),
-    ]
+fn parse_moons(input: &str) -> Vec<Moon> {
+    let parser = Regex::new(r"<x= *([0-9-]+), y= *([0-9-]+), z= *([0-9-]+)>").unwrap();
+    let labels = [
+        MoonLabel::Io,
+        MoonLabel::Ganymede,
+        MoonLabel::Callisto,
+        MoonLabel::Europa,
+    ];
+    parser
+        .captures_iter(input)
+        .zip(labels)
+        .map(|(c, label)| {
+            Moon::new(
+                label,
+                c[1].parse().unwrap(),
+                c[2].parse().unwrap(),
+                c[3].parse().unwrap(),
+            )
+        })
+        .collect()
 }
 
 fn equals1d(m1:&[Moon1D], m2:&[Moon1D]) -> bool {
@@ -339,27 +353,33 @@ fn get_period( fullmoons: &[Moon], dim:i8) -> u64 {
     return 0;
 }
 
-fn main() {
+pub struct Day {
+    moons: Vec<Moon>,
+}
 
-	let now = SystemTime::now();
-    let mut moons = get_moons();
-    for n in 0..1000 {
-        simulate_step(&mut moons);
-    }
-    let energy: i32 = moons.iter().map(|m| m.total_energy()).sum();
-    println!("after 1000: {}", energy);
+impl Solution for Day {
+    type Part1 = i32;
+    type Part2 = u128;
 
-    let moons = get_moons();
-    let periods : Vec<u64> = vec![1i8,2,3]
-        .into_iter()
-        .map(|dim| get_period(&moons, dim))
-        .collect();
+    fn parse(input: &str) -> Self {
+        Day {
+            moons: parse_moons(input),
+        }
+    }
 
-    println!("periods: {:?}",periods);
-    let fact = factorize(&periods);
-    println!("total: {}",fact);
-	if let Ok(t) = now.elapsed() {
-		println!("took {}", t.as_millis());
-	}
+    fn part1(&self) -> i32 {
+        let mut moons = self.moons.clone();
+        for _ in 0..1000 {
+            simulate_step(&mut moons);
+        }
+        moons.iter().map(|m| m.total_energy()).sum()
+    }
 
+    fn part2(&self) -> u128 {
+        let periods: Vec<u64> = vec![1i8, 2, 3]
+            .into_iter()
+            .map(|dim| get_period(&self.moons, dim))
+            .collect();
+        factorize(&periods)
+    }
 }
@@ -1,14 +1,14 @@
+use crate::intcode::trace::recorder_from_env;
+use crate::intcode::{Prg, Queue, Stop};
+use crate::solution::Solution;
 use std::collections::HashMap;
-use std::io::Stdout;
-use std::sync::mpsc::Receiver;
-use std::sync::mpsc::Sender;
-use std::thread;
-use std::sync::mpsc;
-use std::time::Duration;
-use termion::input::TermRead;
-use termion::raw::IntoRawMode;
-use termion::raw::RawTerminal;
-use std::io::{Write, stdout, stdin};
+use std::collections::VecDeque;
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Names a file to write every frame of the game to.
+const FRAMES_VAR: &str = "ARCADE_FRAMES";
 
 fn as_char(output:i64)->char{
 	match output {
@@ -21,326 +21,259 @@ fn as_char(output:i64)->char{
 	}
 }
 
-fn main() {
-	let mut p = Prg::new(&std::fs::read_to_string("2019/13.txt").unwrap());
-	let (_, input_rx) = mpsc::channel();
-	let (output_tx, output_rx) = mpsc::channel();
-	let handle = thread::spawn(move || {
-		p.run(input_rx,output_tx,false);
-	});
+/// Everything the cabinet has drawn so far.
+struct Screen {
+	tiles: HashMap<(i64,i64),i64>,
+	score: i64,
+	ball: (i64,i64),
+	ball_dir: (bool,bool),
+	paddle_x: i64,
+}
 
-	let mut tileset:HashMap<i64,i32> = HashMap::new();
-	loop {
-		if let Ok(x) = output_rx.recv_timeout(Duration::from_millis(100)) {
-			if let Ok(y) = output_rx.recv() {
-				if let Ok(o) = output_rx.recv() {
-					let counter = tileset.entry(o).or_default();
-					*counter += 1;
-				}
-			}
-		}
-		else {
-			break;
-		}
-	}
-	if let Err(_) = handle.join() {
-		panic!("couldnt join");
+impl Screen {
+	fn new() -> Screen {
+		Screen { tiles: HashMap::new(), score: 0, ball: (0,0), ball_dir: (true,true), paddle_x: -1 }
 	}
-	for (a,b) in tileset {
-		println!("{}: {}",as_char(a),b);
-	//  _: 1
-	//   : 442
-	//  #: 76
-	//  .: 236   // assuming linear score, but incorrect. different layers are probably worth different scores
-	//  o: 1
-	// guessed 15340
 
+	/// Takes in the cabinet output, three values per tile.
+	fn draw(&mut self, output: &mut VecDeque<i64>) {
+		while output.len() >= 3 {
+			let x = output.pop_front().unwrap();
+			let y = output.pop_front().unwrap();
+			let t = output.pop_front().unwrap();
+			self.update(x,y,t);
+		}
 	}
 
-	let mut p = Prg::new(&std::fs::read_to_string("2019/13.txt").unwrap());
-	p.data.write(0,2);
-	let (input_tx, input_rx) = mpsc::channel();
-	let (output_tx, output_rx) = mpsc::channel();
-	let handle = thread::spawn(move || {
-		p.run(input_rx,output_tx,false);
-	});
-	let mut keys = stdin().keys();
-	let mut stdout = stdout().into_raw_mode().unwrap();
-	write!(stdout,"{}",termion::clear::All).unwrap();
-	let mut paddle_x = -1;
-	let mut ball = (0,0);
-	let mut ball_dir = (true,true);
-	let mut score = 0;
-	loop{
-		if let Ok(x) = output_rx.recv_timeout(Duration::from_millis(100)) {
-			if let Ok( y ) = output_rx.recv() {
-				if let Ok( t ) = output_rx.recv() {
-					if x != -1 && t == 4 {
-						if x != ball.0 {
-							ball_dir = (x > ball.0,ball_dir.1);
-						}
-						if y != ball.1 {
-							ball_dir = (ball_dir.0,y > ball.1);
-						}
-						ball = (x,y);
-						write!(stdout,"{}ball x {} y {}",termion::cursor::Goto(2,26),x,y).unwrap();
-						write!(stdout,"{}ball _ {} _ {}",
-							termion::cursor::Goto(2,27),
-							if ball_dir.0 {'0'}else{'1'},
-							if ball_dir.1 {'0'}else{'1'}
-						).unwrap();
-					} else if t == 3 {
-						paddle_x = x;
-						write!(stdout,"{}pad {}",termion::cursor::Goto(2,24),x).unwrap();
-					}
-					if x==-1 && y==0 {
-						score = t;
-						if score > 0 {
-							write!(stdout,"{}score: {} ",termion::cursor::Goto(15,2),t).unwrap();
-						}
-					}
-					else {
-						let c = as_char(t);
-						write!(stdout,"{}{}",termion::cursor::Goto((x+2) as u16,(y +2)as u16),c).unwrap();
-					}
-				}
-				else {
-					panic!("failed to read t");
-				}
-			}
-			else {
-				panic!("failed to read y");
-			}
+	fn update(&mut self, x: i64, y: i64, t: i64) {
+		if x == -1 && y == 0 {
+			self.score = t;
+			return;
 		}
-		else {
-			write!(stdout,"{}",termion::cursor::Goto(1,1)).unwrap();
-			stdout.flush().unwrap();
-			let wait = if score == 0 {
-				500
-			} else if score < 100 {
-				300
-			} else if score < 200 {
-				100
-			} else {
-				1
-			};
-			std::thread::sleep(std::time::Duration::from_millis(wait));
-			let data = decision(ball,ball_dir,paddle_x,&mut stdout);
-			match input_tx.send(data) {
-				Ok(_) => {},
-				Err(_) => break,
+		if t == 4 {
+			if x != self.ball.0 {
+				self.ball_dir = (x > self.ball.0,self.ball_dir.1);
+			}
+			if y != self.ball.1 {
+				self.ball_dir = (self.ball_dir.0,y > self.ball.1);
 			}
+			self.ball = (x,y);
+		} else if t == 3 {
+			self.paddle_x = x;
 		}
+		self.tiles.insert((x,y),t);
 	}
 
+	fn blocks(&self) -> usize {
+		self.tiles.values().filter(|t| **t == 2).count()
+	}
 
-}
-
-fn decision(ball:(i64,i64),dball:(bool,bool),paddle:i64,stdout:&mut RawTerminal<Stdout>) -> i64 {
-	if ball.1 > 14 && dball.1 {
-		let delta_y = 18 - ball.1;
-		let absolute_x_ball = ball.0 + if dball.0 { delta_y } else { -1 * delta_y };
-		let expected_x_ball =
+	/// Where the ball will be when it gets down to the paddle, once it is low enough to tell.
+	fn expected_x(&self) -> Option<i64> {
+		if self.ball.1 <= 14 || !self.ball_dir.1 {
+			return None;
+		}
+		let delta_y = 18 - self.ball.1;
+		let absolute_x_ball = self.ball.0 + if self.ball_dir.0 { delta_y } else { -delta_y };
+		Some(
 			if absolute_x_ball < 0 {
-				(absolute_x_ball * -1) +1 // accounting for the wall
+				-absolute_x_ball +1 // accounting for the wall
 			} else if absolute_x_ball > 34 {
 				32 + ( absolute_x_ball - 34 )
-			} else { absolute_x_ball };
-		write!(stdout,
-			"{}                                           {}{} {}",
-			termion::cursor::Goto(0,23),
-			termion::cursor::Goto((expected_x_ball+1) as u16,23),
-			'x',
-			expected_x_ball
-		).unwrap();
-		if paddle == expected_x_ball {
-			0
-		}
-		else if paddle > expected_x_ball {
+			} else { absolute_x_ball })
+	}
+
+	/// The joystick position the autopilot picks.
+	fn decision(&self) -> i64 {
+		let paddle = self.paddle_x;
+		if let Some(expected_x_ball) = self.expected_x() {
+			if paddle == expected_x_ball {
+				0
+			}
+			else if paddle > expected_x_ball {
+				-1
+			} else {
+				1
+			}
+		} else if paddle == self.ball.0 {
+			if self.ball_dir.0 { 1 } else { -1 }
+		} else if paddle > self.ball.0 {
 			-1
 		} else {
 			1
 		}
-	} else if paddle == ball.0 {
-		if dball.0 { 1 } else { -1 }
-	} else if paddle > ball.0 {
-		-1
-	} else {
-		1
 	}
-}
 
-struct MemBank {
-	prg: Vec<i64>,
-	data:HashMap<usize,Vec<i64>>,
+	/// The score, then the screen as text, with the autopilot's guess for
+	/// where the ball lands marked below the paddle.
+	fn render(&self) -> String {
+		let width = self.tiles.keys().map(|p| p.0).max().unwrap_or(0) + 1;
+		let height = self.tiles.keys().map(|p| p.1).max().unwrap_or(0) + 1;
+		let mut frame = format!("score: {}\n",self.score);
+		for y in 0..height {
+			for x in 0..width {
+				frame.push(as_char(*self.tiles.get(&(x,y)).unwrap_or(&0)));
+			}
+			frame.push('\n');
+		}
+		if let Some(x) = self.expected_x() {
+			frame += &format!("{}x\n"," ".repeat(x as usize));
+		}
+		frame
+	}
 }
 
-struct Prg {
-	data: MemBank,
-	relative_base: i64,
-	pc: usize,
+fn count_blocks(input: &str) -> usize {
+	let mut p = Prg::new(input);
+	let mut io = Queue::default();
+	p.run_and_record(&mut io,false);
+	let mut screen = Screen::new();
+	screen.draw(&mut io.output);
+	//  _: 1
+	//   : 442
+	//  #: 76
+	//  .: 236   // assuming linear score, but incorrect. different layers are probably worth different scores
+	//  o: 1
+	// guessed 15340
+	screen.blocks()
 }
 
-impl MemBank {
-	fn new(prg:Vec<i64>) -> MemBank {
-		MemBank{prg,data: HashMap::new()}
+/// Plays the game to the end on autopilot, showing `frame` the screen each
+/// time the cabinet waits for the joystick. Returns the final score.
+fn play(input: &str, frame: &mut dyn FnMut(&Screen)) -> i64 {
+	let mut p = Prg::new(input);
+	// run with INTCODE_TRACE=<file> to keep a game for `intcode replay`;
+	// after part 1 has counted the blocks, the game lands in <file>.2
+	let mut recorder = recorder_from_env();
+	// put in the quarters
+	match recorder.as_mut() {
+		Some(r) => p.poke_traced(0,2,r).unwrap(),
+		None => p.data.write(0,2),
 	}
-	fn read(&self,pointer:usize) -> i64 {
-		if pointer < self.prg.len() {
-			return self.prg[pointer]
-		}
-
-		let segment = pointer / 1024;
-		if self.data.contains_key(&segment) {
-			let local = pointer % 1024;
-			return self.data.get(&segment).unwrap()[local]
+	let mut io = Queue::default();
+	let mut screen = Screen::new();
+	loop {
+		let stop = match recorder.as_mut() {
+			Some(r) => p.run_traced(&mut io,false,r).unwrap(),
+			None => p.run(&mut io,false),
+		};
+		screen.draw(&mut io.output);
+		if stop == Stop::Halted {
+			return screen.score;
 		}
-		0
+		frame(&screen);
+		io.input.push_back(screen.decision());
 	}
+}
 
-	fn write(&mut self, pointer: usize, data: i64) {
-		if pointer < self.prg.len() {
-			self.prg[pointer] = data;
-		}
-		else {
-			let segment = pointer / 1024;
-			if !self.data.contains_key(&segment) {
-				self.data.insert(segment, vec![0;1024]);
-			}
-				let local = pointer % 1024;
-				self.data.get_mut(&segment).unwrap()[local] = data;
-		}
+/// Where frames go: the terminal when built with the `terminal` feature and
+/// run in one, else the file named by `ARCADE_FRAMES`, else nowhere.
+fn frame_sink() -> Box<dyn FnMut(&Screen)> {
+	#[cfg(feature = "terminal")]
+	if termion::is_tty(&std::io::stdout()) {
+		let mut cabinet = terminal::Cabinet::new();
+		return Box::new(move |screen| cabinet.show(screen));
+	}
+	if let Ok(path) = env::var(FRAMES_VAR) {
+		let file = File::create(&path).unwrap_or_else(|e| panic!("could not create {}: {}", path, e));
+		let mut out = BufWriter::new(file);
+		return Box::new(move |screen| writeln!(out,"{}",screen.render()).unwrap());
 	}
+	Box::new(|_| {})
 }
 
-impl Prg {
-	fn new(s: &str) -> Prg {
-		let data: Vec<i64> = s
-			.split(',')
-			.map(|a| a.parse().unwrap())
-			.collect::<Vec<i64>>();
-		Prg { data: MemBank::new(data), pc: 0, relative_base: 0 }
-	}
+#[cfg(feature = "terminal")]
+mod terminal {
+	use super::Screen;
+	use std::io::{stdout, Stdout, Write};
+	use std::time::Duration;
 
-	fn mode_for(op: i64, ix:u32) -> i64 {
-		(op / 10i64.pow(1+ix)) % 10
+	/// Shows the game as it is played, slow enough to follow at first.
+	pub struct Cabinet {
+		stdout: Stdout,
 	}
 
-	fn get_mempointer(&mut self, op: i64, ix: u32) -> i64 {
-		let mode = Prg::mode_for(op,ix);
-		let param_pointer = self.pc + ix as usize;
+	impl Cabinet {
+		pub fn new() -> Cabinet {
+			Cabinet { stdout: stdout() }
+		}
 
-		match mode {
-			0 => self.data.read(param_pointer),
-			1 => param_pointer as i64,
-			2 => self.data.read(param_pointer) + self.relative_base,
-			_ => panic!("invalid mode"),
+		pub fn show(&mut self, screen: &Screen) {
+			write!(self.stdout,"{}{}{}",termion::clear::All,termion::cursor::Goto(1,1),screen.render()).unwrap();
+			self.stdout.flush().unwrap();
+			let wait = if screen.score == 0 {
+				500
+			} else if screen.score < 100 {
+				300
+			} else if screen.score < 200 {
+				100
+			} else {
+				1
+			};
+			std::thread::sleep(Duration::from_millis(wait));
 		}
 	}
+}
 
-	fn get_param(&mut self, op: i64, ix: u32) -> i64 {
-		let pointer = self.get_mempointer(op,ix);
-		self.data.read(pointer as usize)
-	}
+pub struct Day {
+	program: String,
+}
 
-	fn mul(&mut self, op: i64) -> usize {
-		let a = self.get_param( op, 1);
-		let b = self.get_param( op, 2);
-		let ix_c = self.get_mempointer(op,3) as usize;
-		self.data.write(ix_c, a*b);
-		4
-	}
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = i64;
 
-	fn add(&mut self, op: i64 ) -> usize {
-		let a = self.get_param( op, 1);
-		let b = self.get_param( op, 2);
-		let ix_c = self.get_mempointer(op,3) as usize;
-		self.data.write(ix_c, a+b);
-		4
+	fn parse(input: &str) -> Self {
+		Day { program: input.to_string() }
 	}
 
-	fn jnz(&mut self, op: i64) -> usize {
-		let a = self.get_param( op, 1);
-		if a != 0 {
-			self.get_param(op, 2) as usize
-		}
-		else {
-			self.pc + 3
-		}
+	fn part1(&self) -> usize {
+		count_blocks(&self.program)
 	}
 
-	fn jz(&mut self, op: i64 ) -> usize {
-		let a = self.get_param( op, 1);
-		if a == 0 {
-			self.get_param(op, 2) as usize
-		}
-		else {
-			self.pc + 3
-		}
+	fn part2(&self) -> i64 {
+		play(&self.program,&mut *frame_sink())
 	}
+}
 
-	fn lt(&mut self, op: i64 ) -> usize {
-		let a = self.get_param( op, 1);
-		let b = self.get_param( op, 2);
-		let ix_c = self.get_mempointer(op,3) as usize;
-		self.data.write(ix_c, if a < b { 1 } else {0});
-		4
-	}
 
-	fn eq(&mut self, op: i64 ) -> usize {
-		let a = self.get_param( op, 1);
-		let b = self.get_param( op, 2);
-		let ix_c = self.get_mempointer(op,3) as usize;
-		self.data.write(ix_c, if a == b { 1 } else {0});
-		4
-	}
+#[cfg(test)]
+mod tests {
+	use super::*;
 
-	fn off(&mut self, op:i64) -> usize {
-		let pointer = self.get_mempointer(op,1) as usize;
-		self.relative_base += self.data.read(pointer);
-		2
+	#[test]
+	fn test_screen_tracks_ball_and_paddle() {
+		let mut s = Screen::new();
+		s.draw(&mut VecDeque::from(vec![0,0,1, 1,0,2, 2,0,2, 5,5,3, 4,3,4, -1,0,12]));
+		assert_eq!(2, s.blocks());
+		assert_eq!(5, s.paddle_x);
+		assert_eq!((4,3), s.ball);
+		assert_eq!(12, s.score);
+		s.update(3,4,4);
+		assert_eq!((false,true), s.ball_dir);
+		assert_eq!(-1, s.decision());
+		assert_eq!("score: 12\n#..   \n      \n      \n    o \n   o  \n     \"\n", s.render());
 	}
-	fn write(&mut self, op:i64, tx: &Sender<i64> ) -> usize {
-		let x = self.get_param(op,1);
-		if let Err(e) = tx.send(x) {
-			panic!("bad write");
-		}
-		return 2;
-	}
-	fn read(&mut self, op:i64, rx: &Receiver<i64> ) -> usize {
-		let pointer = self.get_mempointer(op,1) as usize;
-		if let Ok(x) = rx.recv() {
-			self.data.write(pointer,x);
-			return 2;
-		}
-		else {
-			panic!("bad read");
-		}
+
+	#[test]
+	fn test_expected_x_bounces_off_walls() {
+		let mut s = Screen::new();
+		s.ball = (1,16);
+		s.ball_dir = (false,true);
+		assert_eq!(Some(2), s.expected_x());
+		s.paddle_x = 2;
+		assert_eq!(0, s.decision());
+		s.ball_dir = (false,false);
+		assert_eq!(None, s.expected_x());
 	}
-	fn run(&mut self, input_rx:Receiver<i64>, output_tx:Sender<i64>, halt_on_write:bool ) -> bool {
-		loop {
-			let opcode = self.data.read(self.pc);
-			if opcode == 99 {
-				return true;
-			}
 
-			match opcode % 100 {
-				1 => self.pc += self.add(opcode),
-				2 => self.pc += self.mul(opcode),
-				3 => self.pc += self.read(opcode,&input_rx),
-				4 => {
-					self.pc += self.write(opcode, &output_tx);
-					if halt_on_write {
-						return true;
-					}
-				},
-				5 => self.pc = self.jnz(opcode),
-				6 => self.pc = self.jz(opcode),
-				7 => self.pc += self.lt(opcode),
-				8 => self.pc += self.eq(opcode),
-				9 => self.pc += self.off(opcode),
-				_ => panic!("bad opcode at {}", self.pc),
-			}
-		}
+	#[test]
+	fn test_play_headless() {
+		// draws a ball and a paddle, reads the joystick once and reports it as the score;
+		// the first instruction is there to take the quarters poked into address 0
+		let program = "1,0,0,200,104,1,104,1,104,4,104,3,104,2,104,3,3,100,104,-1,104,0,4,100,99";
+		let mut frames = 0;
+		assert_eq!(-1, play(program, &mut |_| frames += 1));
+		assert_eq!(1, frames);
 	}
 }
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::{collections::HashMap};
 use std::collections::VecDeque;
 
@@ -130,21 +131,14 @@ fn get_cost(input:&str, name: &str, q: u64) -> (u64,HashMap<String,u64>) {
 		}
 	}
 }
-fn main() {
-	let t =&std::fs::read_to_string("2019/14.txt").unwrap();
-
-	let ore = get_cost_count(t,"FUEL",1);
-	println!("ore: {}", ore);
-
+fn max_fuel(t: &str) -> u64 {
 	let mut fuelcount = 1u64;
 	let limit = 1_000_000_000_000u64;
 
-
-	println!("find limit");
+	// find limit
 	loop {
 		fuelcount *= 2;
 		let result = get_cost_count(t,"FUEL", fuelcount);
-		println!("{} fuel -> {} ore", fuelcount, result);
 		if result > limit {
 			break;
 		}
@@ -153,26 +147,42 @@ fn main() {
 	let mut max = fuelcount;
 	let mut min = fuelcount / 2;
 
-	println!("bisect search");
+	// bisect search
 	loop {
 		let pivot = (max-min)/2 + min;
 		if pivot == min {
-			println!("{} fuel",pivot);
-			break;
+			return pivot;
 		} else if pivot == max {
-			println!("{} fuel",pivot-1);
-			break;
+			return pivot-1;
 		}
 		let result = get_cost_count(t,"FUEL", pivot);
-		println!("{}-{} {} fuel -> {} ore", min,max,pivot, result);
 		if result > limit {
 			max = pivot;
 		} else if result < limit {
 			min = pivot;
 		} else { panic!("aaaaaah"); }
 	}
-	//
+}
+
+pub struct Day {
+	reactions: String,
+}
+
+impl Solution for Day {
+	type Part1 = u64;
+	type Part2 = u64;
+
+	fn parse(input: &str) -> Self {
+		Day { reactions: input.to_string() }
+	}
 
+	fn part1(&self) -> u64 {
+		get_cost_count(&self.reactions,"FUEL",1)
+	}
+
+	fn part2(&self) -> u64 {
+		max_fuel(&self.reactions)
+	}
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 use std::collections::HashMap;
 
 #[derive(Debug,Clone,PartialEq,Eq)]
@@ -18,6 +20,10 @@ impl Node {
 		wires == self.wires
 	}
 }
+/// Only the points the wires pass through, keyed by position. The wires
+/// cover a box over eleven thousand steps on each side, so a
+/// dense `crate::grid::Grid` would hold over a hundred million cells for a
+/// few hundred thousand that are ever touched.
 struct Grid {
 	nodes: HashMap<(isize,isize),Node>,
 	pointer: Vec<isize>,
@@ -172,10 +178,25 @@ mod tests {
 	}
 }
 
-fn main() {
-	let mut g = Grid::new();
-	g.parse_file(&std::fs::read_to_string("2019/3.txt").unwrap());
-	println!("closest: {}",g.closest());
+pub struct Day {
+	grid: Grid,
+}
+
+impl Solution for Day {
+	type Part1 = isize;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Self {
+		let mut grid = Grid::new();
+		grid.parse_file(input);
+		Day { grid }
+	}
 
-	println!("cheapest: {}",g.cheapest());
-}
\ No newline at end of file
+	fn part1(&self) -> isize {
+		self.grid.closest()
+	}
+
+	fn part2(&self) -> u32 {
+		self.grid.cheapest()
+	}
+}
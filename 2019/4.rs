@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 
 fn getnum(p:u32,i:u32) -> u32 {
 	(p / 10u32.pow(i)) % 10
@@ -40,22 +42,40 @@ fn has_singlepair(p:u32) -> bool {
 	runlength == 2
 }
 
-fn main() {
+fn candidates(from: u32, to: u32) -> Vec<u32> {
 	let mut c = Vec::new();
-	for p in 359282..=820401 {
+	for p in from..=to {
 		if ismatch(p) {
 			c.push(p);
 		}
 	}
-	println!("count {}",c.len());
+	c
+}
 
-	let mut actually = Vec::new();
-	for p in c {
-		if has_singlepair(p) {
-			actually.push(p);
-		}
+pub struct Day {
+	from: u32,
+	to: u32,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		let (from, to) = input.trim().split_once('-').unwrap();
+		Day { from: from.parse().unwrap(), to: to.parse().unwrap() }
+	}
+
+	fn part1(&self) -> usize {
+		candidates(self.from, self.to).len()
+	}
+
+	fn part2(&self) -> usize {
+		candidates(self.from, self.to)
+			.into_iter()
+			.filter(|&p| has_singlepair(p))
+			.count()
 	}
-	println!("{:?}\ncount {}",actually,actually.len());
 }
 
 #[cfg(test)]
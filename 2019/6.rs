@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 type Map = HashMap<String,Stellar>;
@@ -151,11 +152,25 @@ I)SAN");
 
 
 
-fn main(){
-	let m = map(&std::fs::read_to_string("2019/6.txt").unwrap());
+pub struct Day {
+	map: Map,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Self {
+		Day { map: map(input) }
+	}
 
-	println!("part 1: {}",count(&m,0,&ancestor(&m)));
-	println!("part 2: {}",transfer(&m,"YOU","SAN"));
+	fn part1(&self) -> usize {
+		count(&self.map,0,&ancestor(&self.map))
+	}
+
+	fn part2(&self) -> usize {
+		transfer(&self.map,"YOU","SAN")
+	}
 }
 
 fn transfer(map:&Map,from:&str,to:&str) -> usize {
@@ -1,4 +1,6 @@
-use std::collections::VecDeque;
+use crate::intcode::network::{LastOutput, Network, Routing};
+use crate::intcode::Prg;
+use crate::solution::Solution;
 use permutohedron::heap_recursive;
 
 #[cfg(test)]
@@ -23,14 +25,10 @@ mod tests {
 	}
 }
 
-fn run(s:&str,depth:usize,history:&[usize],input:i32,mut max:i32) -> i32 {
+fn run(s:&str,depth:usize,history:&[usize],input:i64,mut max:i64) -> i64 {
 	for a in 0..=4 {
 		if !history.contains(&a) {
-			let mut program = Prg::new(&s);
-			program.input.push_back(a as i32);
-			program.input.push_back(input);
-			let _ = program.run(false);
-			let result = program.output.pop_front().unwrap();
+			let result = Prg::new(s).run_with(&[a as i64,input])[0];
 			if depth == 4 {
 				if result > max {
 					max = result;
@@ -46,16 +44,28 @@ fn run(s:&str,depth:usize,history:&[usize],input:i32,mut max:i32) -> i32 {
 	max
 }
 
-fn main() {
-	let p = std::fs::read_to_string("2019/7.txt").unwrap();
-	let result = run(&p,0,&[],0,0);
-	println!("part 1: {}",result);
+pub struct Day {
+	program: String,
+}
+
+impl Solution for Day {
+	type Part1 = i64;
+	type Part2 = i64;
+
+	fn parse(input: &str) -> Self {
+		Day { program: input.to_string() }
+	}
+
+	fn part1(&self) -> i64 {
+		run(&self.program,0,&[],0,0)
+	}
 
-	let result = find_loopback(&p);
-	println!("part 2: {}",result);
+	fn part2(&self) -> i64 {
+		find_loopback(&self.program)
+	}
 }
 
-fn find_loopback(s:&str) -> i32 {
+fn find_loopback(s:&str) -> i64 {
 	let mut data = [5,6,7,8,9];
 	let mut max = 0;
 	heap_recursive(&mut data, |permutation| {
@@ -67,152 +77,14 @@ fn find_loopback(s:&str) -> i32 {
 	max
 }
 
-fn do_loopback(setting:&[i32],s:&str) -> i32 {
-	let mut prgs :Vec<Prg> = setting.iter().map(|i| {
-		let mut p = Prg::new(&s);
-		p.input.push_back(*i);
-		p
-	}).collect::<Vec<Prg>>();
-
-	let mut result = 0;
-	loop {
-		for p in prgs.iter_mut() {
-			p.input.push_back(result);
-			if p.run(true) {
-				return result
-			}
-			if let Some(x) = p.output.pop_front() {
-				result = x;
-			}
-			else {
-				panic!("didnt get output");
-			}
-		}
-	}
-}
-
-struct Prg {
-	data: Vec<i32>,
-	input: VecDeque<i32>,
-	output: VecDeque<i32>,
-	pc: usize,
-}
-
-impl Prg {
-	fn new(s: &str) -> Prg {
-		let data: Vec<i32> = s
-			.split(',')
-			.map(|a| a.parse().unwrap())
-			.collect::<Vec<i32>>();
-		Prg { data, input: VecDeque::new(), output: VecDeque::new(), pc: 0 }
-	}
-
-	fn mode_for(op: i32, ix:u32) -> i32 {
-		(op / 10i32.pow(1+ix)) % 10
-	}
-
-	fn get_param_from_mode(&mut self, val: i32, mode: i32 ) -> i32 {
-		match mode {
-			0 => self.data[val as usize],
-			1 => val,
-			_ => panic!("invalid mode"),
-		}
-	}
-
-	fn get_param(&mut self, op: i32, ix: u32) -> i32 {
-		let mode = Prg::mode_for(op,ix);
-		let val = self.data[self.pc + ix as usize];
-		self.get_param_from_mode(val,mode)
-	}
-
-	fn mul(&mut self, op: i32) -> usize {
-		let a = self.get_param( op, 1);
-		let b = self.get_param( op, 2);
-		let ix_c = self.data[self.pc + 3] as usize;
-		self.data[ix_c] = a*b;
-		4
-	}
-
-	fn add(&mut self, op: i32 ) -> usize {
-		let a = self.get_param( op, 1);
-		let b = self.get_param( op, 2);
-		let ix_c = self.data[self.pc + 3] as usize;
-		self.data[ix_c] = a+b;
-		4
-	}
-
-	fn jnz(&mut self, op: i32) -> usize {
-		let a = self.get_param( op, 1);
-		if a != 0 {
-			self.get_param(op, 2) as usize
-		}
-		else {
-			self.pc + 3
-		}
-	}
-
-	fn jz(&mut self, op: i32 ) -> usize {
-		let a = self.get_param( op, 1);
-		if a == 0 {
-			self.get_param(op, 2) as usize
-		}
-		else {
-			self.pc + 3
-		}
-	}
-
-	fn lt(&mut self, op: i32 ) -> usize {
-		let a = self.get_param( op, 1);
-		let b = self.get_param( op, 2);
-		let ix_c = self.data[self.pc + 3] as usize;
-		self.data[ix_c] = if a < b { 1 } else {0};
-		4
-	}
-
-	fn eq(&mut self, op: i32 ) -> usize {
-		let a = self.get_param( op, 1);
-		let b = self.get_param( op, 2);
-		let ix_c = self.data[self.pc + 3] as usize;
-		self.data[ix_c] = if a == b { 1 } else {0};
-		4
-	}
-
-	fn run(&mut self, halt_on_write:bool ) -> bool {
-		while self.pc < self.data.len() {
-			let opcode = self.data[self.pc];
-			if opcode == 99 {
-				return true
-			}
-
-			match opcode % 100 {
-				1 => self.pc += self.add(opcode),
-				2 => self.pc += self.mul(opcode),
-				3 => {
-					let ix_a = self.data[self.pc + 1] as usize;
-					if let Some(x) = self.input.pop_front() {
-						self.data[ix_a] = x;
-					}
-					else {
-						panic!("input buffer empty");
-					}
-					self.pc += 2;
-				},
-				4 => {
-					let x = self.get_param(opcode,1);
-					self.output.push_back(x);
-					self.pc += 2;
-					if halt_on_write {
-						return false
-					}
-				},
-				5 => self.pc = self.jnz(opcode),
-				6 => self.pc = self.jz(opcode),
-				7 => self.pc += self.lt(opcode),
-				8 => self.pc += self.eq(opcode),
-				_ => panic!("bad opcode at {}, {:?}", self.pc, self.data),
-			}
-		}
-
-		panic!("halted at {}, {:?}", self.pc, self.data);
+// the amplifiers are a ring of machines, each primed with its phase setting
+fn do_loopback(setting:&[i64],s:&str) -> i64 {
+	let mut net = Network::new(vec![Prg::new(s);setting.len()],Routing::Ring);
+	for (id,phase) in setting.iter().enumerate() {
+		net.send(id,&[*phase]);
 	}
+	net.send(0,&[0]);
+	let mut thrusters = LastOutput::new(setting.len()-1);
+	net.run(&mut thrusters);
+	thrusters.value.unwrap()
 }
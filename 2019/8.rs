@@ -1,3 +1,5 @@
+use crate::solution::Solution;
+
 
 struct Layer {
 	data: Vec<u8>,
@@ -110,15 +112,24 @@ mod tests {
 	}
 }
 
-fn main(){
-	let i = Image::new(&std::fs::read_to_string("2019/8.txt").unwrap(), 25, 6);
-	let l = i.get_chklayer();
+pub struct Day {
+	image: Image,
+}
+
+impl Solution for Day {
+	type Part1 = usize;
+	type Part2 = String;
 
-	let ones = l.count('1' as u8);
-	let twos = l.count('2' as u8);
+	fn parse(input: &str) -> Self {
+		Day { image: Image::new(input.trim(), 25, 6) }
+	}
 
-	println!("part 1: {}",ones*twos);
-	println!("part 2:");
+	fn part1(&self) -> usize {
+		let l = self.image.get_chklayer();
+		l.count(b'1') * l.count(b'2')
+	}
 
-	println!("{}", tostr(&i.get_image(),25));
+	fn part2(&self) -> String {
+		tostr(&self.image.get_image(),25)
+	}
 }
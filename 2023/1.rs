@@ -1,10 +1,35 @@
+use crate::solution::Solution;
 use regex::{Match, Regex};
 
-fn main() {
-    let data = std::fs::read_to_string("2023/1.txt").unwrap();
+pub struct Day {
+    document: String,
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            document: input.to_owned(),
+        }
+    }
+
+    fn part1(&self) -> u32 {
+        self.document.lines().map(get_digits).sum()
+    }
 
-    println!("sum: {}", get_summed_lines(data));
-    // 53896 is too high
+    fn part2(&self) -> u32 {
+        get_summed_lines(self.document.clone())
+        // 53896 is too high
+    }
+}
+
+fn get_digits(line: &str) -> u32 {
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+    let first = digits.next().unwrap();
+    let last = digits.next_back().unwrap_or(first);
+    first * 10 + last
 }
 
 fn map_nr(s: &str) -> u32 {
@@ -32,7 +57,7 @@ fn map_nr(s: &str) -> u32 {
     }
 }
 
-fn get_last(line: &String, r: Regex, mut ix: usize) -> Option<Match> {
+fn get_last(line: &str, r: Regex, mut ix: usize) -> Option<Match<'_>> {
     let mut lastmatch = None;
     loop {
         match r.find_at(line, ix) {
@@ -56,7 +81,6 @@ fn get_calibration_values(line: &String) -> (u32, u32) {
 }
 fn get_combined(line: &String) -> u32 {
     let res = get_calibration_values(line);
-    println!("{1}-{2} :: {0}", line, res.0, res.1);
     return res.0 * 10 + res.1;
 }
 fn get_summed(lines: Vec<String>) -> u32 {
@@ -75,6 +99,14 @@ mod tests {
         assert_eq!((1, 8), get_calibration_values(&"oneight".to_owned()))
     }
 
+    #[test]
+    fn test_digits_only() {
+        assert_eq!(12, get_digits("1abc2"));
+        assert_eq!(38, get_digits("pqr3stu8vwx"));
+        assert_eq!(15, get_digits("a1b2c3d4e5f"));
+        assert_eq!(77, get_digits("treb7uchet"));
+    }
+
     #[test]
     fn test_value_extraction() {
         assert_eq!((1, 2), get_calibration_values(&String::from("1abc2")));
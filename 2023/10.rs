@@ -1,20 +1,15 @@
-use std::{fmt, fs::File, io::Write};
+use crate::grid::Grid;
+use crate::solution::Solution;
+use std::fmt;
 
 impl fmt::Display for Loop {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let strings: Vec<String> = self
-            .map
-            .iter()
-            .map(|v| v.iter().collect::<String>())
-            .collect();
-        let s = strings.join("\n");
-
-        write!(f, "{}", s)
+        write!(f, "{}", self.map)
     }
 }
 
 struct Loop {
-    map: Vec<Vec<char>>,
+    map: Grid<char>,
 }
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 struct Coord {
@@ -33,43 +28,31 @@ impl Coord {
 }
 impl Loop {
     fn new_sized(line_count: usize, row_size: usize) -> Loop {
-        let map = vec![vec![' '; row_size]; line_count];
+        let map = Grid::new(row_size, line_count, ' ');
         Loop { map }
     }
     fn new(s: &str) -> Loop {
-        let map = s.lines().map(|l| l.chars().collect()).collect();
+        let map = Grid::parse(s);
         Loop { map }
     }
     fn at(&self, line: usize, ix: usize) -> char {
-        *self.map.get(line).unwrap().get(ix).unwrap()
+        self.map[(ix, line)]
     }
     fn find(&self, c: char) -> Coord {
-        let height = self.map.len();
-        let width = self.map.get(0).unwrap().len();
-        for l in 0..height {
-            for w in 0..width {
-                if self.at(l, w) == c {
-                    return Coord {
-                        line: l as isize,
-                        ix: w as isize,
-                    };
-                }
-            }
-        }
-        panic!("invalid character");
+        let (ix, line) = self.map.find(|&x| x == c).expect("invalid character");
+        Coord::new(line as isize, ix as isize)
     }
     fn set(&mut self, line: isize, ix: isize, c: char) {
-        if let Some(l) = self.map.get_mut(line as usize) {
-            if let Some(i) = l.get_mut(ix as usize) {
-                if *i != 'x' {
-                    *i = c;
-                }
+        if let Some(pos) = self.map.inside(ix, line) {
+            let i = &mut self.map[pos];
+            if *i != 'x' {
+                *i = c;
             }
         }
     }
     fn mask(&self, marks: &Loop) -> Loop {
-        let height = self.map.len();
-        let width = self.map[0].len();
+        let height = self.map.height();
+        let width = self.map.width();
         let mut l = Loop::new_sized(height, width);
 
         for line in 0..height {
@@ -84,11 +67,10 @@ impl Loop {
     }
 
     fn filter_pos(&mut self, line: isize, ix: isize, wipe: &str) {
-        if let Some(c) = self.map.get_mut(line as usize) {
-            if let Some(s) = c.get_mut(ix as usize) {
-                if wipe.contains(s.to_owned()) {
-                    *s = ' ';
-                }
+        if let Some(pos) = self.map.inside(ix, line) {
+            let s = &mut self.map[pos];
+            if wipe.contains(*s) {
+                *s = ' ';
             }
         }
     }
@@ -106,8 +88,8 @@ impl Loop {
     }
 
     fn clean_edges(&mut self) {
-        let height = self.map.len() as isize;
-        let width = self.map.get(0).unwrap().len() as isize;
+        let height = self.map.height() as isize;
+        let width = self.map.width() as isize;
         for i in 0..width {
             self.mark_location_as_below_open_tile(0, i);
             self.mark_location_as_above_open_tile(height - 1, i);
@@ -119,11 +101,11 @@ impl Loop {
     }
 
     fn clean_pass(&mut self) {
-        let height = self.map.len() as isize;
-        let width = self.map.get(0).unwrap().len() as isize;
+        let height = self.map.height() as isize;
+        let width = self.map.width() as isize;
         for l in 0..height {
             for w in 0..width {
-                let c = self.map.get(l as usize).unwrap().get(w as usize).unwrap();
+                let c = self.at(l as usize, w as usize);
                 match c {
                     '|' => {
                         self.mark_location_as_left_of_open_tile(l, w - 1);
@@ -326,57 +308,68 @@ fn experiment_2() {
     let paint = walk_mark_loop(&mut l, Coord { line: 1, ix: 1 }, Dir::N);
     println!("{}", paint)
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/10.txt").unwrap();
-    let mut strs = Vec::new();
-    let mut l = Loop::new(&data);
-    let marks = walk_mark_loop(&l, Coord { line: 15, ix: 22 }, Dir::N);
 
-    strs.push(l.to_string());
-    l.clean_edges();
-    strs.push(l.to_string());
-    let mut counter = 0;
-    loop {
-        counter += 1;
-        l.clean_pass();
-        let tmp = l.to_string();
-        if *strs.last().unwrap() == tmp {
-            break;
+pub struct Day {
+    maze: String,
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            maze: input.to_string(),
         }
-        strs.push(tmp);
     }
 
-    println!("clean iterations {}", counter);
-    let start = l.find('S');
-    println!("start at {}", start);
-    let steps = iter_loop(&mut l, start);
-    println!("steps: {}", steps);
+    fn part1(&self) -> u32 {
+        let mut l = Loop::new(&self.maze);
+        let start = l.find('S');
+        iter_loop(&mut l, start)
+    }
 
-    let mut buffer = File::create("./clean.txt").unwrap();
-    buffer.write_all(l.to_string().as_bytes()).unwrap();
+    fn part2(&self) -> usize {
+        let mut strs = Vec::new();
+        let mut l = Loop::new(&self.maze);
+        let marks = walk_mark_loop(&l, Coord { line: 15, ix: 22 }, Dir::N);
 
-    let mut tile_count = 0;
-    let masked = l.mask(&marks);
-    for line in &masked.map {
-        let inside: Vec<&char> = line.iter().filter(|&x| *x == 'I').collect();
-        tile_count += inside.len();
-    }
-    // also sum the space in the middle
-    for l in 60..85 {
-        for x in 50..100 {
-            if &masked.at(l, x) == &' ' {
-                tile_count += 1;
+        strs.push(l.to_string());
+        l.clean_edges();
+        strs.push(l.to_string());
+        loop {
+            l.clean_pass();
+            let tmp = l.to_string();
+            if *strs.last().unwrap() == tmp {
+                break;
             }
+            strs.push(tmp);
         }
-    }
-    println!("inner area: {}", tile_count);
 
-    // 887 is too high!
-    // 260 is too low
+        let start = l.find('S');
+        iter_loop(&mut l, start);
+
+        let mut tile_count = 0;
+        let masked = l.mask(&marks);
+        tile_count += masked.map.iter().filter(|&x| *x == 'I').count();
+        // also sum the space in the middle
+        for l in 60..85 {
+            for x in 50..100 {
+                if masked.at(l, x) == ' ' {
+                    tile_count += 1;
+                }
+            }
+        }
+        tile_count
+
+        // 887 is too high!
+        // 260 is too low
+    }
 }
+
 fn walk_mark_loop(l: &Loop, start: Coord, facing: Dir) -> Loop {
     let mut walker = Walker::new(start, facing);
-    let mut marks = Loop::new_sized(l.map.len(), l.map[0].len());
+    let mut marks = Loop::new_sized(l.map.height(), l.map.width());
     walker.step_and_mark(l, &mut marks);
     loop {
         if walker.coord == start {
@@ -392,7 +385,7 @@ fn iter_loop(l: &mut Loop, start: Coord) -> u32 {
         Walker::new(Coord::new(start.line, start.ix - 1), Dir::W),
         Walker::new(Coord::new(start.line, start.ix + 1), Dir::E),
     ];
-    let mut marks = Loop::new_sized(l.map.len(), l.map[0].len());
+    let mut marks = Loop::new_sized(l.map.height(), l.map.width());
     marks.set(start.line, start.ix, 'x');
     loop {
         if walkers[0].coord == walkers[1].coord {
@@ -404,8 +397,8 @@ fn iter_loop(l: &mut Loop, start: Coord) -> u32 {
         marks.set(walkers[1].coord.line, walkers[1].coord.ix, 'x');
     }
 
-    let height = l.map.len();
-    let width = l.map.get(0).unwrap().len();
+    let height = l.map.height();
+    let width = l.map.width();
 
     for line in 0..height {
         for ix in 0..width {
@@ -430,7 +423,7 @@ LJ.LJ";
     #[test]
     fn test_can_make_loop() {
         let d = Loop::new(TEST_DATA);
-        assert_eq!('7', *d.map.get(0).unwrap().get(0).unwrap());
+        assert_eq!('7', d.at(0, 0));
     }
     #[test]
     fn test_can_clean_edges() {
@@ -0,0 +1,108 @@
+use crate::solution::Solution;
+
+pub struct Day {
+    galaxies: Vec<(usize, usize)>,
+    width: usize,
+    height: usize,
+}
+
+impl Day {
+    /// The sum of the distances along one axis between every pair of
+    /// galaxies, where every empty line counts `factor` times over.
+    fn spread(positions: &mut [usize], size: usize, factor: usize) -> usize {
+        positions.sort();
+        let mut occupied = vec![false; size];
+        for p in positions.iter() {
+            occupied[*p] = true;
+        }
+        // where each line ends up once the empty ones have grown
+        let mut grown = Vec::with_capacity(size);
+        let mut at = 0;
+        for o in occupied {
+            grown.push(at);
+            at += if o { 1 } else { factor };
+        }
+        // sorted, the k-th galaxy is that far past each of the k before it
+        // and short of each of the rest
+        let n = positions.len() as i64;
+        let total: i64 = positions
+            .iter()
+            .enumerate()
+            .map(|(k, p)| grown[*p] as i64 * (2 * k as i64 - (n - 1)))
+            .sum();
+        total as usize
+    }
+
+    fn distances(&self, factor: usize) -> usize {
+        let mut xs: Vec<usize> = self.galaxies.iter().map(|g| g.0).collect();
+        let mut ys: Vec<usize> = self.galaxies.iter().map(|g| g.1).collect();
+        Day::spread(&mut xs, self.width, factor) + Day::spread(&mut ys, self.height, factor)
+    }
+}
+
+impl Solution for Day {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Self {
+        let lines: Vec<&str> = input
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let galaxies = lines
+            .iter()
+            .enumerate()
+            .flat_map(|(y, l)| {
+                l.char_indices()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+        Day {
+            galaxies,
+            width: lines.iter().map(|l| l.len()).max().unwrap_or(0),
+            height: lines.len(),
+        }
+    }
+
+    fn part1(&self) -> usize {
+        self.distances(2)
+    }
+
+    fn part2(&self) -> usize {
+        self.distances(1_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn test_parse() {
+        let day = Day::parse(EXAMPLE);
+        assert_eq!(9, day.galaxies.len());
+        assert_eq!((3, 0), day.galaxies[0]);
+        assert_eq!((10, 10), (day.width, day.height));
+    }
+
+    #[test]
+    fn test_example() {
+        let day = Day::parse(EXAMPLE);
+        assert_eq!(374, day.distances(2));
+        assert_eq!(1030, day.distances(10));
+        assert_eq!(8410, day.distances(100));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 use regex::Regex;
@@ -391,26 +392,39 @@ impl Record {
     //     false
     // }
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/12.txt").unwrap();
-    let mut sum = 0;
-    for line in data.lines() {
-        sum += Map::new(line).count_matches();
-    }
-    println!("summed: {}", sum);
-    let data = std::fs::read_to_string("2023/12.txt").unwrap();
-    let mut sum = 0;
-    let mut id = 0;
-    for line in data.lines() {
-        let mut map = Map::new_expanded(line);
-        map.id = id;
-        println!("{} {} ({})", id, line, map.count_unknowns());
-        sum += map.count_matches();
-        id += 1;
-    }
-    println!("expanded: {}", sum);
-    // 351 is too low
-    // 2596401949 is too low
+
+pub struct Day {
+    records: String,
+}
+
+impl Solution for Day {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            records: input.to_string(),
+        }
+    }
+
+    fn part1(&self) -> u64 {
+        self.records
+            .lines()
+            .map(|line| Map::new(line).count_matches())
+            .sum()
+    }
+
+    fn part2(&self) -> u64 {
+        let mut sum = 0;
+        for (id, line) in self.records.lines().enumerate() {
+            let mut map = Map::new_expanded(line);
+            map.id = id;
+            sum += map.count_matches();
+        }
+        sum
+        // 351 is too low
+        // 2596401949 is too low
+    }
 }
 
 #[cfg(test)]
@@ -1,23 +1,20 @@
+use crate::grid::Grid;
+use crate::solution::Solution;
+
 struct Pattern {
-    data: Vec<Vec<u8>>,
+    data: Grid<char>,
     width: usize,
     height: usize,
 }
 
 impl std::fmt::Display for Pattern {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        for row in &self.data {
-            let str = String::from_utf8(row.clone()).unwrap();
-            if let Err(e) = writeln!(f, "{}", &str) {
-                return Err(e);
-            }
-        }
-        Ok(())
+        writeln!(f, "{}", self.data)
     }
 }
 impl Pattern {
     fn is_vertical_candidate(&self, x: isize, row: usize) -> bool {
-        let s = self.data.get(row).unwrap();
+        let s = self.data.row(row);
         let s_size = s.len() as isize;
         for ix in 0isize.. {
             let left_ix: isize = x - ix;
@@ -35,11 +32,7 @@ impl Pattern {
         false
     }
     fn is_horizontal_candidate(&self, x: usize, row: isize) -> bool {
-        let s: Vec<u8> = self
-            .data
-            .iter()
-            .map(|st| *st.get(x as usize).unwrap())
-            .collect();
+        let s: Vec<char> = self.data.column(x).copied().collect();
         let s_size = s.len() as isize;
 
         for ix in 0isize.. {
@@ -160,12 +153,8 @@ impl Pattern {
     }
 
     fn flip(&mut self, row: usize, col: usize) {
-        let c = self.data.get_mut(row).unwrap().get_mut(col).unwrap();
-        if *c == 35 {
-            *c = 46;
-        } else {
-            *c = 35
-        }
+        let c = &mut self.data[(col, row)];
+        *c = if *c == '#' { '.' } else { '#' };
     }
 
     fn new_vec(data: &str) -> Vec<Pattern> {
@@ -185,9 +174,9 @@ impl Pattern {
         r
     }
     fn new(strdata: Vec<String>) -> Pattern {
-        let width = strdata.get(0).unwrap().len();
-        let height = strdata.len();
-        let data = strdata.iter().map(|d| d.as_bytes().to_owned()).collect();
+        let data = Grid::parse(&strdata.join("\n"));
+        let width = data.width();
+        let height = data.height();
 
         Pattern {
             data,
@@ -196,19 +185,35 @@ impl Pattern {
         }
     }
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/13.txt").unwrap();
-    let mut p = Pattern::new_vec(&data);
 
-    println!(
-        "summarized: {}",
-        p.iter().map(|f| f.summarise()).sum::<usize>()
-    );
-    println!(
-        "flipped: {}",
-        p.iter_mut().map(|f| f.find_flipped()).sum::<usize>()
-    );
-    // 32587 is too high
+pub struct Day {
+    notes: String,
+}
+
+impl Solution for Day {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            notes: input.to_string(),
+        }
+    }
+
+    fn part1(&self) -> usize {
+        Pattern::new_vec(&self.notes)
+            .iter()
+            .map(|f| f.summarise())
+            .sum()
+    }
+
+    fn part2(&self) -> usize {
+        Pattern::new_vec(&self.notes)
+            .iter_mut()
+            .map(|f| f.find_flipped())
+            .sum()
+        // 32587 is too high
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,6 @@
-use md5;
+use crate::grid::Grid;
+use crate::solution::Solution;
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 enum Rock {
     Round,
@@ -16,54 +18,33 @@ impl Rock {
     }
 }
 
+impl std::fmt::Display for Rock {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let c = match self {
+            Rock::Square => '#',
+            Rock::Round => 'O',
+            _ => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+
 struct Platform {
-    data: Vec<Vec<Rock>>,
-    width: usize,
-    height: usize,
+    data: Grid<Rock>,
 }
 impl Platform {
     fn new(strdata: &str) -> Platform {
-        let mut data: Vec<Vec<Rock>> = Vec::new();
-        for line in strdata.lines() {
-            data.push(line.chars().map(Rock::from_char).collect());
-        }
-        let height = data.len();
-        let width = data.get(0).unwrap().len();
         Platform {
-            data,
-            height,
-            width,
+            data: Grid::parse_with(strdata, Rock::from_char),
         }
     }
     fn rotate_cw(&mut self) {
-        let mut data: Vec<Vec<Rock>> = (0..(self.width)).map(|_| Vec::new()).collect();
-
-        self.data.reverse();
-        for row in &self.data {
-            for (ix, rock) in row.iter().enumerate() {
-                data[ix].push(*rock);
-            }
-        }
-        let tmp = self.width;
-        self.width = self.height;
-        self.height = tmp;
-        self.data = data;
+        self.data = self.data.rotated_cw();
     }
     fn rotate_ccw(&mut self) {
-        let mut data: Vec<Vec<Rock>> = (0..(self.width)).map(|_| Vec::new()).collect();
-
-        for row in self.data.iter_mut() {
-            row.reverse();
-            for (ix, rock) in row.iter().enumerate() {
-                data[ix].push(*rock);
-            }
-        }
-        let tmp = self.width;
-        self.width = self.height;
-        self.height = tmp;
-        self.data = data;
+        self.data = self.data.rotated_ccw();
     }
-    fn sort_segmented_row(data: &mut Vec<Rock>, from: usize, to: usize, rock_count: usize) {
+    fn sort_segmented_row(data: &mut [Rock], from: usize, to: usize, rock_count: usize) {
         // to is an index, not included in sort
         let delta = to - from;
         if delta <= 1 {
@@ -82,13 +63,13 @@ impl Platform {
         }
     }
     fn tilt_row_right(&mut self, row: usize) {
-        let mut v = self.data.get_mut(row).unwrap();
+        let v = self.data.row_mut(row);
         let mut left_edge = 0;
         let mut rock_count = 0;
-        for ix in 0..self.width {
+        for ix in 0..v.len() {
             match v[ix] {
                 Rock::Square => {
-                    Platform::sort_segmented_row(&mut v, left_edge, ix, rock_count);
+                    Platform::sort_segmented_row(v, left_edge, ix, rock_count);
                     left_edge = ix + 1;
                     rock_count = 0;
                 }
@@ -100,11 +81,11 @@ impl Platform {
         }
         if left_edge < v.len() - 1 {
             let end = v.len();
-            Platform::sort_segmented_row(&mut v, left_edge, end, rock_count);
+            Platform::sort_segmented_row(v, left_edge, end, rock_count);
         }
     }
     fn tilt_right(&mut self) {
-        for r in 0..self.height {
+        for r in 0..self.data.height() {
             self.tilt_row_right(r);
         }
     }
@@ -120,8 +101,8 @@ impl Platform {
     }
     fn sum(&self) -> usize {
         let mut sum = 0;
-        for (ix, row) in self.data.iter().enumerate() {
-            let one_rock_weight = self.height - ix;
+        for (ix, row) in self.data.rows().enumerate() {
+            let one_rock_weight = self.data.height() - ix;
             let rock_count = row.iter().filter(|&r| r == &Rock::Round).count();
             sum += one_rock_weight * rock_count;
         }
@@ -130,48 +111,47 @@ impl Platform {
 }
 impl std::fmt::Display for Platform {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        for row in &self.data {
-            let a = row.iter().map(|q| match q {
-                Rock::Square => b'#',
-                Rock::Round => b'O',
-                _ => b'.',
-            });
-            let s = String::from_utf8(a.collect()).unwrap();
-            if let Err(e) = writeln!(f, "{}", s) {
-                return Err(e);
-            }
-        }
-        Ok(())
+        writeln!(f, "{}", self.data)
     }
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/14.txt").unwrap();
-    let mut p = Platform::new(&data);
-    p.rotate_cw();
-    p.tilt_right();
-    p.rotate_ccw();
-    println!("summed: {}", p.sum());
-    let data = std::fs::read_to_string("2023/14.txt").unwrap();
-    let mut p = Platform::new(&data);
-    p.rotate_cw();
-    // hypothesis, repeats stable
-    // seems to have a freq of 9
-    for x in 1..=595 {
-        p.cycle();
-        let str = p.to_string();
-        let digest = md5::compute(str);
-        p.rotate_ccw();
-        let sum = p.sum();
+
+pub struct Day {
+    platform: String,
+}
+
+impl Solution for Day {
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            platform: input.to_string(),
+        }
+    }
+
+    fn part1(&self) -> usize {
+        let mut p = Platform::new(&self.platform);
         p.rotate_cw();
-        println!("after {} {:x} {}", x, digest, sum);
+        p.tilt_right();
+        p.rotate_ccw();
+        p.sum()
     }
-    p.rotate_ccw();
 
-    println!("spinned: {}", p.sum());
+    fn part2(&self) -> usize {
+        let mut p = Platform::new(&self.platform);
+        p.rotate_cw();
+        // hypothesis, repeats stable
+        // seems to have a freq of 9, judging by the md5 of each state
+        for _ in 1..=595 {
+            p.cycle();
+        }
+        p.rotate_ccw();
+        p.sum()
 
-    // 91100 is too low
-    // 95619 is too low
-    // 95648 is too low
+        // 91100 is too low
+        // 95619 is too low
+        // 95648 is too low
+    }
 }
 
 #[cfg(test)]
@@ -266,9 +246,9 @@ O.#..O.#.#
     #[test]
     fn test_can_make_platform() {
         let p = Platform::new(&TEST_DATA);
-        assert_eq!(Rock::Square, *p.data.get(1).unwrap().get(4).unwrap());
-        assert_eq!(10, p.width);
-        assert_eq!(10, p.height);
+        assert_eq!(Rock::Square, p.data[(4, 1)]);
+        assert_eq!(10, p.data.width());
+        assert_eq!(10, p.data.height());
     }
     #[test]
     fn test_can_tostring() {
@@ -288,8 +268,8 @@ O.#..O.#.#
         let mut p = Platform::new(&"#...\n..O.");
         p.rotate_cw();
         assert_eq!(".#\n..\nO.\n..\n", p.to_string());
-        assert_eq!(2, p.width);
-        assert_eq!(4, p.height);
+        assert_eq!(2, p.data.width());
+        assert_eq!(4, p.data.height());
         p.rotate_ccw();
         assert_eq!("#...\n..O.\n", p.to_string());
     }
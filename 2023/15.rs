@@ -1,5 +1,4 @@
-#[macro_use]
-extern crate lazy_static;
+use crate::solution::Solution;
 
 struct Lens {
     id: String,
@@ -120,17 +119,32 @@ impl Boxes {
         sum
     }
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/15.txt").unwrap();
-    let s = sum(&data);
-    println!("hash: {}", s);
-    let mut boxes = Boxes::new();
-    let ops = Op::new_many(&data);
-    for op in ops {
-        boxes.run(op);
-    }
-    let power = boxes.power();
-    println!("power: {}", power);
+
+pub struct Day {
+    sequence: String,
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            sequence: input.trim().to_string(),
+        }
+    }
+
+    fn part1(&self) -> u32 {
+        sum(&self.sequence)
+    }
+
+    fn part2(&self) -> u32 {
+        let mut boxes = Boxes::new();
+        for op in Op::new_many(&self.sequence) {
+            boxes.run(op);
+        }
+        boxes.power()
+    }
 }
 
 fn sum(data: &str) -> u32 {
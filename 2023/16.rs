@@ -1,3 +1,5 @@
+use crate::grid::Grid;
+use crate::solution::Solution;
 use std::collections::{HashSet, VecDeque};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
@@ -129,14 +131,14 @@ impl Tile {
     }
 }
 struct Map {
-    tiles: Vec<Vec<Tile>>,
+    tiles: Grid<Tile>,
     active_row: isize,
     active_col: isize,
 }
 
 impl std::fmt::Display for Map {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        for row in &self.tiles {
+        for row in self.tiles.rows() {
             let str: String = row
                 .iter()
                 .map(|t| t.repr(self.active_row, self.active_col))
@@ -150,16 +152,14 @@ impl std::fmt::Display for Map {
 }
 impl Map {
     fn new(data: &str) -> Map {
-        let tiles: Vec<Vec<Tile>> = data
-            .lines()
-            .enumerate()
-            .map(|(row, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(col, c)| Tile::new(c, row as isize, col as isize))
-                    .collect()
-            })
-            .collect();
+        let chars = Grid::parse(data);
+        let tiles = Grid::from_cells(
+            chars.width(),
+            chars
+                .enumerate()
+                .map(|((col, row), c)| Tile::new(*c, row as isize, col as isize))
+                .collect(),
+        );
 
         Map {
             tiles,
@@ -179,32 +179,19 @@ impl Map {
                 break;
             }
             let t = todos.pop_front().unwrap();
-            if t.row < 0 || t.col < 0 {
-                continue;
-            }
             self.active_col = t.col;
             self.active_row = t.row;
-            if let Some(row) = self.tiles.get_mut(t.row as usize) {
-                if let Some(tile) = row.get_mut(t.col as usize) {
-                    let result = tile.pass(t.from);
-                    for next in result {
-                        todos.push_back(next);
-                    }
-                    //      println!("{}", &self);
+            if let Some(pos) = self.tiles.inside(t.col, t.row) {
+                let result = self.tiles[pos].pass(t.from);
+                for next in result {
+                    todos.push_back(next);
                 }
+                //      println!("{}", &self);
             }
         }
     }
     fn count_visited(&self) -> u32 {
-        let mut sum = 0;
-        for row in &self.tiles {
-            for t in row {
-                if t.visit_count > 0 {
-                    sum += 1;
-                }
-            }
-        }
-        sum
+        self.tiles.iter().filter(|t| t.visit_count > 0).count() as u32
     }
     fn max(data: &str) -> u32 {
         let height = data.lines().collect::<Vec<&str>>().len() as isize;
@@ -245,15 +232,30 @@ impl Map {
         max
     }
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/16.txt").unwrap();
-    let mut map = Map::new(&data);
-    map.walk(0, 0, Dir::W);
-    println!("visited: {}", map.count_visited());
 
-    let max = Map::max(&data);
+pub struct Day {
+    contraption: String,
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u32;
 
-    println!("max: {}", max); // 7489 is too low
+    fn parse(input: &str) -> Self {
+        Day {
+            contraption: input.to_string(),
+        }
+    }
+
+    fn part1(&self) -> u32 {
+        let mut map = Map::new(&self.contraption);
+        map.walk(0, 0, Dir::W);
+        map.count_visited()
+    }
+
+    fn part2(&self) -> u32 {
+        Map::max(&self.contraption) // 7489 is too low
+    }
 }
 
 #[cfg(test)]
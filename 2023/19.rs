@@ -1,34 +1,18 @@
-#[macro_use]
-extern crate lazy_static;
-use std::{
-    cmp::Ordering,
-    collections::{HashMap, HashSet},
-    time::SystemTime,
-};
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
 
 use regex::Regex;
 struct WorkSet {
     ratings: Vec<Rating>,
     workflows: HashMap<String, Workflow>,
 }
+#[derive(Clone, PartialEq, Debug)]
 struct Rule {
     op: Option<Op>,
     result: String,
     category: Option<Category>,
     limit: u32,
 }
-struct Limits {
-    x: Vec<Range>,
-    m: Vec<Range>,
-    a: Vec<Range>,
-    s: Vec<Range>,
-}
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
-struct RuleData {
-    op: Op,
-    limit: u32,
-    category: Category,
-}
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 struct Range {
     from: u32,
@@ -44,22 +28,67 @@ impl Range {
     fn delta(&self) -> u64 {
         (self.to as u64 - self.from as u64) + 1
     }
+    /// The values that pass the comparison and the values that do not,
+    /// either of which may be none at all.
+    fn split(&self, op: Op, limit: u32) -> (Option<Range>, Option<Range>) {
+        let below = |to: u32| (self.from <= to).then(|| Range::new(self.from, to.min(self.to)));
+        let above = |from: u32| (from <= self.to).then(|| Range::new(from.max(self.from), self.to));
+        match op {
+            Op::Lt => (limit.checked_sub(1).and_then(below), above(limit)),
+            Op::Gt => (limit.checked_add(1).and_then(above), below(limit)),
+        }
+    }
+}
+
+/// A box of parts, holding every combination of the values each category
+/// may take. Rules only look at x, m, a and s, but the box can have more
+/// sides than that, which just multiply its volume.
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Parts {
+    sides: Vec<Range>,
 }
-impl Limits {
-    fn new() -> Limits {
-        Limits {
-            x: Vec::new(),
-            m: Vec::new(),
-            a: Vec::new(),
-            s: Vec::new(),
+/// The values each category of a rating can take in the puzzle.
+const RATING_RANGE: Range = Range { from: 1, to: 4000 };
+
+impl Parts {
+    /// Every rating the puzzle allows.
+    fn ratings() -> Parts {
+        Parts {
+            sides: vec![RATING_RANGE; 4],
         }
     }
+    /// The box holding each category from the first to the second number
+    /// of its pair.
+    fn within(bounds: &[(u32, u32)]) -> Parts {
+        Parts {
+            sides: bounds
+                .iter()
+                .map(|(from, to)| Range::new(*from, *to))
+                .collect(),
+        }
+    }
+    fn volume(&self) -> u64 {
+        self.sides.iter().map(|r| r.delta()).product()
+    }
+    /// The parts the rule sends on to its result and the parts that go on
+    /// to the next rule.
+    fn split(&self, rule: &Rule) -> (Option<Parts>, Option<Parts>) {
+        let (Some(op), Some(category)) = (rule.op, rule.category) else {
+            return (Some(self.clone()), None);
+        };
+        let side = category.index();
+        let (pass, fail) = self.sides[side].split(op, rule.limit);
+        let with = |r: Range| {
+            let mut sides = self.sides.clone();
+            sides[side] = r;
+            Parts { sides }
+        };
+        (pass.map(with), fail.map(with))
+    }
 }
 
 struct Workflow {
     name: String,
-    accept_all: bool,
-    reject_all: bool,
     rules: Vec<Rule>,
 }
 struct Rating {
@@ -95,6 +124,16 @@ impl Category {
         }
     }
 }
+impl Category {
+    fn index(self) -> usize {
+        match self {
+            Category::X => 0,
+            Category::M => 1,
+            Category::A => 2,
+            Category::S => 3,
+        }
+    }
+}
 impl Op {
     fn from(m: Option<regex::Match>) -> Option<Op> {
         if let Some(mm) = m {
@@ -109,34 +148,34 @@ impl Op {
     }
 }
 
-impl RuleData {
-    fn from(rule: &Rule) -> RuleData {
-        RuleData {
-            category: rule.category.unwrap(),
-            limit: rule.limit,
-            op: rule.op.unwrap(),
-        }
-    }
-}
-
 lazy_static! {
     static ref PARSE_W: Regex = Regex::new(r"([^{]+)\{([^}]+)\}").unwrap();
     static ref PARSE_R: Regex = Regex::new(r"([xmas])([><])([0-9]+):(\w+)").unwrap();
     static ref PARSE_RATING: Regex = Regex::new(r"\{x=(\d+),m=(\d+),a=(\d+),s=(\d+)\}").unwrap();
 }
 
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let (Some(op), Some(category)) = (self.op, self.category) {
+            let category = match category {
+                Category::X => 'x',
+                Category::M => 'm',
+                Category::A => 'a',
+                Category::S => 's',
+            };
+            let op = match op {
+                Op::Lt => '<',
+                Op::Gt => '>',
+            };
+            write!(f, "{}{}{}:", category, op, self.limit)?;
+        }
+        write!(f, "{}", self.result)
+    }
+}
 impl std::fmt::Display for Workflow {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let d: String = if self.accept_all {
-            "A".to_owned()
-        } else if self.reject_all {
-            "R".to_owned()
-        } else {
-            " ".to_owned()
-        };
-        let deps: Vec<String> = self.rules.iter().map(|r| r.result.to_owned()).collect();
-
-        write!(f, "{} ({}) {{{}}}", self.name, d, deps.join(","))
+        let rules: Vec<String> = self.rules.iter().map(|r| r.to_string()).collect();
+        write!(f, "{}{{{}}}", self.name, rules.join(","))
     }
 }
 impl Workflow {
@@ -146,16 +185,7 @@ impl Workflow {
         let rules_str = cap.get(2).unwrap().as_str();
         let rules: Vec<Rule> = rules_str.split(",").map(Rule::new).collect();
 
-        Workflow {
-            name,
-            rules,
-            accept_all: false,
-            reject_all: false,
-        }
-    }
-    fn find_early_outs(&mut self) {
-        self.accept_all = self.rules.iter().all(|r| r.result == "A");
-        self.reject_all = self.rules.iter().all(|r| r.result == "R");
+        Workflow { name, rules }
     }
     fn run(&self, rating: &Rating) -> String {
         for rule in &self.rules {
@@ -178,6 +208,53 @@ impl Workflow {
         }
         panic!("invalid workflow end");
     }
+    /// Drops the rules no rating can get to, turns rules every rating left
+    /// passes into the last one, and merges neighbouring rules that send to
+    /// the same place. Whether a rule can be reached is judged from the
+    /// rules before it, for the ratings in `bounds`.
+    fn prune(&mut self, bounds: &Parts) -> bool {
+        let mut kept: Vec<Rule> = Vec::new();
+        let mut rest = Some(bounds.clone());
+        for rule in &self.rules {
+            let Some(parts) = rest else {
+                break;
+            };
+            let (pass, fail) = parts.split(rule);
+            rest = fail;
+            if pass.is_none() {
+                continue;
+            }
+            let rule = if rest.is_none() {
+                Rule::new(&rule.result)
+            } else {
+                rule.clone()
+            };
+            while let Some(last) = kept.last() {
+                if last.result != rule.result {
+                    break;
+                }
+                if rule.op.is_none() {
+                    kept.pop();
+                } else if last.op == rule.op && last.category == rule.category {
+                    // the looser of the two covers them both
+                    let tighter = match rule.op {
+                        Some(Op::Lt) => rule.limit < last.limit,
+                        _ => rule.limit > last.limit,
+                    };
+                    if tighter {
+                        break;
+                    }
+                    kept.pop();
+                } else {
+                    break;
+                }
+            }
+            kept.push(rule);
+        }
+        let changed = kept != self.rules;
+        self.rules = kept;
+        changed
+    }
 }
 
 impl Rule {
@@ -217,10 +294,25 @@ impl Rating {
         self.x + self.m + self.a + self.s
     }
 }
+impl std::fmt::Display for Rating {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{{x={},m={},a={},s={}}}", self.x, self.m, self.a, self.s)
+    }
+}
+/// The workflows, sorted by name, and the ratings, written the way the
+/// puzzle input is.
 impl std::fmt::Display for WorkSet {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let counts = self.count_early_outs();
-        writeln!(f, "A:{} R:{}", counts.0, counts.1)
+        let mut names: Vec<&String> = self.workflows.keys().collect();
+        names.sort();
+        for name in names {
+            writeln!(f, "{}", self.workflows[name])?;
+        }
+        writeln!(f)?;
+        for r in &self.ratings {
+            writeln!(f, "{}", r)?;
+        }
+        Ok(())
     }
 }
 impl WorkSet {
@@ -270,196 +362,183 @@ impl WorkSet {
         }
         sum
     }
-    fn count_early_outs(&self) -> (u32, u32) {
-        let mut accept = 0;
-        let mut reject = 0;
-        for wf in &self.workflows {
-            if wf.1.accept_all {
-                accept += 1;
-            } else if wf.1.reject_all {
-                reject += 1
-            }
-        }
-        (accept, reject)
-    }
-    fn find_early_outs(&mut self) {
-        for wf in self.workflows.values_mut() {
-            wf.find_early_outs();
-        }
-    }
-    fn minimize(&mut self) {
-        self.find_early_outs();
-        let accept_early: HashSet<String> = self
-            .workflows
-            .iter()
-            .filter_map(|f| {
-                if f.1.accept_all {
-                    Some(f.0.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
-        let reject_early: HashSet<String> = self
-            .workflows
-            .iter()
-            .filter_map(|f| {
-                if f.1.reject_all {
-                    Some(f.0.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
+    /// Points every rule sending to `from` at `to` instead.
+    fn redirect(&mut self, from: &str, to: &str) {
         for wf in self.workflows.values_mut() {
             for r in wf.rules.iter_mut() {
-                if accept_early.contains(&r.result) {
-                    r.result = "A".to_owned();
-                } else if reject_early.contains(&r.result) {
-                    r.result = "R".to_owned();
+                if r.result == from {
+                    r.result = to.to_owned();
                 }
             }
         }
     }
-
-    fn find_ranges(rules: &[RuleData], cat: Category) -> Vec<Range> {
-        let mut head = 1;
-        let mut ranges = Vec::new();
-        for r in rules.iter().filter(|f| f.category == cat) {
-            if r.op == Op::Lt {
-                if head < r.limit {
-                    ranges.push(Range::new(head, r.limit - 1));
-                    head = r.limit;
-                }
-            } else if r.op == Op::Gt {
-                if head == r.limit {
-                    ranges.push(Range::new(head, head));
-                    head += 1;
-                } else {
-                    ranges.push(Range::new(head, r.limit));
-                    head = r.limit + 1;
-                }
-            }
+    /// Skips over a workflow that sends everything to the same place,
+    /// which leaves `in` as just that one rule. Workflows are tried in
+    /// order of name, so the result is the same every time.
+    fn collapse(&mut self) -> bool {
+        let mut workflows: Vec<&Workflow> = self.workflows.values().collect();
+        workflows.sort_by_key(|wf| &wf.name);
+        let same = workflows.into_iter().find_map(|wf| {
+            let target = &wf.rules.first()?.result;
+            let all_same = wf.rules.iter().all(|r| &r.result == target);
+            let worth_it = wf.name != "in" || wf.rules.len() > 1;
+            (all_same && worth_it && *target != wf.name).then(|| (wf.name.clone(), target.clone()))
+        });
+        let Some((name, target)) = same else {
+            return false;
+        };
+        if name == "in" {
+            self.workflows.get_mut("in").unwrap().rules = vec![Rule::new(&target)];
+        } else {
+            self.redirect(&name, &target);
         }
-        if head <= 4000 {
-            ranges.push(Range::new(head, 4000));
+        true
+    }
+    /// Moves the rules of a workflow that only one other workflow falls
+    /// through to onto the end of that one.
+    fn inline(&mut self) -> bool {
+        let mut uses: HashMap<&str, usize> = HashMap::new();
+        for wf in self.workflows.values() {
+            for r in &wf.rules {
+                *uses.entry(&r.result).or_default() += 1;
+            }
         }
-        ranges
-    }
-    fn find_limits(&self) -> Limits {
-        let mut l = Limits::new();
-        let mut rules_set: HashSet<RuleData> = HashSet::new();
-        for wf in &self.workflows {
-            for rule in wf.1.rules.iter() {
-                if let Some(_) = rule.category {
-                    let data = RuleData::from(rule);
-                    if !rules_set.contains(&data) {
-                        rules_set.insert(data);
-                    }
+        let mut names: Vec<&String> = self.workflows.keys().collect();
+        names.sort();
+        let found = names.into_iter().find_map(|name| {
+            let last = self.workflows[name].rules.last()?;
+            let used_once = uses.get(last.result.as_str()) == Some(&1);
+            (last.op.is_none()
+                && used_once
+                && last.result != *name
+                && last.result != "in"
+                && self.workflows.contains_key(&last.result))
+            .then(|| (name.clone(), last.result.clone()))
+        });
+        let Some((into, from)) = found else {
+            return false;
+        };
+        let rules = self.workflows.remove(&from).unwrap().rules;
+        let wf = self.workflows.get_mut(&into).unwrap();
+        wf.rules.pop();
+        wf.rules.extend(rules);
+        true
+    }
+    /// Drops every workflow that no rating starting at `in` can get to.
+    fn drop_unreachable(&mut self) -> bool {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut todo = vec!["in".to_owned()];
+        while let Some(name) = todo.pop() {
+            if let Some(wf) = self.workflows.get(&name) {
+                if seen.insert(name) {
+                    todo.extend(wf.rules.iter().map(|r| r.result.clone()));
                 }
             }
         }
-        let mut all_rules: Vec<RuleData> = rules_set.into_iter().collect();
-        all_rules.sort_by(|a, b| {
-            if a.limit == b.limit {
-                if a.op == b.op {
-                    Ordering::Equal
-                } else if a.op == Op::Gt {
-                    Ordering::Greater
-                } else {
-                    Ordering::Less
-                }
-            } else if a.limit > b.limit {
-                Ordering::Greater
-            } else {
-                Ordering::Less
+        let before = self.workflows.len();
+        self.workflows.retain(|name, _| seen.contains(name));
+        before != self.workflows.len()
+    }
+    /// Simplifies the workflows as far as they go without changing which
+    /// ratings in `bounds` get accepted, and checks that with `crunch_in`.
+    fn optimize(&mut self, bounds: &Parts) -> Result<(), String> {
+        let before = self.crunch_in(bounds.clone());
+        loop {
+            let mut changed = false;
+            for wf in self.workflows.values_mut() {
+                changed |= wf.prune(bounds);
             }
-        });
-        l.x = WorkSet::find_ranges(&all_rules, Category::X);
-        l.m = WorkSet::find_ranges(&all_rules, Category::M);
-        l.a = WorkSet::find_ranges(&all_rules, Category::A);
-        l.s = WorkSet::find_ranges(&all_rules, Category::S);
-        l
-    }
-    fn crunch(&self) -> u64 {
-        let mut count: u64 = 0;
-        let limits = self.find_limits();
-        println!(
-            "x:{} m:{} a:{} s:{}",
-            &limits.x.len(),
-            &limits.m.len(),
-            &limits.a.len(),
-            &limits.s.len()
-        );
-        let progress_done: f32 = limits.x.len().to_owned() as f32;
-        let mut progress = 0f32;
-        let now = SystemTime::now();
-        for x_range in &limits.x {
-            let dx = x_range.delta();
-            for m_range in &limits.m {
-                let dm = m_range.delta();
-                for a_range in &limits.a {
-                    let da = a_range.delta();
-                    for s_range in &limits.s {
-                        let ds = s_range.delta();
-                        let rating = Rating {
-                            x: x_range.from,
-                            m: m_range.from,
-                            a: a_range.from,
-                            s: s_range.from,
-                        };
-                        let is_accepted = self.run(&rating);
-                        let rating_end = Rating {
-                            x: x_range.to,
-                            m: m_range.to,
-                            a: a_range.to,
-                            s: s_range.to,
-                        };
-                        let is_accepted_end = self.run(&rating_end);
-                        if is_accepted != is_accepted_end {
-                            panic!("invalid assumption")
-                        }
-                        if is_accepted {
-                            count += dx * dm * da * ds;
-                        }
-                    }
-                }
+            changed |= self.collapse();
+            changed |= self.inline();
+            changed |= self.drop_unreachable();
+            if !changed {
+                break;
             }
-            if let Ok(t) = now.elapsed() {
-                let percent: f32 = progress / progress_done;
-                let remaining_percent = 1f32 - percent;
-                let took = t.as_millis();
+        }
+        let after = self.crunch_in(bounds.clone());
+        if before != after {
+            return Err(format!(
+                "optimizing changed the accepted combinations from {} to {}",
+                before, after
+            ));
+        }
+        Ok(())
+    }
 
-                let one_p_speed: f32 = took as f32 / percent;
-                println!(
-                    "{:.2}% took {}ms - remaining: {:.2} minutes",
-                    percent,
-                    took,
-                    (remaining_percent * one_p_speed) / 60000f32
-                );
+    /// How many of the parts in the box get accepted when they start out
+    /// at the workflow `name`. Each rule splits off the parts it matches
+    /// and sends them on, and the rest carry on to the next rule.
+    fn accepted(&self, name: &str, parts: Parts) -> u64 {
+        match name {
+            "A" => return parts.volume(),
+            "R" => return 0,
+            _ => {}
+        }
+        let Some(workflow) = self.workflows.get(name) else {
+            panic!("invalid workflow received")
+        };
+        let mut count = 0;
+        let mut rest = Some(parts);
+        for rule in &workflow.rules {
+            let Some(parts) = rest else {
+                break;
+            };
+            let (matched, unmatched) = parts.split(rule);
+            if let Some(m) = matched {
+                count += self.accepted(&rule.result, m);
             }
-            progress += 1f32;
+            rest = unmatched;
         }
         count
     }
+    /// How many combinations of ratings the puzzle allows get accepted.
+    fn crunch(&self) -> u64 {
+        self.crunch_in(Parts::ratings())
+    }
+    /// How many of the ratings in the box get accepted.
+    fn crunch_in(&self, bounds: Parts) -> u64 {
+        self.accepted("in", bounds)
+    }
+}
+
+/// How many ratings get accepted by the workflows in `input`, with each
+/// category in turn taking the values from the first to the second number
+/// of its pair. Pairs past x, m, a and s count as categories no rule looks
+/// at.
+pub fn accepted_combinations(input: &str, bounds: &[(u32, u32)]) -> u64 {
+    WorkSet::new(input).crunch_in(Parts::within(bounds))
+}
+
+/// The system in `input` with its workflows optimized for the ratings in
+/// `bounds`, given as for `accepted_combinations`, and written back out in
+/// the puzzle's own syntax.
+pub fn optimize(input: &str, bounds: &[(u32, u32)]) -> Result<String, String> {
+    let mut w = WorkSet::new(input);
+    w.optimize(&Parts::within(bounds))?;
+    Ok(w.to_string())
+}
+
+pub struct Day {
+    system: String,
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/19.txt").unwrap();
-    let mut w = WorkSet::new(&data);
 
-    println!("sum: {}", w.summarize());
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            system: input.to_string(),
+        }
+    }
 
-    println!("start: {}", w);
-    w.minimize();
-    println!("m once: {}", w);
-    w.minimize();
-    println!("m twice: {}", w);
-    w.minimize();
-    println!("m thrice: {}", w);
+    fn part1(&self) -> u32 {
+        WorkSet::new(&self.system).summarize()
+    }
 
-    let c = w.crunch(); // 40000 hours with first attempt minimize
-                        // 1 hour with second attempt minimize (broken)
-    println!("crunch: {}", c);
+    fn part2(&self) -> u64 {
+        WorkSet::new(&self.system).crunch()
+    }
 }
 
 #[cfg(test)]
@@ -527,50 +606,119 @@ mod tests {
         assert_eq!(167409079868000, count);
     }
     #[test]
-    fn test_can_count_range_delta() {
-        let w = WorkSet::new(TEST_DATA);
-        let l = w.find_limits();
-        assert_eq!(1415, l.x[0].delta());
-        assert_eq!(1025, l.x[1].delta());
+    fn test_can_split_range() {
+        let r = Range::new(10, 20);
+        let some = |from, to| Some(Range::new(from, to));
+        assert_eq!((some(10, 14), some(15, 20)), r.split(Op::Lt, 15));
+        assert_eq!((some(16, 20), some(10, 15)), r.split(Op::Gt, 15));
+        assert_eq!((None, some(10, 20)), r.split(Op::Lt, 10));
+        assert_eq!((some(11, 20), some(10, 10)), r.split(Op::Gt, 10));
+        assert_eq!((some(10, 19), some(20, 20)), r.split(Op::Lt, 20));
+        assert_eq!((None, some(10, 20)), r.split(Op::Gt, 20));
+        assert_eq!((some(10, 20), None), r.split(Op::Lt, 30));
+        assert_eq!((some(10, 20), None), r.split(Op::Gt, 0));
+        assert_eq!((None, some(0, 5)), Range::new(0, 5).split(Op::Lt, 0));
+        let top = Range::new(5, u32::MAX);
+        assert_eq!((None, Some(top)), top.split(Op::Gt, u32::MAX));
     }
     #[test]
-    fn test_can_find_tricky_ranges() {
-        let w = WorkSet::new("px{a>1:ooo,a>2067:qkq,a<2068:aaa,a>2068:abcd,a<3333:uuu,rfg}\n\n");
-        let l = w.find_limits();
+    fn test_can_split_parts() {
+        let parts = Parts {
+            sides: vec![Range::new(1, 10); 5],
+        };
+        assert_eq!(100000, parts.volume());
+        let (pass, fail) = parts.split(&Rule::new("m<4:one"));
+        assert_eq!(Range::new(1, 3), pass.as_ref().unwrap().sides[1]);
+        assert_eq!(Range::new(4, 10), fail.as_ref().unwrap().sides[1]);
         assert_eq!(
-            vec![
-                Range::new(1, 1),
-                Range::new(2, 2067),
-                Range::new(2068, 2068),
-                Range::new(2069, 3332),
-                Range::new(3333, 4000),
-            ],
-            l.a
+            30000 + 70000,
+            pass.unwrap().volume() + fail.unwrap().volume()
         );
+        assert_eq!((Some(parts.clone()), None), parts.split(&Rule::new("R")));
     }
     #[test]
-    fn test_can_collect_rule_limits() {
-        let w = WorkSet::new(TEST_DATA);
-        let l = w.find_limits();
-        assert_eq!(
-            vec![
-                Range::new(1, 1415),
-                Range::new(1416, 2440),
-                Range::new(2441, 2662),
-                Range::new(2663, 4000),
-            ],
-            l.x
+    fn test_accepted_matches_running_every_rating() {
+        let w = WorkSet::new(
+            "in{x<5:lo,m>11:R,hi}\nlo{a>3:A,s<7:R,x>2:A,R}\nhi{s>14:A,a<9:lo,m<3:R,A}\n\n",
         );
-        assert_eq!(
-            vec![
-                Range::new(1, 838),
-                Range::new(839, 1548),
-                Range::new(1549, 1800),
-                Range::new(1801, 2090),
-                Range::new(2091, 4000),
-            ],
-            l.m
-        )
+        let side = Range::new(1, 16);
+        let mut expected = 0;
+        for x in 1..=16 {
+            for m in 1..=16 {
+                for a in 1..=16 {
+                    for s in 1..=16 {
+                        if w.run(&Rating { x, m, a, s }) {
+                            expected += 1;
+                        }
+                    }
+                }
+            }
+        }
+        let parts = Parts {
+            sides: vec![side; 4],
+        };
+        assert_eq!(expected, w.crunch_in(parts));
+        // a fifth category nothing looks at only scales the count
+        let parts = Parts {
+            sides: vec![side, side, side, side, Range::new(0, 2)],
+        };
+        assert_eq!(expected * 3, w.accepted("in", parts));
+    }
+    #[test]
+    fn test_can_print_system() {
+        let w = WorkSet::new(TEST_DATA);
+        let text = w.to_string();
+        assert!(text.starts_with("crn{x>2662:A,R}\ngd{a>3333:R,R}\nhdj{m>838:A,pv}\n"));
+        assert!(text.ends_with("\n\n{x=787,m=2655,a=1222,s=2876}\n{x=1679,m=44,a=2067,s=496}\n{x=2036,m=264,a=79,s=2244}\n{x=2461,m=1339,a=466,s=291}\n{x=2127,m=1623,a=2188,s=1013}\n"));
+        let again = WorkSet::new(&text);
+        assert_eq!(text, again.to_string());
+    }
+    #[test]
+    fn test_can_optimize() {
+        let optimized = optimize(TEST_DATA, &[(1, 4000); 4]).unwrap();
+        let expected = r"hdj{m>838:A,a>1716:R,A}
+in{s<1351:px,s>2770:A,m<1801:hdj,R}
+px{a<2006:qkq,m>2090:A,s<537:R,x>2440:R,A}
+qkq{x<1416:A,x>2662:A,R}
+";
+        assert!(optimized.starts_with(expected));
+        let w = WorkSet::new(&optimized);
+        assert_eq!(19114, w.summarize());
+        assert_eq!(167409079868000, w.crunch());
+    }
+    #[test]
+    fn test_can_prune_rules() {
+        let ratings = Parts::ratings();
+        let mut w = Workflow::new("in{x<10:R,x<20:R,m>5:R,a>3999:q,a>4000:A,s<4001:q,A}");
+        assert!(w.prune(&ratings));
+        assert_eq!("in{x<20:R,m>5:R,q}", w.to_string());
+        assert!(!w.prune(&ratings));
+        let all = [(1, 4000); 4];
+        let optimized = optimize("in{x<10:a,x<5:R,x>4000:R,a}\na{m<4001:A,R}\n\n", &all).unwrap();
+        assert_eq!("in{A}\n\n", optimized);
+    }
+    #[test]
+    fn test_can_count_within_bounds() {
+        let all = [(1, 4000); 4];
+        assert_eq!(167409079868000, accepted_combinations(TEST_DATA, &all));
+        // s<1351 goes to px, a<2006 on to qkq, and x<1416 is accepted there
+        let bounds = [(1, 1), (3000, 3001), (1, 1), (1, 1)];
+        assert_eq!(2, accepted_combinations(TEST_DATA, &bounds));
+    }
+    #[test]
+    fn test_can_prune_within_bounds() {
+        // no rating here has an x under 100, nor an m over 5
+        let bounds = Parts::within(&[(100, 200), (1, 5), (1, 4000), (1, 4000)]);
+        let mut w = Workflow::new("in{x<10:R,m>5:R,a<2000:A,R}");
+        assert!(w.prune(&bounds));
+        assert_eq!("in{a<2000:A,R}", w.to_string());
+        // the bounds only cut this down when the optimizer knows them
+        let input = "in{x<100:A,m>5:A,R}\n\n";
+        let tight = [(100, 200), (1, 5), (1, 4000), (1, 4000)];
+        assert_eq!("in{R}\n\n", optimize(input, &tight).unwrap());
+        let optimized = optimize(input, &[(1, 4000); 4]).unwrap();
+        assert_eq!(input, optimized);
+        assert_eq!(0, accepted_combinations(&optimized, &tight));
     }
     static TEST_DATA: &str = r"px{a<2006:qkq,m>2090:A,rfg}
 pv{a>1716:R,A}
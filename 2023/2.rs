@@ -0,0 +1,120 @@
+use crate::solution::Solution;
+use std::str::FromStr;
+
+/// The most cubes of each colour, red, green and blue, shown at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Game {
+    id: u32,
+    most: [u32; 3],
+}
+
+/// Reads lines like "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Game, String> {
+        let (game, draws) = line
+            .split_once(": ")
+            .ok_or_else(|| format!("expected \"Game n: draws\": {}", line))?;
+        let id = game
+            .strip_prefix("Game ")
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| format!("not a game: {}", game))?;
+        let mut most = [0; 3];
+        for cubes in draws.split([';', ',']).map(|c| c.trim()) {
+            let (count, colour) = cubes
+                .split_once(' ')
+                .ok_or_else(|| format!("expected \"count colour\": {}", cubes))?;
+            let count: u32 = count
+                .parse()
+                .map_err(|_| format!("not a count: {}", count))?;
+            let ix = ["red", "green", "blue"]
+                .iter()
+                .position(|c| *c == colour)
+                .ok_or_else(|| format!("unknown colour: {}", colour))?;
+            most[ix] = most[ix].max(count);
+        }
+        Ok(Game { id, most })
+    }
+}
+
+impl Game {
+    fn possible(&self, bag: [u32; 3]) -> bool {
+        self.most.iter().zip(bag).all(|(m, b)| *m <= b)
+    }
+
+    fn power(&self) -> u32 {
+        self.most.iter().product()
+    }
+}
+
+pub struct Day {
+    games: Vec<Game>,
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Self {
+        let games = input
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| l.parse())
+            .collect::<Result<_, _>>();
+        Day {
+            games: games.unwrap_or_else(|e| panic!("{}", e)),
+        }
+    }
+
+    fn part1(&self) -> u32 {
+        self.games
+            .iter()
+            .filter(|g| g.possible([12, 13, 14]))
+            .map(|g| g.id)
+            .sum()
+    }
+
+    fn part2(&self) -> u32 {
+        self.games.iter().map(|g| g.power()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_parse() {
+        let game: Game =
+            "Game 83: 14 red, 2 green; 3 blue, 16 red, 2 green; 4 green, 13 red, 1 blue"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            Game {
+                id: 83,
+                most: [16, 4, 3]
+            },
+            game
+        );
+        assert_eq!(
+            Err("unknown colour: purple".to_string()),
+            "Game 1: 3 purple".parse::<Game>()
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        let day = Day::parse(EXAMPLE);
+        assert_eq!(8, day.part1());
+        assert_eq!(48, day.games[0].power());
+        assert_eq!(2286, day.part2());
+    }
+}
@@ -1,10 +1,10 @@
-use std::collections::HashMap;
+use crate::solution::Solution;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
 
 use regex::Regex;
 
-#[macro_use]
-extern crate lazy_static;
-
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
 enum ModuleType {
     FlipFlop,
@@ -29,6 +29,62 @@ impl Transmission {
     }
 }
 
+/// A pulse as it went by: which button press it came from and how many
+/// hops from the button it was sent.
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct Event {
+    press: u64,
+    step: u64,
+    from: String,
+    to: String,
+    signal: Signal,
+}
+
+/// One pulse to a line, in the puzzle's own notation behind the press and
+/// step, like `3 1 broadcaster -low-> a`.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let signal = match self.signal {
+            Signal::High => "high",
+            Signal::Low => "low",
+        };
+        write!(
+            f,
+            "{} {} {} -{}-> {}",
+            self.press, self.step, self.from, signal, self.to
+        )
+    }
+}
+
+impl FromStr for Event {
+    type Err = String;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = line.split(' ').collect();
+        let [press, step, from, arrow, to] = parts[..] else {
+            return Err(format!(
+                "expected \"press step from -signal-> to\": {}",
+                line
+            ));
+        };
+        let number = |n: &str| {
+            n.parse::<u64>()
+                .map_err(|e| format!("{} in {}: {}", e, line, n))
+        };
+        let signal = match arrow {
+            "-high->" => Signal::High,
+            "-low->" => Signal::Low,
+            _ => return Err(format!("unknown pulse {} in {}", arrow, line)),
+        };
+        Ok(Event {
+            press: number(press)?,
+            step: number(step)?,
+            from: from.to_owned(),
+            to: to.to_owned(),
+            signal,
+        })
+    }
+}
+
 struct Module {
     name: String,
     typ: ModuleType,
@@ -38,14 +94,9 @@ struct Module {
     low_signals_sent: u64,
     flop_state_on: bool,
     conj_high_count: usize,
-    stats: Vec<u64>,
 }
 lazy_static! {
     static ref PARSE: Regex = Regex::new(r"([^ ]+) -> (.+)").unwrap();
-    static ref IMPORTANT: Vec<String> = vec!["rd", "bt", "fv", "pr"]
-        .iter()
-        .map(|&s| s.to_owned())
-        .collect();
 }
 
 impl Module {
@@ -62,7 +113,7 @@ impl Module {
             .collect();
         v
     }
-    fn handle(&mut self, t: &Transmission, button_count: u64) -> Vec<Transmission> {
+    fn handle(&mut self, t: &Transmission) -> Vec<Transmission> {
         match self.typ {
             ModuleType::Broadcast => self.each(t.signal),
             ModuleType::Conjunction => {
@@ -76,9 +127,6 @@ impl Module {
                     *entry = t.signal;
                 }
                 let send_low = self.inputs.len() == self.conj_high_count;
-                if !send_low && IMPORTANT.contains(&self.name) {
-                    self.stats.push(button_count);
-                }
                 self.each(if send_low { Signal::Low } else { Signal::High })
             }
             ModuleType::FlipFlop => match t.signal {
@@ -132,15 +180,23 @@ impl Module {
             conj_high_count: 0,
             high_signals_sent: 0,
             low_signals_sent: 0,
-            stats: Vec::new(),
         }
     }
 }
 
+/// How many presses to try before giving up on a counter ever firing.
+const MAX_PRESSES: u64 = 100_000;
+
 struct ModuleSet {
     modules: HashMap<String, Module>,
     button_press_count: u64,
     rx_sent: bool,
+    /// The module whose incoming high pulses get noted down.
+    watched: Option<String>,
+    /// The presses on which each module sent a high pulse to the watched one.
+    highs_to_watched: HashMap<String, Vec<u64>>,
+    /// Every pulse sent so far, once `record` has been called.
+    log: Option<Vec<Event>>,
 }
 impl ModuleSet {
     fn sum(&self) -> (u64, u64) {
@@ -154,25 +210,79 @@ impl ModuleSet {
     }
     fn run(&mut self) {
         let mut next = vec![Transmission::new(
-            "".to_owned(),
+            "button".to_owned(),
             "broadcaster".to_owned(),
             Signal::Low,
         )];
         self.button_press_count += 1;
 
+        let mut step = 0;
         while next.len() > 0 {
-            next = self.run_transmissions(&next, self.button_press_count);
+            if let Some(log) = &mut self.log {
+                log.extend(next.iter().map(|t| Event {
+                    press: self.button_press_count,
+                    step,
+                    from: t.from.clone(),
+                    to: t.to.clone(),
+                    signal: t.signal,
+                }));
+            }
+            next = self.run_transmissions(&next);
+            step += 1;
         }
     }
-    fn run_transmissions(
-        &mut self,
-        transmissions: &[Transmission],
-        button_count: u64,
-    ) -> Vec<Transmission> {
+    /// Starts keeping every pulse from the next press on.
+    fn record(&mut self) {
+        self.log.get_or_insert_with(Vec::new);
+    }
+    fn events(&self) -> Result<&[Event], String> {
+        self.log
+            .as_deref()
+            .ok_or_else(|| "pulses are not being recorded".to_string())
+    }
+    /// The first pulse `from` sent with the given signal, if it has.
+    fn first_sent(&self, from: &str, signal: Signal) -> Result<Option<&Event>, String> {
+        Ok(self
+            .events()?
+            .iter()
+            .find(|e| e.from == from && e.signal == signal))
+    }
+    /// Whether each flip-flop was on once `press` had been dealt with,
+    /// worked out from the log: every low pulse a flip-flop gets flips it.
+    fn flip_flops_after(&self, press: u64) -> Result<BTreeMap<String, bool>, String> {
+        let events = self.events()?;
+        if press > self.button_press_count {
+            return Err(format!(
+                "only {} presses have been made, not {}",
+                self.button_press_count, press
+            ));
+        }
+        let mut states: BTreeMap<String, bool> = self
+            .modules
+            .values()
+            .filter(|m| m.typ == ModuleType::FlipFlop)
+            .map(|m| (m.name.clone(), false))
+            .collect();
+        for e in events.iter().take_while(|e| e.press <= press) {
+            if e.signal == Signal::Low {
+                if let Some(on) = states.get_mut(&e.to) {
+                    *on = !*on;
+                }
+            }
+        }
+        Ok(states)
+    }
+    fn run_transmissions(&mut self, transmissions: &[Transmission]) -> Vec<Transmission> {
         let mut nexts = Vec::new();
         for t in transmissions {
+            if t.signal == Signal::High && self.watched.as_ref() == Some(&t.to) {
+                let presses = self.highs_to_watched.entry(t.from.clone()).or_default();
+                if presses.last() != Some(&self.button_press_count) {
+                    presses.push(self.button_press_count);
+                }
+            }
             if let Some(to) = self.modules.get_mut(&t.to) {
-                let mut nn = to.handle(t, button_count);
+                let mut nn = to.handle(t);
                 nexts.append(&mut nn)
             } else {
                 if t.to == "rx" && t.signal == Signal::Low {
@@ -204,8 +314,222 @@ impl ModuleSet {
             modules,
             button_press_count: 0,
             rx_sent: false,
+            watched: None,
+            highs_to_watched: HashMap::new(),
+            log: None,
         }
     }
+
+    /// The one module sending to `target`, which has to be a conjunction.
+    fn feeder(&self, target: &str) -> Result<&Module, String> {
+        let mut feeders: Vec<&Module> = self
+            .modules
+            .values()
+            .filter(|m| m.outputs.iter().any(|o| o == target))
+            .collect();
+        feeders.sort_by_key(|m| &m.name);
+        match feeders[..] {
+            [] => Err(format!("no module sends to {}", target)),
+            [m] if m.typ == ModuleType::Conjunction => Ok(m),
+            [m] => Err(format!(
+                "{} sends to {} but is not a conjunction",
+                m.name, target
+            )),
+            [a, b, ..] => Err(format!(
+                "{} and {} both send to {}, expected a single conjunction",
+                a.name, b.name, target
+            )),
+        }
+    }
+
+    /// Every module that sends pulses on their way to `name`, short of the
+    /// broadcaster everything starts from.
+    fn upstream(&self, name: &str) -> HashSet<String> {
+        let mut seen = HashSet::new();
+        let mut todo = vec![name.to_owned()];
+        while let Some(n) = todo.pop() {
+            let Some(m) = self.modules.get(&n) else {
+                continue;
+            };
+            if m.typ != ModuleType::Broadcast && seen.insert(n) {
+                todo.extend(m.inputs.keys().cloned());
+            }
+        }
+        seen
+    }
+
+    /// The conjunction before `target` and the inputs to it, which have to
+    /// be the outputs of counters that share no modules.
+    fn counters(&self, target: &str) -> Result<(String, Vec<String>), String> {
+        let hub = self.feeder(target)?;
+        let mut inputs: Vec<String> = hub.inputs.keys().cloned().collect();
+        inputs.sort();
+        let parts: Vec<HashSet<String>> = inputs.iter().map(|i| self.upstream(i)).collect();
+        for a in 0..parts.len() {
+            for b in a + 1..parts.len() {
+                if let Some(shared) = parts[a].intersection(&parts[b]).min() {
+                    return Err(format!(
+                        "the counters behind {} and {} share {}, so they are not independent",
+                        inputs[a], inputs[b], shared
+                    ));
+                }
+            }
+        }
+        Ok((hub.name.clone(), inputs))
+    }
+
+    /// How many presses it takes, from the start, for `target` to get a low
+    /// pulse. The conjunction before it sends low once every one of its
+    /// counters fires in the same press, so this is the least common
+    /// multiple of their periods, found by pressing until each has fired a
+    /// few times. Expects a freshly built set.
+    fn presses_until_low(&mut self, target: &str) -> Result<u64, String> {
+        let (hub, inputs) = self.counters(target)?;
+        self.watched = Some(hub.clone());
+        let fired = |s: &ModuleSet, i: &String| s.highs_to_watched.get(i).map_or(0, |p| p.len());
+        while self.button_press_count < MAX_PRESSES && inputs.iter().any(|i| fired(self, i) < 3) {
+            self.run();
+        }
+        let periods = inputs
+            .iter()
+            .map(|i| match self.highs_to_watched.get(i).map(|p| &p[..]) {
+                None | Some([]) => Err(format!(
+                    "{} never sent a high pulse to {} in {} presses",
+                    i, hub, MAX_PRESSES
+                )),
+                Some(presses @ [first, ..])
+                    if presses.len() >= 3
+                        && presses.iter().zip(1..).all(|(p, k)| *p == k * first) =>
+                {
+                    Ok(*first)
+                }
+                Some(presses) => Err(format!(
+                    "{} does not send high to {} on a fixed period, but on presses {:?}",
+                    i, hub, presses
+                )),
+            })
+            .collect::<Result<Vec<u64>, String>>()?;
+        lcm_many(&periods)
+            .ok_or_else(|| format!("nothing sends to {}, so {} never gets a pulse", hub, target))
+    }
+
+    /// The module graph in Graphviz DOT, each kind of module drawn as its
+    /// own shape. With `annotate`, every module is labelled with the pulses
+    /// it has sent so far and flip-flops with whether they are on, filled in
+    /// if they are.
+    fn to_dot(&self, annotate: bool) -> String {
+        let mut names: Vec<&String> = self.modules.keys().collect();
+        names.sort();
+        let mut sinks: Vec<&String> = self
+            .modules
+            .values()
+            .flat_map(|m| &m.outputs)
+            .filter(|o| !self.modules.contains_key(*o))
+            .collect();
+        sinks.sort();
+        sinks.dedup();
+
+        let mut dot = vec!["digraph modules {".to_string()];
+        if annotate {
+            let s = if self.button_press_count == 1 {
+                ""
+            } else {
+                "es"
+            };
+            dot.push(format!(
+                "    label=\"after {} press{}\";",
+                self.button_press_count, s
+            ));
+        }
+        for name in &names {
+            let m = &self.modules[*name];
+            let mut attributes = vec![format!(
+                "shape={}",
+                match m.typ {
+                    ModuleType::Broadcast => "doubleoctagon",
+                    ModuleType::FlipFlop => "box",
+                    ModuleType::Conjunction => "invtriangle",
+                }
+            )];
+            if annotate {
+                let mut label = format!(
+                    "{}\\nhigh {}, low {}",
+                    name, m.high_signals_sent, m.low_signals_sent
+                );
+                if m.typ == ModuleType::FlipFlop {
+                    label.push_str(if m.flop_state_on { "\\non" } else { "\\noff" });
+                    if m.flop_state_on {
+                        attributes.push("style=filled".to_string());
+                    }
+                }
+                attributes.push(format!("label=\"{}\"", label));
+            }
+            dot.push(format!("    \"{}\" [{}];", name, attributes.join(", ")));
+        }
+        for sink in sinks {
+            dot.push(format!("    \"{}\" [shape=plaintext];", sink));
+        }
+        for name in &names {
+            for o in &self.modules[*name].outputs {
+                dot.push(format!("    \"{}\" -> \"{}\";", name, o));
+            }
+        }
+        dot.push("}".to_string());
+        dot.join("\n") + "\n"
+    }
+}
+
+/// The module network described by `input` as a Graphviz DOT graph. Given a
+/// number of button presses, the graph shows what every module has done
+/// after that many.
+pub fn dot(input: &str, presses: Option<u64>) -> String {
+    let mut r = ModuleSet::new(input);
+    for _ in 0..presses.unwrap_or(0) {
+        r.run();
+    }
+    r.to_dot(presses.is_some())
+}
+
+/// Every pulse sent over the first `presses` presses of the network
+/// described by `input`, a line each, so runs can be compared with diff.
+pub fn pulse_log(input: &str, presses: u64) -> String {
+    let mut r = ModuleSet::new(input);
+    r.record();
+    for _ in 0..presses {
+        r.run();
+    }
+    r.events()
+        .unwrap()
+        .iter()
+        .map(|e| format!("{}\n", e))
+        .collect()
+}
+
+/// The press, and the step within it, on which `module` first sends a high
+/// pulse, or a low one, trying at most `max_presses` presses.
+pub fn first_sent(input: &str, module: &str, high: bool, max_presses: u64) -> Option<(u64, u64)> {
+    let signal = if high { Signal::High } else { Signal::Low };
+    let mut r = ModuleSet::new(input);
+    r.record();
+    for _ in 0..max_presses {
+        r.run();
+        if let Some(e) = r.first_sent(module, signal).unwrap() {
+            return Some((e.press, e.step));
+        }
+        // the pulses of the presses before have been looked through already
+        r.log = Some(Vec::new());
+    }
+    None
+}
+
+/// Whether each flip-flop is on after `presses` presses, by name.
+pub fn flip_flops_after(input: &str, presses: u64) -> BTreeMap<String, bool> {
+    let mut r = ModuleSet::new(input);
+    r.record();
+    for _ in 0..presses {
+        r.run();
+    }
+    r.flip_flops_after(presses).unwrap()
 }
 
 fn gcd(first: u64, second: u64) -> u64 {
@@ -223,65 +547,44 @@ fn lcm(first: u64, second: u64) -> u64 {
     let gcd = gcd(first, second);
     return (first * second) / gcd;
 }
-fn gcd_many(n: &[u64]) -> u64 {
-    let mut r = n[0];
-    for i in 1..n.len() {
-        r = gcd(r, n[i]);
-    }
-    r
-}
-fn lcm_many(n: &[u64]) -> u64 {
-    let mut r = n[0];
+/// The least common multiple of all the numbers, if there are any.
+fn lcm_many(n: &[u64]) -> Option<u64> {
+    let mut r = *n.first()?;
     for i in 1..n.len() {
         r = lcm(r, n[i]);
     }
-    r
+    Some(r)
 }
 
-fn main() {
-    let data = std::fs::read_to_string("2023/20.txt").unwrap();
-    let mut r = ModuleSet::new(&data);
+pub struct Day {
+    modules: String,
+}
 
-    for _ in 1..=1000 {
-        r.run();
-    }
-    let (highs, lows) = r.sum();
-    println!("highs:{} lows:{} product: {}", highs, lows, highs * lows);
+impl Solution for Day {
+    type Part1 = u64;
+    type Part2 = u64;
 
-    let mut r = ModuleSet::new(&data);
-    for _ in 0..50000 {
-        r.run();
-        if r.rx_sent {
-            break;
+    fn parse(input: &str) -> Self {
+        Day {
+            modules: input.to_string(),
         }
     }
-    for imp in IMPORTANT.iter() {
-        if let Some(module) = r.modules.get(imp) {
-            println!("stats for {}", imp);
-            println!("hits: {:?}", module.stats);
-            let mut d0 = 0;
-            let mut deltas: Vec<u64> = vec![];
-            for st in &module.stats {
-                deltas.push(st - d0);
-                d0 = *st;
-            }
-            println!("deltas: {:?}", deltas);
+
+    fn part1(&self) -> u64 {
+        let mut r = ModuleSet::new(&self.modules);
+
+        for _ in 1..=1000 {
+            r.run();
         }
+        let (highs, lows) = r.sum();
+        highs * lows
+    }
+
+    fn part2(&self) -> u64 {
+        ModuleSet::new(&self.modules)
+            .presses_until_low("rx")
+            .unwrap_or_else(|e| panic!("{}", e))
     }
-    //  stats for rd
-    // hits: [3911, 7822, 11733, 15644, 19555, 23466, 27377, 31288, 35199, 39110, 43021, 46932]
-    // deltas: [3911, 3911, 3911, 3911, 3911, 3911, 3911, 3911, 3911, 3911, 3911, 3911]
-    // stats for bt
-    // hits: [3917, 7834, 11751, 15668, 19585, 23502, 27419, 31336, 35253, 39170, 43087, 47004]
-    // deltas: [3917, 3917, 3917, 3917, 3917, 3917, 3917, 3917, 3917, 3917, 3917, 3917]
-    // stats for fv
-    // hits: [3929, 7858, 11787, 15716, 19645, 23574, 27503, 31432, 35361, 39290, 43219, 47148]
-    // deltas: [3929, 3929, 3929, 3929, 3929, 3929, 3929, 3929, 3929, 3929, 3929, 3929]
-    // stats for pr
-    // hits: [3793, 7586, 11379, 15172, 18965, 22758, 26551, 30344, 34137, 37930, 41723, 45516, 49309]
-    // deltas: [3793, 3793, 3793, 3793, 3793, 3793, 3793, 3793, 3793, 3793, 3793, 3793, 3793]
-
-    println!("gcd: {}", lcm_many(&vec![3911, 3917, 3929, 3793]));
 }
 
 #[cfg(test)]
@@ -331,5 +634,217 @@ mod tests {
         assert_eq!(5, r.modules.len());
     }
 
-    static TEST_DATA: &str = r"";
+    const EXAMPLE: &str = r"broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output";
+
+    #[test]
+    fn test_dot() {
+        let expected = r#"digraph modules {
+    "a" [shape=box];
+    "b" [shape=box];
+    "broadcaster" [shape=doubleoctagon];
+    "con" [shape=invtriangle];
+    "inv" [shape=invtriangle];
+    "output" [shape=plaintext];
+    "a" -> "inv";
+    "a" -> "con";
+    "b" -> "con";
+    "broadcaster" -> "a";
+    "con" -> "output";
+    "inv" -> "b";
+}
+"#;
+        assert_eq!(expected, dot(EXAMPLE, None));
+    }
+
+    #[test]
+    fn test_dot_annotated() {
+        let after_one = dot(EXAMPLE, Some(1));
+        assert!(after_one.contains("    label=\"after 1 press\";\n"));
+        assert!(after_one
+            .contains(r#"    "a" [shape=box, style=filled, label="a\nhigh 2, low 0\non"];"#));
+        assert!(after_one.contains(r#"    "con" [shape=invtriangle, label="con\nhigh 1, low 1"];"#));
+        // the second press turns a off again, and b stays on
+        let after_two = dot(EXAMPLE, Some(2));
+        assert!(after_two.contains("after 2 presses"));
+        assert!(after_two.contains(r#"    "a" [shape=box, label="a\nhigh 2, low 2\noff"];"#));
+        assert!(after_two.contains(r#"label="b\nhigh 1, low 0\non""#));
+    }
+
+    #[test]
+    fn test_pulse_log() {
+        let log = pulse_log(EXAMPLE, 2);
+        let first_press: Vec<&str> = log.lines().take_while(|l| l.starts_with("1 ")).collect();
+        assert_eq!(
+            vec![
+                "1 0 button -low-> broadcaster",
+                "1 1 broadcaster -low-> a",
+                "1 2 a -high-> inv",
+                "1 2 a -high-> con",
+                "1 3 inv -low-> b",
+                "1 3 con -high-> output",
+                "1 4 b -high-> con",
+                "1 5 con -low-> output",
+            ],
+            first_press
+        );
+        let events = log
+            .lines()
+            .map(|l| l.parse::<Event>())
+            .collect::<Result<Vec<Event>, String>>()
+            .unwrap();
+        let mut r = ModuleSet::new(EXAMPLE);
+        r.record();
+        r.run();
+        r.run();
+        assert_eq!(r.events().unwrap(), &events[..]);
+        assert_eq!(
+            "expected \"press step from -signal-> to\": 1 a -low-> b",
+            "1 a -low-> b".parse::<Event>().unwrap_err()
+        );
+        assert_eq!(
+            "unknown pulse -mid-> in 1 0 a -mid-> b",
+            "1 0 a -mid-> b".parse::<Event>().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_log_queries() {
+        let mut r = ModuleSet::new(EXAMPLE);
+        assert_eq!(
+            Err("pulses are not being recorded".to_string()),
+            r.flip_flops_after(0)
+        );
+        r.record();
+        for _ in 0..4 {
+            r.run();
+        }
+        let first_low = r.first_sent("con", Signal::Low).unwrap().unwrap();
+        assert_eq!((1, 5), (first_low.press, first_low.step));
+        let b_low = r.first_sent("b", Signal::Low).unwrap().unwrap();
+        assert_eq!((3, 4), (b_low.press, b_low.step));
+        assert_eq!(None, r.first_sent("output", Signal::High).unwrap());
+
+        let state = |press| {
+            r.flip_flops_after(press)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        let both = |a, b| vec![("a".to_string(), a), ("b".to_string(), b)];
+        assert_eq!(both(false, false), state(0));
+        assert_eq!(both(true, true), state(1));
+        assert_eq!(both(false, true), state(2));
+        assert_eq!(both(true, false), state(3));
+        assert_eq!(both(false, false), state(4));
+        assert_eq!(
+            Err("only 4 presses have been made, not 5".to_string()),
+            r.flip_flops_after(5)
+        );
+    }
+
+    #[test]
+    fn test_query_input() {
+        assert_eq!(Some((1, 5)), first_sent(EXAMPLE, "con", false, 10));
+        assert_eq!(Some((3, 4)), first_sent(EXAMPLE, "b", false, 10));
+        assert_eq!(None, first_sent(EXAMPLE, "b", false, 2));
+        assert_eq!(None, first_sent(EXAMPLE, "output", true, 10));
+        let states = flip_flops_after(EXAMPLE, 3);
+        assert_eq!(Some(&true), states.get("a"));
+        assert_eq!(Some(&false), states.get("b"));
+    }
+
+    /// A chain of flip-flops counting presses in binary, with a conjunction
+    /// that sends low and starts the count over on reaching `period`,
+    /// inverted on its way to `hub`.
+    fn counter(name: &str, bits: u32, period: u32, hub: &str) -> String {
+        let flop = |i: u32| format!("{}{}", name, i);
+        let mut lines = Vec::new();
+        for i in 0..bits {
+            let mut outputs = Vec::new();
+            if i + 1 < bits {
+                outputs.push(flop(i + 1));
+            }
+            if period & 1 << i != 0 {
+                outputs.push(format!("{}c", name));
+            }
+            lines.push(format!("%{} -> {}", flop(i), outputs.join(", ")));
+        }
+        let resets = (0..bits).filter(|i| *i == 0 || period & 1 << i == 0);
+        let mut outputs: Vec<String> = resets.map(flop).collect();
+        outputs.push(format!("{}i", name));
+        lines.push(format!("&{}c -> {}", name, outputs.join(", ")));
+        lines.push(format!("&{}i -> {}", name, hub));
+        lines.join("\n")
+    }
+
+    fn network(periods: &[u32]) -> String {
+        let names = ["a", "b", "c", "d"];
+        let starts: Vec<String> = (0..periods.len())
+            .map(|i| format!("{}0", names[i]))
+            .collect();
+        let mut lines = vec![format!("broadcaster -> {}", starts.join(", "))];
+        for (i, p) in periods.iter().enumerate() {
+            lines.push(counter(names[i], 4, *p, "hub"));
+        }
+        lines.push("&hub -> rx".to_string());
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_presses_until_low() {
+        let data = network(&[11, 13, 15]);
+        assert_eq!(Ok(2145), ModuleSet::new(&data).presses_until_low("rx"));
+        // pressing that many times really does send rx a low pulse
+        let mut r = ModuleSet::new(&data);
+        while !r.rx_sent {
+            r.run();
+        }
+        assert_eq!(2145, r.button_press_count);
+    }
+
+    #[test]
+    fn test_counters() {
+        let r = ModuleSet::new(&network(&[9, 15]));
+        let (hub, inputs) = r.counters("rx").unwrap();
+        assert_eq!("hub", hub);
+        assert_eq!(vec!["ai", "bi"], inputs);
+        assert_eq!(6, r.upstream("ai").len());
+    }
+
+    #[test]
+    fn test_structure_errors() {
+        let error = |data: &str| ModuleSet::new(data).presses_until_low("rx").unwrap_err();
+        assert_eq!(
+            "no module sends to rx",
+            error("broadcaster -> a\n%a -> b\n&b -> a")
+        );
+        assert_eq!(
+            "a sends to rx but is not a conjunction",
+            error("broadcaster -> a\n%a -> rx")
+        );
+        assert_eq!(
+            "a and b both send to rx, expected a single conjunction",
+            error("broadcaster -> b, a\n%b -> rx\n%a -> rx")
+        );
+        assert_eq!(
+            "nothing sends to hub, so rx never gets a pulse",
+            error("broadcaster -> a\n%a -> b\n&b -> a\n&hub -> rx")
+        );
+        // the two inputs of the hub both count the same flip-flop
+        assert_eq!(
+            "the counters behind x and y share a, so they are not independent",
+            error("broadcaster -> a\n%a -> x, y\n&x -> hub\n&y -> hub\n&hub -> rx")
+        );
+        // a flip-flop sends high on every other press, but from the first
+        // press on, so it is out of step with a period of two
+        let data = "broadcaster -> a\n%a -> hub\n&hub -> rx";
+        assert_eq!(
+            "a does not send high to hub on a fixed period, but on presses [1, 3, 5]",
+            error(data)
+        );
+    }
 }
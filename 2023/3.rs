@@ -1,5 +1,4 @@
-#[macro_use]
-extern crate lazy_static;
+use crate::solution::Solution;
 use regex::Regex;
 
 #[derive(Debug, Clone, Copy)]
@@ -18,18 +17,31 @@ struct Symbol {
 struct Gear {
     ratio: u32,
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/3.txt").unwrap();
-    let inp = parse_input(&data);
 
-    println!("sum: {}", sum(&inp));
-    let gears = get_gears(&inp.1, &inp.0);
-    let mut sum = 0;
-    for g in gears {
-        sum += g.ratio;
+pub struct Day {
+    schematic: (Vec<PartNumber>, Vec<Symbol>),
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            schematic: parse_input(input),
+        }
+    }
+
+    fn part1(&self) -> u32 {
+        sum(&self.schematic)
+    }
+
+    fn part2(&self) -> u32 {
+        let (numbers, symbols) = &self.schematic;
+        get_gears(symbols, numbers).iter().map(|g| g.ratio).sum()
     }
-    println!("gears: {:?}", sum);
 }
+
 fn sum(inp: &(Vec<PartNumber>, Vec<Symbol>)) -> u32 {
     let mut sum = 0;
     for part in inp.0.iter().filter(|p| has_adjacent_symbol(p, &inp.1)) {
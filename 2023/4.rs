@@ -0,0 +1,98 @@
+use crate::solution::Solution;
+
+/// How many of the numbers on each card are winning numbers.
+fn matches(input: &str) -> Result<Vec<usize>, String> {
+    input
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let (_, numbers) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected \"Card n: numbers\": {}", line))?;
+            let (winning, mine) = numbers
+                .split_once('|')
+                .ok_or_else(|| format!("expected \"winning | mine\": {}", line))?;
+            let parse = |s: &str| {
+                s.split_whitespace()
+                    .map(|n| n.parse().map_err(|_| format!("not a number: {}", n)))
+                    .collect::<Result<Vec<u32>, String>>()
+            };
+            let winning = parse(winning)?;
+            Ok(parse(mine)?.iter().filter(|n| winning.contains(n)).count())
+        })
+        .collect()
+}
+
+/// The first match makes a card worth one point, each after that doubles it.
+fn points(matches: &[usize]) -> u32 {
+    matches
+        .iter()
+        .filter(|m| **m > 0)
+        .map(|m| 1 << (m - 1))
+        .sum()
+}
+
+/// How many cards there are in the end, when each card wins a copy of as
+/// many of the cards after it as it has matches.
+fn copies(matches: &[usize]) -> u32 {
+    let mut copies = vec![1; matches.len()];
+    for (i, m) in matches.iter().enumerate() {
+        let end = (i + 1 + m).min(matches.len());
+        for j in i + 1..end {
+            copies[j] += copies[i];
+        }
+    }
+    copies.iter().sum()
+}
+
+pub struct Day {
+    matches: Vec<usize>,
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            matches: matches(input).unwrap_or_else(|e| panic!("{}", e)),
+        }
+    }
+
+    fn part1(&self) -> u32 {
+        points(&self.matches)
+    }
+
+    fn part2(&self) -> u32 {
+        copies(&self.matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_matches() {
+        assert_eq!(Ok(vec![4, 2, 2, 1, 0, 0]), matches(EXAMPLE));
+        assert_eq!(
+            Err("expected \"winning | mine\": Card 1: 1 2".to_string()),
+            matches("Card 1: 1 2")
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        let day = Day::parse(EXAMPLE);
+        assert_eq!(13, day.part1());
+        assert_eq!(30, day.part2());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::cmp;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -171,34 +172,48 @@ impl Map {
     }
 }
 
-fn find(almanac: Almanac) -> u64 {
+fn find(almanac: &Almanac) -> u64 {
     let mut min = u64::MAX;
     for ix in (0usize..(almanac.seeds.len() - 1)).step_by(2) {
-        let now = std::time::SystemTime::now();
         let start: u64 = *almanac.seeds.get(ix).unwrap();
         let length: u64 = *almanac.seeds.get(ix + 1).unwrap();
-        println!("looking for {} - {}", start, length);
 
         let result = almanac.transform(Range::new(start, length));
-        println!("{:?}", result);
         for r in &result {
             if r.from < min {
-                println!("found {}", r.from);
                 min = r.from
             }
         }
-
-        let took = now.elapsed().unwrap().as_millis();
-        println!("size {}, took {}ms", result.len(), took);
     }
-    println!("min: {}", min);
     return min;
 }
 
-fn main() {
-    let data = std::fs::read_to_string("2023/5.txt").unwrap();
-    let almanac = Almanac::new(&data);
-    let _ = find(almanac);
+pub struct Day {
+    almanac: Almanac,
+}
+
+impl Solution for Day {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            almanac: Almanac::new(&input.replace("\r\n", "\n")),
+        }
+    }
+
+    fn part1(&self) -> u64 {
+        self.almanac
+            .seeds
+            .iter()
+            .map(|&seed| self.almanac.follow(seed))
+            .min()
+            .unwrap()
+    }
+
+    fn part2(&self) -> u64 {
+        find(&self.almanac)
+    }
 }
 
 #[cfg(test)]
@@ -297,7 +312,7 @@ humidity-to-location map:
     #[test]
     fn test_finder() {
         let a = Almanac::new(TEST_DATA);
-        let r = find(a);
+        let r = find(&a);
         assert_eq!(46, r);
         // let mut interim: Vec<Range> = vec![source];
         // for map in &self.maps {
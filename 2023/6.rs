@@ -0,0 +1,112 @@
+use crate::solution::Solution;
+
+/// The race times and record distances, as the two lines read.
+fn races(input: &str) -> Result<(Vec<&str>, Vec<&str>), String> {
+    let mut lines = input.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+    let mut row = |label: &str| {
+        let line = lines.next().unwrap_or("");
+        line.strip_prefix(label)
+            .map(|rest| rest.split_whitespace().collect::<Vec<_>>())
+            .ok_or_else(|| format!("expected \"{}\": {}", label, line))
+    };
+    let (times, records) = (row("Time:")?, row("Distance:")?);
+    if times.len() != records.len() {
+        return Err("every race needs a time and a distance".to_string());
+    }
+    Ok((times, records))
+}
+
+fn number(digits: &str) -> u64 {
+    digits
+        .parse()
+        .unwrap_or_else(|_| panic!("not a number: {}", digits))
+}
+
+/// How many whole seconds of holding the button beat the record. Holding for
+/// `h` goes `h * (time - h)`, so the winners lie between the roots of a
+/// quadratic.
+fn ways(time: u64, record: u64) -> u64 {
+    let beats = |h: u64| h * (time - h) > record;
+    // a first guess from floating point, then nudged to the exact edge
+    let half = time as f64 / 2.0;
+    let spread = (half * half - record as f64).max(0.0).sqrt();
+    let mut low = ((half - spread).floor().max(0.0) as u64).min(time / 2);
+    while low > 0 && beats(low - 1) {
+        low -= 1;
+    }
+    while low <= time / 2 && !beats(low) {
+        low += 1;
+    }
+    if low > time / 2 {
+        return 0;
+    }
+    // the winners are symmetric around half the time
+    time + 1 - 2 * low
+}
+
+pub struct Day {
+    times: Vec<String>,
+    records: Vec<String>,
+}
+
+impl Solution for Day {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Self {
+        let (times, records) = races(input).unwrap_or_else(|e| panic!("{}", e));
+        Day {
+            times: times.iter().map(|t| t.to_string()).collect(),
+            records: records.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    fn part1(&self) -> u64 {
+        self.times
+            .iter()
+            .zip(&self.records)
+            .map(|(t, r)| ways(number(t), number(r)))
+            .product()
+    }
+
+    fn part2(&self) -> u64 {
+        // the spaces were only bad kerning
+        ways(number(&self.times.concat()), number(&self.records.concat()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    #[test]
+    fn test_ways() {
+        assert_eq!(4, ways(7, 9));
+        assert_eq!(8, ways(15, 40));
+        assert_eq!(9, ways(30, 200));
+        assert_eq!(0, ways(4, 4));
+        assert_eq!(1, ways(4, 3));
+    }
+
+    #[test]
+    fn test_example() {
+        let day = Day::parse(EXAMPLE);
+        assert_eq!(288, day.part1());
+        assert_eq!(71503, day.part2());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Err("expected \"Distance:\": Record: 9".to_string()),
+            races("Time: 7\nRecord: 9")
+        );
+        assert_eq!(
+            Err("every race needs a time and a distance".to_string()),
+            races("Time: 7 8\nDistance: 9")
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::solution::Solution;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -187,14 +188,28 @@ impl Hand {
         h
     }
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/7.txt").unwrap();
-    let mut g = Game::new(&data, false);
-    let w = &g.get_winnings();
-    println!("regular: {}", w);
-    let mut g = Game::new(&data, true);
-    let w = &g.get_winnings();
-    println!("joker: {}", w);
+
+pub struct Day {
+    hands: String,
+}
+
+impl Solution for Day {
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            hands: input.to_string(),
+        }
+    }
+
+    fn part1(&self) -> u64 {
+        Game::new(&self.hands, false).get_winnings()
+    }
+
+    fn part2(&self) -> u64 {
+        Game::new(&self.hands, true).get_winnings()
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,5 @@
+use crate::solution::Solution;
 use std::{collections::HashMap, str::Lines};
-#[macro_use]
-extern crate lazy_static;
 use regex::Regex;
 
 fn make_nodes(lines: Lines) -> HashMap<String, (String, String)> {
@@ -55,10 +54,13 @@ fn is_end(n: &str) -> bool {
     &n[2..=2] == "Z"
 }
 fn get_starts(n: Vec<&String>) -> Vec<String> {
-    n.iter()
+    let mut starts: Vec<String> = n
+        .iter()
         .filter(|s| is_start(s))
         .map(|s| s.to_string())
-        .collect()
+        .collect();
+    starts.sort();
+    starts
 }
 fn all_end(n: &[String]) -> bool {
     n.iter().all(|s| is_end(s))
@@ -156,28 +158,41 @@ fn lcm_many(n: &[u64]) -> u64 {
     }
     r
 }
-fn main() {
-    let data = std::fs::read_to_string("2023/8.txt").unwrap();
-    let mut lines = data.lines().into_iter();
-    let path = lines.next().unwrap();
-    let _ = lines.next();
-
-    let nodes = make_nodes(lines);
-    let result = walk(path, nodes);
-    println!("single: {}", result);
-
-    let data = std::fs::read_to_string("2023/8.txt").unwrap();
-    let mut lines = data.lines().into_iter();
-    let path = lines.next().unwrap();
-    let _ = lines.next();
-    let nodes = make_nodes(lines);
-    let result = all_path_lengths(path, nodes);
-    println!("paths: {:?}", result);
-    let gcd = gcd_many(&result);
-    println!("gcd: {:?}", gcd);
-    let lcm = lcm_many(&result);
-    println!("lcm: {:?}", lcm);
+
+pub struct Day {
+    network: String,
+}
+
+impl Day {
+    fn read(&self) -> (&str, HashMap<String, (String, String)>) {
+        let mut lines = self.network.lines();
+        let path = lines.next().unwrap();
+        let _ = lines.next();
+        (path, make_nodes(lines))
+    }
+}
+
+impl Solution for Day {
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Self {
+        Day {
+            network: input.to_string(),
+        }
+    }
+
+    fn part1(&self) -> u32 {
+        let (path, nodes) = self.read();
+        walk(path, nodes)
+    }
+
+    fn part2(&self) -> u64 {
+        let (path, nodes) = self.read();
+        lcm_many(&all_path_lengths(path, nodes))
+    }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
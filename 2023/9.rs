@@ -0,0 +1,71 @@
+use crate::solution::Solution;
+
+/// The next value in the sequence, found by taking differences until they
+/// are all the same and adding the last of each back up.
+fn extrapolate(values: &[i64]) -> i64 {
+    if values.iter().all(|v| *v == values[0]) {
+        return values[0];
+    }
+    let differences: Vec<i64> = values.windows(2).map(|w| w[1] - w[0]).collect();
+    values[values.len() - 1] + extrapolate(&differences)
+}
+
+/// The value before the first, which is the next one going backwards.
+fn extrapolate_back(values: &[i64]) -> i64 {
+    let reversed: Vec<i64> = values.iter().rev().copied().collect();
+    extrapolate(&reversed)
+}
+
+pub struct Day {
+    sequences: Vec<Vec<i64>>,
+}
+
+impl Solution for Day {
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Self {
+        let sequences = input
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| {
+                l.split_whitespace()
+                    .map(|n| n.parse().unwrap_or_else(|_| panic!("not a number: {}", n)))
+                    .collect()
+            })
+            .collect();
+        Day { sequences }
+    }
+
+    fn part1(&self) -> i64 {
+        self.sequences.iter().map(|s| extrapolate(s)).sum()
+    }
+
+    fn part2(&self) -> i64 {
+        self.sequences.iter().map(|s| extrapolate_back(s)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    #[test]
+    fn test_extrapolate() {
+        assert_eq!(18, extrapolate(&[0, 3, 6, 9, 12, 15]));
+        assert_eq!(68, extrapolate(&[10, 13, 16, 21, 30, 45]));
+        assert_eq!(5, extrapolate_back(&[10, 13, 16, 21, 30, 45]));
+        assert_eq!(2, extrapolate(&[2, 2, 2]));
+    }
+
+    #[test]
+    fn test_example() {
+        let day = Day::parse(EXAMPLE);
+        assert_eq!(114, day.part1());
+        assert_eq!(2, day.part2());
+    }
+}
@@ -0,0 +1,459 @@
+//! Assembunny, the little assembly language of 2016 days 12, 23 and 25.
+//!
+//! The bunnies do their arithmetic with loops of `inc`, `dec` and `jnz`.
+//! Peephole passes spot those loops and fuse them into a single add or
+//! multiply, and since `tgl` can rewrite the code under a fused loop, every
+//! toggle throws the fusions away and looks for them again.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arg {
+    Reg(usize),
+    Val(i64),
+}
+
+impl fmt::Display for Arg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Arg::Reg(r) => write!(f, "{}", (b'a' + *r as u8) as char),
+            Arg::Val(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl FromStr for Arg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Arg, String> {
+        match s {
+            "a" => Ok(Arg::Reg(0)),
+            "b" => Ok(Arg::Reg(1)),
+            "c" => Ok(Arg::Reg(2)),
+            "d" => Ok(Arg::Reg(3)),
+            _ => s
+                .parse()
+                .map(Arg::Val)
+                .map_err(|_| format!("not a register or number: {}", s)),
+        }
+    }
+}
+
+/// An instruction as written. Toggling can produce nonsense like `cpy 1 2`
+/// or `inc 5`; those are skipped when run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Instr {
+    Cpy(Arg, Arg),
+    Inc(Arg),
+    Dec(Arg),
+    Jnz(Arg, Arg),
+    Tgl(Arg),
+    Out(Arg),
+}
+
+impl Instr {
+    /// What `tgl` turns the instruction into.
+    pub fn toggled(self) -> Instr {
+        match self {
+            Instr::Inc(x) => Instr::Dec(x),
+            Instr::Dec(x) | Instr::Tgl(x) | Instr::Out(x) => Instr::Inc(x),
+            Instr::Jnz(x, y) => Instr::Cpy(x, y),
+            Instr::Cpy(x, y) => Instr::Jnz(x, y),
+        }
+    }
+}
+
+impl fmt::Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Instr::Cpy(x, y) => write!(f, "cpy {} {}", x, y),
+            Instr::Inc(x) => write!(f, "inc {}", x),
+            Instr::Dec(x) => write!(f, "dec {}", x),
+            Instr::Jnz(x, y) => write!(f, "jnz {} {}", x, y),
+            Instr::Tgl(x) => write!(f, "tgl {}", x),
+            Instr::Out(x) => write!(f, "out {}", x),
+        }
+    }
+}
+
+impl FromStr for Instr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Instr, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let arg = |ix: usize| -> Result<Arg, String> {
+            words
+                .get(ix)
+                .ok_or_else(|| format!("missing argument: {}", s))?
+                .parse()
+        };
+        let (instr, count) = match words.first() {
+            Some(&"cpy") => (Instr::Cpy(arg(1)?, arg(2)?), 3),
+            Some(&"inc") => (Instr::Inc(arg(1)?), 2),
+            Some(&"dec") => (Instr::Dec(arg(1)?), 2),
+            Some(&"jnz") => (Instr::Jnz(arg(1)?, arg(2)?), 3),
+            Some(&"tgl") => (Instr::Tgl(arg(1)?), 2),
+            Some(&"out") => (Instr::Out(arg(1)?), 2),
+            _ => return Err(format!("unknown instruction: {}", s)),
+        };
+        if words.len() != count {
+            return Err(format!("wrong number of arguments: {}", s));
+        }
+        Ok(instr)
+    }
+}
+
+/// Parses a program, one instruction per line.
+pub fn parse(program: &str) -> Vec<Instr> {
+    program
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.parse().unwrap_or_else(|e| panic!("{}", e)))
+        .collect()
+}
+
+/// A loop replaced by the arithmetic it works out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fused {
+    /// `to += from; from = 0`.
+    Add { to: usize, from: usize },
+    /// `to += factor * count; scratch = 0; count = 0`.
+    Mul {
+        to: usize,
+        factor: Arg,
+        scratch: usize,
+        count: usize,
+    },
+}
+
+impl Fused {
+    /// Number of instructions the loop takes up.
+    pub fn size(&self) -> usize {
+        match self {
+            Fused::Add { .. } => 3,
+            Fused::Mul { .. } => 6,
+        }
+    }
+}
+
+/// Looks for a loop starting at `pc` that it knows how to fuse.
+pub type Pass = fn(&[Instr], usize) -> Option<Fused>;
+
+/// `inc to, dec from, jnz from -2`, or with the first two swapped.
+pub fn add_loop(code: &[Instr], pc: usize) -> Option<Fused> {
+    let (to, from) = match code.get(pc..pc + 3)? {
+        [Instr::Inc(Arg::Reg(to)), Instr::Dec(Arg::Reg(from)), Instr::Jnz(Arg::Reg(test), Arg::Val(-2))]
+        | [Instr::Dec(Arg::Reg(from)), Instr::Inc(Arg::Reg(to)), Instr::Jnz(Arg::Reg(test), Arg::Val(-2))]
+            if test == from =>
+        {
+            (*to, *from)
+        }
+        _ => return None,
+    };
+    (to != from).then_some(Fused::Add { to, from })
+}
+
+/// `cpy factor scratch`, an add loop from scratch, then `dec count, jnz count -5`.
+pub fn mul_loop(code: &[Instr], pc: usize) -> Option<Fused> {
+    let [Instr::Cpy(factor, Arg::Reg(scratch)), .., Instr::Dec(Arg::Reg(count)), Instr::Jnz(Arg::Reg(test), Arg::Val(-5))] =
+        *code.get(pc..pc + 6)?
+    else {
+        return None;
+    };
+    let Some(Fused::Add { to, from }) = add_loop(code, pc + 1) else {
+        return None;
+    };
+    let distinct = from == scratch && test == count && to != count && scratch != count;
+    let reads_loop = matches!(factor, Arg::Reg(r) if [to, scratch, count].contains(&r));
+    (distinct && !reads_loop).then_some(Fused::Mul {
+        to,
+        factor,
+        scratch,
+        count,
+    })
+}
+
+/// The passes `Machine::optimized` uses, longest loops first.
+pub const PASSES: &[Pass] = &[mul_loop, add_loop];
+
+/// What one step of the machine did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Ran,
+    Out(i64),
+    Halted,
+}
+
+#[derive(Clone)]
+pub struct Machine {
+    pub code: Vec<Instr>,
+    pub reg: [i64; 4],
+    pub pc: usize,
+    /// Instructions executed so far, a fused loop counting as one.
+    pub steps: usize,
+    passes: Vec<Pass>,
+    fused: Vec<Option<Fused>>,
+}
+
+impl Machine {
+    /// A machine running the program exactly as written.
+    pub fn new(program: &str) -> Machine {
+        let code = parse(program);
+        Machine {
+            fused: vec![None; code.len()],
+            code,
+            reg: [0; 4],
+            pc: 0,
+            steps: 0,
+            passes: Vec::new(),
+        }
+    }
+
+    /// A machine fusing every loop the standard passes recognise.
+    pub fn optimized(program: &str) -> Machine {
+        Machine::new(program).with_passes(PASSES)
+    }
+
+    /// Replaces the peephole passes, trying them in order at each pc.
+    pub fn with_passes(mut self, passes: &[Pass]) -> Machine {
+        self.passes = passes.to_vec();
+        self.fuse();
+        self
+    }
+
+    /// The fused loop starting at `pc`, if there is one.
+    pub fn fused(&self, pc: usize) -> Option<Fused> {
+        self.fused.get(pc).copied().flatten()
+    }
+
+    fn fuse(&mut self) {
+        self.fused = (0..self.code.len())
+            .map(|pc| self.passes.iter().find_map(|pass| pass(&self.code, pc)))
+            .collect();
+    }
+
+    fn value(&self, arg: Arg) -> i64 {
+        match arg {
+            Arg::Reg(r) => self.reg[r],
+            Arg::Val(v) => v,
+        }
+    }
+
+    pub fn halted(&self) -> bool {
+        self.pc >= self.code.len()
+    }
+
+    /// Runs the fused loop at `pc` if the registers are such that it would
+    /// finish. Counters starting at zero or below would have the real loop
+    /// wrap around, so those are left to run the slow way.
+    fn run_fused(&mut self) -> bool {
+        let Some(fused) = self.fused(self.pc) else {
+            return false;
+        };
+        match fused {
+            Fused::Add { to, from } if self.reg[from] > 0 => {
+                self.reg[to] += self.reg[from];
+                self.reg[from] = 0;
+            }
+            Fused::Mul {
+                to,
+                factor,
+                scratch,
+                count,
+            } if self.value(factor) > 0 && self.reg[count] > 0 => {
+                self.reg[to] += self.value(factor) * self.reg[count];
+                self.reg[scratch] = 0;
+                self.reg[count] = 0;
+            }
+            _ => return false,
+        }
+        self.pc += fused.size();
+        true
+    }
+
+    pub fn step(&mut self) -> Step {
+        if self.halted() {
+            return Step::Halted;
+        }
+        self.steps += 1;
+        if self.run_fused() {
+            return Step::Ran;
+        }
+        let mut next = self.pc as i64 + 1;
+        let mut step = Step::Ran;
+        match self.code[self.pc] {
+            Instr::Cpy(x, Arg::Reg(r)) => self.reg[r] = self.value(x),
+            Instr::Inc(Arg::Reg(r)) => self.reg[r] += 1,
+            Instr::Dec(Arg::Reg(r)) => self.reg[r] -= 1,
+            Instr::Jnz(x, y) if self.value(x) != 0 => next = self.pc as i64 + self.value(y),
+            Instr::Tgl(x) => {
+                let target = self.pc as i64 + self.value(x);
+                if let Some(instr) = usize::try_from(target)
+                    .ok()
+                    .and_then(|t| self.code.get_mut(t))
+                {
+                    *instr = instr.toggled();
+                    self.fuse();
+                }
+            }
+            Instr::Out(x) => step = Step::Out(self.value(x)),
+            _ => {}
+        }
+        self.pc = usize::try_from(next).unwrap_or(usize::MAX);
+        step
+    }
+
+    /// Runs until the program outputs something or halts.
+    pub fn next_output(&mut self) -> Option<i64> {
+        loop {
+            match self.step() {
+                Step::Ran => {}
+                Step::Out(value) => return Some(value),
+                Step::Halted => return None,
+            }
+        }
+    }
+
+    /// Runs until the program halts or `sink` returns false, handing it
+    /// every value output along with the machine as it is right after the `out`.
+    pub fn run_with(&mut self, mut sink: impl FnMut(&Machine, i64) -> bool) {
+        while let Some(value) = self.next_output() {
+            if !sink(self, value) {
+                return;
+            }
+        }
+    }
+
+    /// Runs until the program halts, returning everything it output.
+    pub fn run(&mut self) -> Vec<i64> {
+        let mut output = Vec::new();
+        self.run_with(|_, value| {
+            output.push(value);
+            true
+        });
+        output
+    }
+
+    /// Whether the program outputs 0, 1, 0, 1... forever.
+    ///
+    /// The signal is checked as it comes out, noting the whole machine
+    /// along with which bit is due next. Coming back to a noted state means
+    /// the run from there on repeats exactly, so the signal never breaks.
+    /// A program that loops without ever outputting keeps this running.
+    pub fn emits_clock_signal(&self) -> bool {
+        let mut seen = HashSet::new();
+        let mut expected = 0;
+        let mut repeats = false;
+        self.clone().run_with(|m, value| {
+            if value != expected {
+                return false;
+            }
+            expected = 1 - expected;
+            repeats = !seen.insert((m.reg, m.pc, m.code.clone(), expected));
+            !repeats
+        });
+        repeats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a = 5 * 4 + 3, with a mul loop wrapping an add loop
+    const MUL: &str = "cpy 3 a\ncpy 4 d\ncpy 5 b\ninc a\ndec b\njnz b -2\ndec d\njnz d -5";
+
+    #[test]
+    fn test_parse_round_trip() {
+        let program = "cpy 41 a\ninc a\ndec b\njnz c -2\ntgl d\nout 1\ncpy -3 b";
+        let text: Vec<String> = parse(program).iter().map(|i| i.to_string()).collect();
+        assert_eq!(program, text.join("\n"));
+        assert!("cpy 1".parse::<Instr>().is_err());
+        assert!("inc e".parse::<Instr>().is_err());
+    }
+
+    #[test]
+    fn test_finds_loops() {
+        let m = Machine::optimized(MUL);
+        assert_eq!(
+            Some(Fused::Mul {
+                to: 0,
+                factor: Arg::Val(5),
+                scratch: 1,
+                count: 3
+            }),
+            m.fused(2)
+        );
+        assert_eq!(Some(Fused::Add { to: 0, from: 1 }), m.fused(3));
+        assert_eq!(None, m.fused(0));
+        assert_eq!(None, Machine::new(MUL).fused(2));
+    }
+
+    #[test]
+    fn test_fused_runs_like_plain() {
+        let mut plain = Machine::new(MUL);
+        plain.run();
+        let mut fused = Machine::optimized(MUL);
+        fused.run();
+        assert_eq!([23, 0, 0, 0], plain.reg);
+        assert_eq!(plain.reg, fused.reg);
+        assert_eq!(3, fused.steps);
+        assert!(plain.steps > 60);
+    }
+
+    #[test]
+    fn test_tgl_example() {
+        let mut m = Machine::optimized("cpy 2 a\ntgl a\ntgl a\ntgl a\ncpy 1 a\ndec a\ndec a");
+        m.run();
+        assert_eq!(3, m.reg[0]);
+    }
+
+    #[test]
+    fn test_tgl_breaks_fused_loop() {
+        // the toggle turns the inc of the add loop into a dec before it runs
+        let program = "cpy 2 a\ntgl a\ncpy 3 b\ninc c\ndec b\njnz b -2";
+        let mut m = Machine::optimized(program);
+        assert_eq!(Some(Fused::Add { to: 2, from: 1 }), m.fused(3));
+        m.run();
+        assert_eq!(None, m.fused(3));
+        assert_eq!(-3, m.reg[2]);
+    }
+
+    #[test]
+    fn test_tgl_forms_fused_loop() {
+        let program = "cpy 2 a\ntgl a\ncpy 3 b\ndec c\ndec b\njnz b -2";
+        let mut m = Machine::optimized(program);
+        assert_eq!(None, m.fused(3));
+        m.run();
+        assert_eq!(Some(Fused::Add { to: 2, from: 1 }), m.fused(3));
+        assert_eq!(3, m.reg[2]);
+    }
+
+    #[test]
+    fn test_clock_signal() {
+        // outs the bits of a least significant first, starting over from
+        // the beginning once a runs out
+        let program = "cpy a d\ncpy d b\ncpy b c\ncpy 0 b\ndec c\njnz c 2\njnz 1 6\n\
+                       dec c\ninc b\njnz c -5\nout 0\njnz 1 2\nout 1\njnz b -11\njnz 1 -13";
+        let signal = |a: i64| {
+            let mut m = Machine::optimized(program);
+            m.reg[0] = a;
+            m.emits_clock_signal()
+        };
+        assert_eq!(
+            vec![2, 10, 42],
+            (1..50).filter(|a| signal(*a)).collect::<Vec<i64>>()
+        );
+        assert!(!Machine::new("out 0\nout 1").emits_clock_signal());
+    }
+
+    #[test]
+    fn test_out() {
+        let mut m = Machine::new("cpy 2 a\nout a\ndec a\njnz a -2\nout 7");
+        assert_eq!(Some(2), m.next_output());
+        assert_eq!(vec![1, 7], m.run());
+        assert_eq!(Step::Halted, m.step());
+    }
+}
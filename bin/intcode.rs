@@ -0,0 +1,72 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufReader};
+use std::process;
+
+use aoc::intcode::debugger::Debugger;
+use aoc::intcode::disasm;
+use aoc::intcode::trace::{self, Recorder};
+use aoc::intcode::{Prg, Queue};
+
+const USAGE: &str = "usage: intcode disasm <program>
+       intcode debug <program> [script]
+       intcode trace <program> [input]...
+       intcode replay <program> <trace>";
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| fail(&format!("could not read {}: {}", path, e)))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, path) = match args.as_slice() {
+        [command, path, ..] => (command.as_str(), path.as_str()),
+        _ => fail("missing command or program"),
+    };
+    let mut prg = Prg::new(&read(path));
+
+    match (command, args.get(2)) {
+        ("disasm", None) => println!("{}", disasm::listing(&prg)),
+        ("debug", script) => {
+            let mut debugger = Debugger::new(prg);
+            let mut stdout = io::stdout();
+            let result = match script {
+                Some(script) => {
+                    let file = fs::File::open(script)
+                        .unwrap_or_else(|e| fail(&format!("could not read {}: {}", script, e)));
+                    debugger.repl(BufReader::new(file), &mut stdout, false)
+                }
+                None => debugger.repl(io::stdin().lock(), &mut stdout, true),
+            };
+            if let Err(e) = result {
+                fail(&e.to_string());
+            }
+        }
+        ("trace", _) => {
+            let input = args[2..]
+                .iter()
+                .map(|a| {
+                    a.parse()
+                        .unwrap_or_else(|_| fail(&format!("not a number: {}", a)))
+                })
+                .collect::<Vec<i64>>();
+            let mut recorder = Recorder::new(io::BufWriter::new(io::stdout()));
+            if let Err(e) = prg.run_traced(&mut Queue::new(&input), false, &mut recorder) {
+                fail(&e);
+            }
+        }
+        ("replay", Some(path)) => match trace::replay(&mut prg, &read(path)) {
+            Ok(stop) => println!("replay matches the trace, ended {:?}", stop),
+            Err(e) => {
+                eprintln!("replay differs from the trace: {}", e);
+                process::exit(1);
+            }
+        },
+        _ => fail(&format!("unknown command: {}", command)),
+    }
+}
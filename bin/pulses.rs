@@ -0,0 +1,64 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use aoc::y2023::day20;
+
+const USAGE: &str = "usage: pulses dot <input> [presses]
+       pulses log <input> <presses>
+       pulses first <input> <module> high|low [max presses]
+       pulses flip-flops <input> <presses>";
+
+/// How many presses `first` tries unless told otherwise.
+const MAX_PRESSES: u64 = 100_000;
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| fail(&format!("could not read {}: {}", path, e)))
+}
+
+fn number(arg: &str) -> u64 {
+    arg.parse()
+        .unwrap_or_else(|_| fail(&format!("not a number: {}", arg)))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, path) = match args.as_slice() {
+        [command, path, ..] => (command.as_str(), path.as_str()),
+        _ => fail("missing command or input"),
+    };
+    let input = read(path);
+    let rest: Vec<&str> = args[2..].iter().map(|a| a.as_str()).collect();
+
+    match (command, rest.as_slice()) {
+        ("dot", []) => print!("{}", day20::dot(&input, None)),
+        ("dot", [presses]) => print!("{}", day20::dot(&input, Some(number(presses)))),
+        ("log", [presses]) => print!("{}", day20::pulse_log(&input, number(presses))),
+        ("first", [module, signal, max @ ..]) if max.len() <= 1 => {
+            let high = match *signal {
+                "high" => true,
+                "low" => false,
+                _ => fail(&format!("expected high or low, not {}", signal)),
+            };
+            let max = max.first().map_or(MAX_PRESSES, |m| number(m));
+            match day20::first_sent(&input, module, high, max) {
+                Some((press, step)) => println!("press {} step {}", press, step),
+                None => {
+                    println!("{} sent no {} pulse in {} presses", module, signal, max);
+                    process::exit(1);
+                }
+            }
+        }
+        ("flip-flops", [presses]) => {
+            for (name, on) in day20::flip_flops_after(&input, number(presses)) {
+                println!("{} {}", name, if on { "on" } else { "off" });
+            }
+        }
+        _ => fail(&format!("unknown command: {}", args.join(" "))),
+    }
+}
@@ -0,0 +1,32 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use aoc::y2015::day22;
+
+const USAGE: &str = "usage: wizard replay <input> [--hard]";
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| fail(&format!("could not read {}: {}", path, e)))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, path) = match args.as_slice() {
+        [command, path, ..] => (command.as_str(), path.as_str()),
+        _ => fail("missing command or input"),
+    };
+    let input = read(path);
+    let rest: Vec<&str> = args[2..].iter().map(|a| a.as_str()).collect();
+
+    match (command, rest.as_slice()) {
+        ("replay", []) => print!("{}", day22::replay(&input, false)),
+        ("replay", ["--hard"]) => print!("{}", day22::replay(&input, true)),
+        _ => fail(&format!("unknown command: {}", args.join(" "))),
+    }
+}
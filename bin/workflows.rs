@@ -0,0 +1,60 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use aoc::y2023::day19;
+
+const USAGE: &str = "usage: workflows count <input> [<from>-<to>...]
+       workflows optimize <input> [<from>-<to>...]
+
+Bounds go x, m, a, s in order; categories left out take 1-4000.";
+
+/// The values each category takes unless told otherwise.
+const RATINGS: (u32, u32) = (1, 4000);
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| fail(&format!("could not read {}: {}", path, e)))
+}
+
+fn bound(arg: &str) -> (u32, u32) {
+    let range = arg.split_once('-').and_then(|(from, to)| {
+        let (from, to): (u32, u32) = (from.parse().ok()?, to.parse().ok()?);
+        (from <= to).then_some((from, to))
+    });
+    range.unwrap_or_else(|| fail(&format!("not a range: {}", arg)))
+}
+
+fn bounds(args: &[&str]) -> Vec<(u32, u32)> {
+    let mut bounds: Vec<(u32, u32)> = args.iter().map(|a| bound(a)).collect();
+    if bounds.len() < 4 {
+        bounds.resize(4, RATINGS);
+    }
+    bounds
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, path) = match args.as_slice() {
+        [command, path, ..] => (command.as_str(), path.as_str()),
+        _ => fail("missing command or input"),
+    };
+    let input = read(path);
+    let rest: Vec<&str> = args[2..].iter().map(|a| a.as_str()).collect();
+
+    match command {
+        "count" => println!("{}", day19::accepted_combinations(&input, &bounds(&rest))),
+        "optimize" => match day19::optimize(&input, &bounds(&rest)) {
+            Ok(system) => print!("{}", system),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        _ => fail(&format!("unknown command: {}", args.join(" "))),
+    }
+}
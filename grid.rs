@@ -0,0 +1,317 @@
+//! A rectangular grid of cells, for the many puzzles played out on a map.
+//!
+//! Positions are `(x, y)` with `x` the column and `y` the row, counting
+//! from the top left. Cells are stored row by row.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Steps to the four neighbours: up, right, down and left.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Steps to all eight neighbours, row by row.
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid from cells listed row by row.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't make rows of {}",
+            cells.len(),
+            width
+        );
+        Grid {
+            height: cells.len() / width,
+            width,
+            cells,
+        }
+    }
+
+    /// Reads a grid with one row per line, turning each character into a
+    /// cell. Blank lines are skipped; rows of different lengths panic.
+    pub fn parse_with(s: &str, mut cell: impl FnMut(char) -> T) -> Grid<T> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        for line in s.lines().filter(|l| !l.is_empty()) {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let row = cells.len() - before;
+            if *width.get_or_insert(row) != row {
+                panic!(
+                    "row of {} cells in a grid {} wide: {}",
+                    row,
+                    width.unwrap(),
+                    line
+                );
+            }
+        }
+        Grid::from_cells(width.expect("empty grid"), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The position as unsigned coordinates, if it lies on the grid.
+    pub fn inside(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (a, b) = (self.offset(a), self.offset(b));
+        self.cells.swap(a, b);
+    }
+
+    fn offset(&self, (x, y): (usize, usize)) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        y * self.width + x
+    }
+
+    /// All cells, row by row.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |ix| (ix % width, ix / width))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, whose cell matches.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.enumerate().find(|(_, c)| pred(c)).map(|(p, _)| p)
+    }
+
+    fn around(
+        &self,
+        x: usize,
+        y: usize,
+        steps: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        steps
+            .iter()
+            .filter_map(move |(dx, dy)| self.inside(x as isize + dx, y as isize + dy))
+    }
+
+    /// The up to four positions next to `(x, y)`: up, right, down and left.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.around(x, y, &ORTHOGONAL)
+    }
+
+    /// The up to eight positions around `(x, y)`, diagonals included.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.around(x, y, &ALL_AROUND)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, T> {
+        self.cells.chunks_exact(self.width)
+    }
+
+    /// The cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} of a grid {} wide", x, self.width);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// A grid of the same shape with every cell run through `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_cells(self.width, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid::from_cells(width, vec![fill; width * height])
+    }
+
+    /// Builds a `width` wide grid by looking up each new position in this one.
+    fn remap(&self, width: usize, from: impl Fn(usize, usize) -> (usize, usize)) -> Grid<T> {
+        let height = self.cells.len() / width;
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[from(x, y)].clone())
+            .collect();
+        Grid::from_cells(width, cells)
+    }
+
+    /// Rows become columns, mirroring across the top left to bottom right diagonal.
+    pub fn transposed(&self) -> Grid<T> {
+        self.remap(self.height, |x, y| (y, x))
+    }
+
+    /// Turned a quarter clockwise, so the left column becomes the top row.
+    pub fn rotated_cw(&self) -> Grid<T> {
+        let height = self.height;
+        self.remap(height, |x, y| (y, height - 1 - x))
+    }
+
+    /// Turned a quarter counterclockwise, so the top row becomes the left column.
+    pub fn rotated_ccw(&self) -> Grid<T> {
+        let width = self.width;
+        self.remap(self.height, |x, y| (width - 1 - y, x))
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(s: &str) -> Grid<char> {
+        Grid::parse_with(s, |c| c)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        &self.cells[self.offset(pos)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let offset = self.offset(pos);
+        &mut self.cells[offset]
+    }
+}
+
+/// One line per row, without a newline after the last.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "ab.\n.c#\n";
+
+    #[test]
+    fn test_parse_and_display() {
+        let g = Grid::parse(SAMPLE);
+        assert_eq!((3, 2), (g.width(), g.height()));
+        assert_eq!('c', g[(1, 1)]);
+        assert_eq!(Some(&'#'), g.get(2, 1));
+        assert_eq!(None, g.get(3, 0));
+        assert_eq!(None, g.get(0, 2));
+        assert_eq!(SAMPLE.trim(), g.to_string());
+        assert_eq!(Some((1, 1)), g.find(|c| *c == 'c'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_parse_ragged() {
+        Grid::parse("ab\nc");
+    }
+
+    #[test]
+    fn test_inside() {
+        let g = Grid::new(3, 2, 0);
+        assert_eq!(Some((2, 1)), g.inside(2, 1));
+        assert_eq!(None, g.inside(-1, 0));
+        assert_eq!(None, g.inside(0, 2));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = Grid::new(3, 3, 0);
+        assert_eq!(
+            vec![(1, 0), (2, 1), (1, 2), (0, 1)],
+            g.neighbours(1, 1).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![(1, 0), (0, 1)], g.neighbours(0, 0).collect::<Vec<_>>());
+        assert_eq!(8, g.neighbours8(1, 1).count());
+        assert_eq!(
+            vec![(1, 1), (2, 1), (1, 2)],
+            g.neighbours8(2, 2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut g = Grid::parse(SAMPLE);
+        assert_eq!(&['.', 'c', '#'], g.row(1));
+        assert_eq!(vec!['b', 'c'], g.column(1).copied().collect::<Vec<_>>());
+        g.row_mut(0).rotate_right(1);
+        assert_eq!(".ab\n.c#", g.to_string());
+        assert_eq!(2, g.rows().count());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let g = Grid::parse(SAMPLE);
+        assert_eq!(".a\ncb\n#.", g.rotated_cw().to_string());
+        assert_eq!(".#\nbc\na.", g.rotated_ccw().to_string());
+        assert_eq!("a.\nbc\n.#", g.transposed().to_string());
+        assert_eq!(g, g.rotated_cw().rotated_ccw());
+        let g4 = g.rotated_cw().rotated_cw().rotated_cw().rotated_cw();
+        assert_eq!(g, g4);
+    }
+}
@@ -0,0 +1,281 @@
+//! The Intcode computer from 2019, shared by every day that runs an Intcode program.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{Receiver, Sender};
+
+pub mod debugger;
+pub mod disasm;
+pub mod network;
+pub mod trace;
+
+const SEGMENT: usize = 1024;
+
+/// Program memory. Addresses past the end of the program are zero until
+/// written, and are allocated in segments as they are first touched.
+#[derive(Clone)]
+pub struct MemBank {
+    prg: Vec<i64>,
+    data: HashMap<usize, Vec<i64>>,
+}
+
+impl MemBank {
+    pub fn new(prg: Vec<i64>) -> MemBank {
+        MemBank {
+            prg,
+            data: HashMap::new(),
+        }
+    }
+
+    /// Length of the loaded program, not counting memory written past it.
+    pub fn program_len(&self) -> usize {
+        self.prg.len()
+    }
+
+    pub fn read(&self, pointer: usize) -> i64 {
+        if pointer < self.prg.len() {
+            return self.prg[pointer];
+        }
+        match self.data.get(&(pointer / SEGMENT)) {
+            Some(segment) => segment[pointer % SEGMENT],
+            None => 0,
+        }
+    }
+
+    pub fn write(&mut self, pointer: usize, value: i64) {
+        if pointer < self.prg.len() {
+            self.prg[pointer] = value;
+        } else {
+            self.data
+                .entry(pointer / SEGMENT)
+                .or_insert_with(|| vec![0; SEGMENT])[pointer % SEGMENT] = value;
+        }
+    }
+}
+
+/// Where a program takes its input from and sends its output to.
+pub trait Io {
+    /// The next input value, or `None` if there is nothing to read.
+    fn read(&mut self) -> Option<i64>;
+    fn write(&mut self, value: i64);
+}
+
+/// Input and output kept in queues, for driving a program from the same thread.
+#[derive(Default)]
+pub struct Queue {
+    pub input: VecDeque<i64>,
+    pub output: VecDeque<i64>,
+}
+
+impl Queue {
+    pub fn new(input: &[i64]) -> Queue {
+        Queue {
+            input: input.iter().copied().collect(),
+            output: VecDeque::new(),
+        }
+    }
+}
+
+impl Io for Queue {
+    fn read(&mut self) -> Option<i64> {
+        self.input.pop_front()
+    }
+
+    fn write(&mut self, value: i64) {
+        self.output.push_back(value);
+    }
+}
+
+/// Input and output over channels, for running a program on its own thread.
+/// Reads block until a value arrives or the sending side hangs up.
+pub struct Channel {
+    pub input: Receiver<i64>,
+    pub output: Sender<i64>,
+}
+
+impl Io for Channel {
+    fn read(&mut self) -> Option<i64> {
+        self.input.recv().ok()
+    }
+
+    fn write(&mut self, value: i64) {
+        if self.output.send(value).is_err() {
+            panic!("output channel closed");
+        }
+    }
+}
+
+/// Why a program stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// Reached opcode 99.
+    Halted,
+    /// Wrote an output while running with `halt_on_write`.
+    Wrote,
+    /// Needed input that wasn't there; running again retries the read.
+    Blocked,
+}
+
+#[derive(Clone)]
+pub struct Prg {
+    pub data: MemBank,
+    pub pc: usize,
+    pub relative_base: i64,
+}
+
+impl Prg {
+    pub fn new(s: &str) -> Prg {
+        let data = s.trim().split(',').map(|a| a.parse().unwrap()).collect();
+        Prg {
+            data: MemBank::new(data),
+            pc: 0,
+            relative_base: 0,
+        }
+    }
+
+    fn mode_for(op: i64, ix: u32) -> i64 {
+        (op / 10i64.pow(1 + ix)) % 10
+    }
+
+    fn get_mempointer(&self, op: i64, ix: u32) -> usize {
+        let param_pointer = self.pc + ix as usize;
+        let pointer = match Prg::mode_for(op, ix) {
+            0 => self.data.read(param_pointer),
+            1 => param_pointer as i64,
+            2 => self.data.read(param_pointer) + self.relative_base,
+            mode => panic!("invalid mode {} at {}", mode, self.pc),
+        };
+        usize::try_from(pointer)
+            .unwrap_or_else(|_| panic!("negative address {} at {}", pointer, self.pc))
+    }
+
+    fn get_param(&self, op: i64, ix: u32) -> i64 {
+        self.data.read(self.get_mempointer(op, ix))
+    }
+
+    fn set_param(&mut self, op: i64, ix: u32, value: i64) {
+        let pointer = self.get_mempointer(op, ix);
+        self.data.write(pointer, value);
+    }
+
+    /// Executes a single instruction, returning `None` if the program keeps going.
+    pub fn step(&mut self, io: &mut dyn Io) -> Option<Stop> {
+        let op = self.data.read(self.pc);
+        match op % 100 {
+            1 => {
+                let sum = self.get_param(op, 1) + self.get_param(op, 2);
+                self.set_param(op, 3, sum);
+                self.pc += 4;
+            }
+            2 => {
+                let product = self.get_param(op, 1) * self.get_param(op, 2);
+                self.set_param(op, 3, product);
+                self.pc += 4;
+            }
+            3 => {
+                let Some(value) = io.read() else {
+                    return Some(Stop::Blocked);
+                };
+                self.set_param(op, 1, value);
+                self.pc += 2;
+            }
+            4 => {
+                io.write(self.get_param(op, 1));
+                self.pc += 2;
+                return Some(Stop::Wrote);
+            }
+            5 | 6 => {
+                if (self.get_param(op, 1) != 0) == (op % 100 == 5) {
+                    self.pc = self.get_param(op, 2) as usize;
+                } else {
+                    self.pc += 3;
+                }
+            }
+            7 => {
+                let lt = self.get_param(op, 1) < self.get_param(op, 2);
+                self.set_param(op, 3, lt as i64);
+                self.pc += 4;
+            }
+            8 => {
+                let eq = self.get_param(op, 1) == self.get_param(op, 2);
+                self.set_param(op, 3, eq as i64);
+                self.pc += 4;
+            }
+            9 => {
+                self.relative_base += self.get_param(op, 1);
+                self.pc += 2;
+            }
+            99 => return Some(Stop::Halted),
+            _ => panic!("bad opcode {} at {}", op, self.pc),
+        }
+        None
+    }
+
+    /// Runs until the program halts or runs out of input, or after the
+    /// first output if `halt_on_write` is set.
+    pub fn run(&mut self, io: &mut dyn Io, halt_on_write: bool) -> Stop {
+        loop {
+            match self.step(io) {
+                None => {}
+                Some(Stop::Wrote) if !halt_on_write => {}
+                Some(stop) => return stop,
+            }
+        }
+    }
+
+    /// Runs the program on `input` until it stops, returning everything it wrote.
+    pub fn run_with(&mut self, input: &[i64]) -> Vec<i64> {
+        let mut queue = Queue::new(input);
+        self.run(&mut queue, false);
+        queue.output.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_mode() {
+        assert_eq!(0, Prg::mode_for(1002, 1));
+        assert_eq!(1, Prg::mode_for(1002, 2));
+        assert_eq!(0, Prg::mode_for(1002, 3));
+    }
+
+    #[test]
+    fn test_mempointer() {
+        let mut p = Prg::new("204,-7");
+        p.relative_base = 50;
+        assert_eq!(43, p.get_mempointer(204, 1));
+    }
+
+    #[test]
+    fn test_relative_base() {
+        let mut p = Prg::new("109,19,204,-34");
+        p.relative_base = 2000;
+        assert_eq!(None, p.step(&mut Queue::default()));
+        assert_eq!(2019, p.relative_base);
+        assert_eq!(2, p.pc);
+        assert_eq!(1985, p.get_mempointer(204, 1));
+    }
+
+    #[test]
+    fn test_mem() {
+        let mut p = Prg::new("109,19,204,-34");
+        p.data.write(10000, 1125899906842624);
+        assert_eq!(1125899906842624, p.data.read(10000));
+        assert_eq!(0, p.data.read(10001));
+        assert_eq!(0, p.data.read(50000));
+    }
+
+    #[test]
+    fn test_blocks_without_input() {
+        let mut p = Prg::new("3,9,4,9,3,9,4,9,99,0");
+        let mut q = Queue::new(&[5]);
+        assert_eq!(Stop::Blocked, p.run(&mut q, false));
+        assert_eq!(vec![5], Vec::from(q.output.clone()));
+        q.input.push_back(6);
+        assert_eq!(Stop::Wrote, p.run(&mut q, true));
+        assert_eq!(Stop::Halted, p.run(&mut q, true));
+        assert_eq!(vec![5, 6], Vec::from(q.output));
+    }
+}
@@ -0,0 +1,364 @@
+//! A step debugger for Intcode programs, driven through text commands so it
+//! works the same typed at a prompt or fed from a script.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use super::disasm::decode;
+use super::{Prg, Queue, Stop};
+
+const HELP: &str = "\
+step [n]          execute n instructions (default 1)
+continue          run until a breakpoint, watchpoint, halt or missing input
+break <pc>        stop before executing the instruction at pc
+unbreak <pc>      remove a breakpoint
+watch <addr>      stop after an instruction changes memory at addr
+unwatch <addr>    remove a watchpoint
+input <v>...      queue input values
+mem <addr> [n]    show n words of memory (default 1)
+dis [addr] [n]    disassemble n instructions from addr (default pc, 10)
+regs              show pc, relative base, io queues and the next instruction
+help              show this text
+quit              leave the debugger";
+
+/// Why the debugger handed control back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Executed what was asked without anything else happening.
+    Stepped,
+    /// Reached a breakpoint; the instruction there has not run yet.
+    Breakpoint(usize),
+    /// An instruction changed a watched address.
+    Watchpoint { address: usize, old: i64, new: i64 },
+    /// The program halted or is waiting for input.
+    Stopped(Stop),
+    /// The word at `pc` is not an instruction, so nothing ran.
+    Invalid { pc: usize, op: i64 },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Stepped => write!(f, "stepped"),
+            Event::Breakpoint(pc) => write!(f, "breakpoint at {}", pc),
+            Event::Watchpoint { address, old, new } => {
+                write!(f, "watchpoint {}: {} -> {}", address, old, new)
+            }
+            Event::Stopped(Stop::Blocked) => write!(f, "waiting for input"),
+            Event::Stopped(_) => write!(f, "halted"),
+            Event::Invalid { pc, op } => write!(f, "no instruction at {}, only data {}", pc, op),
+        }
+    }
+}
+
+pub struct Debugger {
+    pub prg: Prg,
+    pub io: Queue,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+}
+
+impl Debugger {
+    pub fn new(prg: Prg) -> Debugger {
+        Debugger {
+            prg,
+            io: Queue::default(),
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn remove_breakpoint(&mut self, pc: usize) -> bool {
+        self.breakpoints.remove(&pc)
+    }
+
+    pub fn add_watchpoint(&mut self, address: usize) {
+        self.watchpoints.insert(address);
+    }
+
+    pub fn remove_watchpoint(&mut self, address: usize) -> bool {
+        self.watchpoints.remove(&address)
+    }
+
+    /// Executes a single instruction, unless the program counter is on a
+    /// word that does not decode to one.
+    pub fn step(&mut self) -> Event {
+        let instruction = decode(&self.prg.data, self.prg.pc);
+        if instruction.is_data() {
+            return Event::Invalid {
+                pc: self.prg.pc,
+                op: instruction.op,
+            };
+        }
+        let watched: Vec<(usize, i64)> = self
+            .watchpoints
+            .iter()
+            .map(|&address| (address, self.prg.data.read(address)))
+            .collect();
+        match self.prg.step(&mut self.io) {
+            Some(stop @ (Stop::Halted | Stop::Blocked)) => return Event::Stopped(stop),
+            Some(Stop::Wrote) | None => {}
+        }
+        for (address, old) in watched {
+            let new = self.prg.data.read(address);
+            if new != old {
+                return Event::Watchpoint { address, old, new };
+            }
+        }
+        if self.breakpoints.contains(&self.prg.pc) {
+            return Event::Breakpoint(self.prg.pc);
+        }
+        Event::Stepped
+    }
+
+    /// Executes up to `count` instructions, stopping early on anything but a plain step.
+    pub fn step_many(&mut self, count: usize) -> Event {
+        for _ in 0..count {
+            match self.step() {
+                Event::Stepped => {}
+                event => return event,
+            }
+        }
+        Event::Stepped
+    }
+
+    /// Runs until something other than a plain step happens. A breakpoint
+    /// on the instruction about to run is stepped over, so continuing from
+    /// one gets somewhere.
+    pub fn cont(&mut self) -> Event {
+        let mut event = self.step();
+        while event == Event::Stepped {
+            if self.breakpoints.contains(&self.prg.pc) {
+                return Event::Breakpoint(self.prg.pc);
+            }
+            event = self.step();
+        }
+        event
+    }
+
+    /// The registers, io queues and the instruction about to run.
+    pub fn dump(&self) -> String {
+        format!(
+            "pc: {}  relative_base: {}\ninput: {:?}\noutput: {:?}\n> {}",
+            self.prg.pc,
+            self.prg.relative_base,
+            self.io.input,
+            self.io.output,
+            decode(&self.prg.data, self.prg.pc)
+        )
+    }
+
+    /// Runs one debugger command, returning what it has to show.
+    pub fn command(&mut self, line: &str) -> Result<String, String> {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            return Ok(String::new());
+        };
+        let args = words
+            .map(|w| w.parse::<i64>().map_err(|_| format!("not a number: {}", w)))
+            .collect::<Result<Vec<i64>, String>>()?;
+        let arg = |ix: usize| -> Result<usize, String> {
+            let value = *args
+                .get(ix)
+                .ok_or_else(|| format!("{} needs an argument", name))?;
+            usize::try_from(value).map_err(|_| format!("not an address: {}", value))
+        };
+        let after = |event: Event, prg: &Prg| format!("{}\n> {}", event, decode(&prg.data, prg.pc));
+
+        match name {
+            "s" | "step" => {
+                let count = if args.is_empty() { 1 } else { arg(0)? };
+                let event = self.step_many(count);
+                Ok(after(event, &self.prg))
+            }
+            "c" | "continue" => {
+                let event = self.cont();
+                Ok(after(event, &self.prg))
+            }
+            "b" | "break" => {
+                self.add_breakpoint(arg(0)?);
+                Ok(format!("breakpoints: {:?}", self.breakpoints))
+            }
+            "unbreak" => match self.remove_breakpoint(arg(0)?) {
+                true => Ok(format!("breakpoints: {:?}", self.breakpoints)),
+                false => Err(format!("no breakpoint at {}", args[0])),
+            },
+            "w" | "watch" => {
+                self.add_watchpoint(arg(0)?);
+                Ok(format!("watchpoints: {:?}", self.watchpoints))
+            }
+            "unwatch" => match self.remove_watchpoint(arg(0)?) {
+                true => Ok(format!("watchpoints: {:?}", self.watchpoints)),
+                false => Err(format!("no watchpoint at {}", args[0])),
+            },
+            "i" | "input" => {
+                self.io.input.extend(&args);
+                Ok(format!("input: {:?}", self.io.input))
+            }
+            "x" | "mem" => {
+                let from = arg(0)?;
+                let count = if args.len() > 1 { arg(1)? } else { 1 };
+                Ok((from..from + count)
+                    .map(|address| format!("{:04}: {}", address, self.prg.data.read(address)))
+                    .collect::<Vec<String>>()
+                    .join("\n"))
+            }
+            "d" | "dis" => {
+                let mut pc = if args.is_empty() {
+                    self.prg.pc
+                } else {
+                    arg(0)?
+                };
+                let count = if args.len() > 1 { arg(1)? } else { 10 };
+                let mut lines = Vec::new();
+                for _ in 0..count {
+                    let instruction = decode(&self.prg.data, pc);
+                    pc += instruction.size();
+                    lines.push(instruction.to_string());
+                }
+                Ok(lines.join("\n"))
+            }
+            "r" | "regs" => Ok(self.dump()),
+            "h" | "help" => Ok(HELP.to_string()),
+            _ => Err(format!("unknown command: {}", name)),
+        }
+    }
+
+    /// Reads commands line by line until `quit` or the end of input,
+    /// writing each result to `out`. A prompt is shown before each command
+    /// when `prompt` is set.
+    pub fn repl(
+        &mut self,
+        commands: impl BufRead,
+        out: &mut impl Write,
+        prompt: bool,
+    ) -> io::Result<()> {
+        if prompt {
+            write!(out, "(intcode) ")?;
+            out.flush()?;
+        }
+        for line in commands.lines() {
+            let line = line?;
+            if matches!(line.trim(), "q" | "quit") {
+                break;
+            }
+            match self.command(&line) {
+                Ok(text) if text.is_empty() => {}
+                Ok(text) => writeln!(out, "{}", text)?,
+                Err(e) => writeln!(out, "error: {}", e)?,
+            }
+            if prompt {
+                write!(out, "(intcode) ")?;
+                out.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reads a value, doubles it into address 11, prints it and halts
+    const DOUBLE: &str = "3,11,1002,11,2,11,4,11,99,0,0,0";
+
+    #[test]
+    fn test_breakpoint_and_step() {
+        let mut d = Debugger::new(Prg::new(DOUBLE));
+        d.io.input.push_back(21);
+        d.add_breakpoint(6);
+        assert_eq!(Event::Breakpoint(6), d.cont());
+        assert_eq!(42, d.prg.data.read(11));
+        assert!(d.io.output.is_empty());
+        assert_eq!(Event::Stepped, d.step());
+        assert_eq!(vec![42], Vec::from(d.io.output.clone()));
+        assert_eq!(Event::Stopped(Stop::Halted), d.cont());
+    }
+
+    #[test]
+    fn test_continue_past_breakpoint() {
+        // counts the word at 9 down from 3, jumping back to the start
+        let mut d = Debugger::new(Prg::new("1001,9,-1,9,1005,9,0,99,0,3"));
+        d.add_breakpoint(0);
+        for left in [2, 1] {
+            assert_eq!(Event::Breakpoint(0), d.cont());
+            assert_eq!(left, d.prg.data.read(9));
+        }
+        assert_eq!(Event::Stopped(Stop::Halted), d.cont());
+        assert_eq!(0, d.prg.data.read(9));
+    }
+
+    #[test]
+    fn test_data() {
+        // stores 2 at 3 and runs on into the zero after it
+        let mut d = Debugger::new(Prg::new("1101,1,1,3"));
+        assert_eq!(Event::Stepped, d.step());
+        let invalid = Event::Invalid { pc: 4, op: 0 };
+        assert_eq!(invalid, d.step());
+        assert_eq!(invalid, d.cont());
+        assert_eq!(4, d.prg.pc);
+        assert_eq!(
+            "no instruction at 4, only data 0\n> 0004: data 0",
+            d.command("step").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_watchpoint() {
+        let mut d = Debugger::new(Prg::new(DOUBLE));
+        d.io.input.push_back(5);
+        d.add_watchpoint(11);
+        assert_eq!(
+            Event::Watchpoint {
+                address: 11,
+                old: 0,
+                new: 5
+            },
+            d.cont()
+        );
+        assert_eq!(2, d.prg.pc);
+        assert_eq!(
+            Event::Watchpoint {
+                address: 11,
+                old: 5,
+                new: 10
+            },
+            d.cont()
+        );
+    }
+
+    #[test]
+    fn test_waits_for_input() {
+        let mut d = Debugger::new(Prg::new(DOUBLE));
+        assert_eq!(Event::Stopped(Stop::Blocked), d.cont());
+        assert_eq!(0, d.prg.pc);
+    }
+
+    #[test]
+    fn test_script() {
+        let mut d = Debugger::new(Prg::new(DOUBLE));
+        let script = "break 6\ninput 4\ncontinue\nmem 11\nregs\nbogus\nquit\nstep\n";
+        let mut out = Vec::new();
+        d.repl(script.as_bytes(), &mut out, false).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            "breakpoints: {6}\n\
+             input: [4]\n\
+             breakpoint at 6\n\
+             > 0006: out [11]\n\
+             0011: 8\n\
+             pc: 6  relative_base: 0\n\
+             input: []\n\
+             output: []\n\
+             > 0006: out [11]\n\
+             error: unknown command: bogus\n",
+            out
+        );
+    }
+}
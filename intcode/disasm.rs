@@ -0,0 +1,158 @@
+//! Decoding Intcode back into readable instructions.
+
+use std::fmt;
+
+use super::{MemBank, Prg};
+
+/// An instruction parameter, as its mode says to read it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Param {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Param::Position(address) => write!(f, "[{}]", address),
+            Param::Immediate(value) => write!(f, "{}", value),
+            Param::Relative(offset) if *offset < 0 => write!(f, "[rb-{}]", -offset),
+            Param::Relative(offset) => write!(f, "[rb+{}]", offset),
+        }
+    }
+}
+
+/// A decoded instruction. Words that don't decode to a valid instruction
+/// come out as `data`, one word long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Instruction {
+    pub pc: usize,
+    pub op: i64,
+    pub name: &'static str,
+    pub params: Vec<Param>,
+}
+
+impl Instruction {
+    /// Number of memory words the instruction takes up.
+    pub fn size(&self) -> usize {
+        1 + self.params.len()
+    }
+
+    pub fn is_data(&self) -> bool {
+        self.name == "data"
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}: ", self.pc)?;
+        if self.is_data() {
+            return write!(f, "data {}", self.op);
+        }
+        write!(f, "{}", self.name)?;
+        for (ix, param) in self.params.iter().enumerate() {
+            let sep = if ix == 0 { " " } else { ", " };
+            write!(f, "{}{}", sep, param)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the instruction starting at `pc`.
+pub fn decode(mem: &MemBank, pc: usize) -> Instruction {
+    let op = mem.read(pc);
+    let data = Instruction {
+        pc,
+        op,
+        name: "data",
+        params: Vec::new(),
+    };
+    let (name, count) = match op % 100 {
+        1 => ("add", 3),
+        2 => ("mul", 3),
+        3 => ("in", 1),
+        4 => ("out", 1),
+        5 => ("jnz", 2),
+        6 => ("jz", 2),
+        7 => ("lt", 3),
+        8 => ("eq", 3),
+        9 => ("arb", 1),
+        99 => ("hlt", 0),
+        _ => return data,
+    };
+    if op < 0 || op / 10i64.pow(2 + count) != 0 {
+        return data;
+    }
+    let mut params = Vec::new();
+    for ix in 1..=count {
+        let value = mem.read(pc + ix as usize);
+        params.push(match Prg::mode_for(op, ix) {
+            0 => Param::Position(value),
+            1 => Param::Immediate(value),
+            2 => Param::Relative(value),
+            _ => return data,
+        });
+    }
+    Instruction {
+        pc,
+        op,
+        name,
+        params,
+    }
+}
+
+/// Decodes instructions one after the other from `from` until reaching `to`.
+pub fn disassemble(mem: &MemBank, from: usize, to: usize) -> Vec<Instruction> {
+    let mut result = Vec::new();
+    let mut pc = from;
+    while pc < to {
+        let instruction = decode(mem, pc);
+        pc += instruction.size();
+        result.push(instruction);
+    }
+    result
+}
+
+/// A listing of the whole loaded program, one instruction per line.
+pub fn listing(prg: &Prg) -> String {
+    disassemble(&prg.data, 0, prg.data.program_len())
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_modes() {
+        let p = Prg::new("1002,4,3,4,33");
+        let i = decode(&p.data, 0);
+        assert_eq!("mul", i.name);
+        assert_eq!(
+            vec![Param::Position(4), Param::Immediate(3), Param::Position(4)],
+            i.params
+        );
+        assert_eq!("0000: mul [4], 3, [4]", i.to_string());
+    }
+
+    #[test]
+    fn test_listing() {
+        let p = Prg::new("109,19,204,-34,3,0,99,12345");
+        assert_eq!(
+            "0000: arb 19\n0002: out [rb-34]\n0004: in [0]\n0006: hlt\n0007: data 12345",
+            listing(&p)
+        );
+    }
+
+    #[test]
+    fn test_bad_mode_is_data() {
+        let p = Prg::new("31101,1,2,3");
+        assert!(decode(&p.data, 0).is_data());
+        let p = Prg::new("301,1,2,3");
+        assert!(decode(&p.data, 0).is_data());
+    }
+}
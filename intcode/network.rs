@@ -0,0 +1,498 @@
+//! Several Intcode machines wired together, either taking turns on one
+//! thread or each running on a thread of its own.
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use super::{Io, Prg, Queue, Stop};
+
+/// Where machine output goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Routing {
+    /// Each machine feeds the next, and the last feeds the first.
+    Ring,
+    /// Every value goes to all the other machines.
+    Broadcast,
+    /// Output comes in packets of an address followed by `size` values. The
+    /// values go to the machine with that id; any other address goes to the
+    /// monitor.
+    Packets { size: usize },
+}
+
+/// What the monitor wants the network to do next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Continue,
+    Stop,
+}
+
+/// How a run of the network ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum End {
+    /// Every machine halted.
+    Halted,
+    /// The monitor asked to stop.
+    Stopped,
+}
+
+/// Watches a running network and can step in, NAT style.
+pub trait Monitor {
+    /// Sees every value a machine writes, before it is routed.
+    fn output(&mut self, _from: usize, _value: i64) -> Action {
+        Action::Continue
+    }
+
+    /// Receives packets addressed outside the network.
+    fn outside(&mut self, _address: i64, _packet: &[i64]) -> Action {
+        Action::Continue
+    }
+
+    /// Called when every machine is waiting for input that is not coming.
+    /// Sending something to a machine gets it going again.
+    fn idle(&mut self, _post: &mut dyn Post) -> Action {
+        Action::Stop
+    }
+}
+
+/// Queues input for machines in a running network.
+pub trait Post {
+    fn send(&mut self, id: usize, values: &[i64]);
+}
+
+/// Remembers the last value one machine wrote.
+pub struct LastOutput {
+    pub from: usize,
+    pub value: Option<i64>,
+}
+
+impl LastOutput {
+    pub fn new(from: usize) -> LastOutput {
+        LastOutput { from, value: None }
+    }
+}
+
+impl Monitor for LastOutput {
+    fn output(&mut self, from: usize, value: i64) -> Action {
+        if from == self.from {
+            self.value = Some(value);
+        }
+        Action::Continue
+    }
+}
+
+pub struct Network {
+    machines: Vec<(Prg, Queue)>,
+    halted: Vec<bool>,
+    routing: Routing,
+    /// Handed to a machine that asks for input when it has none queued,
+    /// instead of leaving it blocked.
+    pub idle_input: Option<i64>,
+}
+
+impl Network {
+    /// Machines get ids in the order given.
+    pub fn new(machines: Vec<Prg>, routing: Routing) -> Network {
+        Network {
+            halted: vec![false; machines.len()],
+            machines: machines
+                .into_iter()
+                .map(|p| (p, Queue::default()))
+                .collect(),
+            routing,
+            idle_input: None,
+        }
+    }
+
+    /// Queues input for the machine `id`.
+    pub fn send(&mut self, id: usize, values: &[i64]) {
+        self.machines[id].1.input.extend(values);
+    }
+
+    /// Gives every machine a turn until they all halt or the monitor stops the run.
+    pub fn run(&mut self, monitor: &mut dyn Monitor) -> End {
+        let count = self.machines.len();
+        loop {
+            let mut busy = false;
+            for id in 0..count {
+                if self.halted[id] {
+                    continue;
+                }
+                let (prg, queue) = &mut self.machines[id];
+                let had_input = !queue.input.is_empty();
+                let mut stop = prg.run(queue, true);
+                if let (Stop::Blocked, Some(value)) = (stop, self.idle_input) {
+                    queue.input.push_back(value);
+                    stop = prg.run(queue, true);
+                    queue.input.clear();
+                }
+                match stop {
+                    Stop::Halted => self.halted[id] = true,
+                    Stop::Wrote => busy = true,
+                    Stop::Blocked => {}
+                }
+                busy |= had_input;
+                let mut output = std::mem::take(&mut self.machines[id].1.output);
+                let action = route(self.routing, count, id, &mut output, monitor, self);
+                self.machines[id].1.output = output;
+                if action == Action::Stop {
+                    return End::Stopped;
+                }
+            }
+            if self.halted.iter().all(|h| *h) {
+                return End::Halted;
+            }
+            if !busy && monitor.idle(self) == Action::Stop {
+                return End::Stopped;
+            }
+        }
+    }
+
+    /// Runs every machine on a thread of its own until they all halt or the
+    /// monitor stops the run, routing output as it comes in.
+    ///
+    /// A machine is waiting once it asks for input it does not have, or with
+    /// `idle_input` set, asks twice in a row without writing in between. The
+    /// network is idle when every machine still running is waiting and has
+    /// read everything sent to it.
+    pub fn run_threaded(&mut self, monitor: &mut dyn Monitor) -> End {
+        let count = self.machines.len();
+        let (reports, events) = channel();
+        let mut post = Wires {
+            inputs: Vec::new(),
+            sent: vec![0; count],
+        };
+        let mut waiting = vec![None; count];
+        let mut outputs = vec![VecDeque::new(); count];
+        let idle_input = self.idle_input;
+        thread::scope(|scope| {
+            for (id, (prg, queue)) in self.machines.iter_mut().enumerate() {
+                let (input, receiver) = channel();
+                post.inputs.push((!self.halted[id]).then_some(input));
+                let values: Vec<i64> = queue.input.drain(..).collect();
+                post.send(id, &values);
+                if self.halted[id] {
+                    continue;
+                }
+                let mut wire = Wire {
+                    id,
+                    input: receiver,
+                    reports: reports.clone(),
+                    idle_input,
+                    read: 0,
+                    misses: 0,
+                };
+                scope.spawn(move || {
+                    if prg.run(&mut wire, false) == Stop::Halted {
+                        let _ = wire.reports.send(Report::Halted(id));
+                    }
+                });
+            }
+            drop(reports);
+            let end = loop {
+                let report = match events.try_recv() {
+                    Ok(report) => report,
+                    Err(TryRecvError::Disconnected) => break End::Halted,
+                    Err(TryRecvError::Empty) => {
+                        let idle = || {
+                            (0..count).all(|id| {
+                                post.inputs[id].is_none() || waiting[id] == Some(post.sent[id])
+                            })
+                        };
+                        if self.halted.iter().all(|h| *h) {
+                            break End::Halted;
+                        }
+                        if idle() && monitor.idle(&mut post) == Action::Stop {
+                            break End::Stopped;
+                        }
+                        match events.recv() {
+                            Ok(report) => report,
+                            Err(_) => break End::Halted,
+                        }
+                    }
+                };
+                match report {
+                    Report::Output(from, value) => {
+                        waiting[from] = None;
+                        outputs[from].push_back(value);
+                        let action = route(
+                            self.routing,
+                            count,
+                            from,
+                            &mut outputs[from],
+                            monitor,
+                            &mut post,
+                        );
+                        if action == Action::Stop {
+                            break End::Stopped;
+                        }
+                    }
+                    Report::Waiting(id, read) => waiting[id] = Some(read),
+                    Report::Halted(id) => {
+                        self.halted[id] = true;
+                        post.inputs[id] = None;
+                    }
+                }
+            };
+            // dropping the inputs ends the machines still reading
+            post.inputs.clear();
+            end
+        })
+    }
+}
+
+impl Post for Network {
+    fn send(&mut self, id: usize, values: &[i64]) {
+        Network::send(self, id, values);
+    }
+}
+
+/// Moves what the machine `from` wrote on to where it should go.
+fn route(
+    routing: Routing,
+    count: usize,
+    from: usize,
+    output: &mut VecDeque<i64>,
+    monitor: &mut dyn Monitor,
+    post: &mut dyn Post,
+) -> Action {
+    match routing {
+        Routing::Ring | Routing::Broadcast => {
+            while let Some(value) = output.pop_front() {
+                if monitor.output(from, value) == Action::Stop {
+                    return Action::Stop;
+                }
+                if routing == Routing::Ring {
+                    post.send((from + 1) % count, &[value]);
+                } else {
+                    for to in (0..count).filter(|to| *to != from) {
+                        post.send(to, &[value]);
+                    }
+                }
+            }
+        }
+        Routing::Packets { size } => {
+            while output.len() > size {
+                let packet: Vec<i64> = output.drain(..=size).collect();
+                for value in &packet {
+                    if monitor.output(from, *value) == Action::Stop {
+                        return Action::Stop;
+                    }
+                }
+                let (address, values) = (packet[0], &packet[1..]);
+                match usize::try_from(address) {
+                    Ok(to) if to < count => post.send(to, values),
+                    _ => {
+                        if monitor.outside(address, values) == Action::Stop {
+                            return Action::Stop;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Action::Continue
+}
+
+/// What a machine on its own thread tells the network.
+enum Report {
+    Output(usize, i64),
+    /// The machine is waiting, having read this many values.
+    Waiting(usize, usize),
+    Halted(usize),
+}
+
+/// The input side of every threaded machine, `None` once it is gone.
+struct Wires {
+    inputs: Vec<Option<Sender<i64>>>,
+    sent: Vec<usize>,
+}
+
+impl Post for Wires {
+    fn send(&mut self, id: usize, values: &[i64]) {
+        if let Some(input) = &self.inputs[id] {
+            for value in values {
+                if input.send(*value).is_ok() {
+                    self.sent[id] += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Like `Channel`, but tells the network when the machine runs dry.
+struct Wire {
+    id: usize,
+    input: Receiver<i64>,
+    reports: Sender<Report>,
+    idle_input: Option<i64>,
+    read: usize,
+    misses: usize,
+}
+
+impl Wire {
+    fn got(&mut self, value: i64) -> i64 {
+        self.read += 1;
+        self.misses = 0;
+        value
+    }
+}
+
+impl Io for Wire {
+    fn read(&mut self) -> Option<i64> {
+        match self.input.try_recv() {
+            Ok(value) => return Some(self.got(value)),
+            Err(TryRecvError::Disconnected) => return None,
+            Err(TryRecvError::Empty) => {}
+        }
+        self.misses += 1;
+        match self.idle_input {
+            Some(value) => {
+                if self.misses == 2 {
+                    let _ = self.reports.send(Report::Waiting(self.id, self.read));
+                }
+                thread::yield_now();
+                Some(value)
+            }
+            None => {
+                let _ = self.reports.send(Report::Waiting(self.id, self.read));
+                let value = self.input.recv().ok()?;
+                Some(self.got(value))
+            }
+        }
+    }
+
+    fn write(&mut self, value: i64) {
+        self.misses = 0;
+        let _ = self.reports.send(Report::Output(self.id, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Nat {
+        outside: Vec<(i64, Vec<i64>)>,
+        idle: usize,
+    }
+
+    impl Monitor for Nat {
+        fn outside(&mut self, address: i64, packet: &[i64]) -> Action {
+            self.outside.push((address, packet.to_vec()));
+            Action::Continue
+        }
+
+        fn idle(&mut self, post: &mut dyn Post) -> Action {
+            self.idle += 1;
+            if self.idle == 1 {
+                post.send(0, &[7]);
+                Action::Continue
+            } else {
+                Action::Stop
+            }
+        }
+    }
+
+    #[test]
+    fn test_packets_leave_the_network() {
+        // reads its address, then sends [5, address, 42] and halts
+        let program = "3,100,104,5,4,100,104,42,99";
+        let mut net = Network::new(vec![Prg::new(program); 2], Routing::Packets { size: 2 });
+        net.send(0, &[0]);
+        net.send(1, &[1]);
+        let mut nat = Nat::default();
+        assert_eq!(End::Halted, net.run(&mut nat));
+        assert_eq!(vec![(5, vec![0, 42]), (5, vec![1, 42])], nat.outside);
+        assert_eq!(0, nat.idle);
+    }
+
+    #[test]
+    fn test_idle_network_wakes_up() {
+        // reads its address, then loops reading x, skipping -1, sending [255, x, address]
+        let program = "3,100,3,101,1008,101,-1,102,1005,102,2,104,255,4,101,4,100,1105,1,2";
+        let mut net = Network::new(vec![Prg::new(program); 2], Routing::Packets { size: 2 });
+        net.idle_input = Some(-1);
+        net.send(0, &[0]);
+        net.send(1, &[1]);
+        let mut nat = Nat::default();
+        assert_eq!(End::Stopped, net.run(&mut nat));
+        assert_eq!(vec![(255, vec![7, 0])], nat.outside);
+        assert_eq!(2, nat.idle);
+    }
+
+    #[test]
+    fn test_broadcast() {
+        // machine 0 writes 3 and halts, the others add it to 10 and write that
+        let sender = Prg::new("104,3,99");
+        let adder = Prg::new("3,9,1001,9,10,9,4,9,99,0");
+        let mut net = Network::new(vec![sender, adder.clone(), adder], Routing::Broadcast);
+        let mut last = LastOutput::new(2);
+        assert_eq!(End::Halted, net.run(&mut last));
+        assert_eq!(Some(13), last.value);
+        // machine 1's 13 also reached machine 2, after the 3 it used
+        assert_eq!(vec![13], Vec::from(net.machines[2].1.input.clone()));
+    }
+
+    #[test]
+    fn test_deadlock_stops() {
+        let mut net = Network::new(vec![Prg::new("3,0,99"); 2], Routing::Ring);
+        assert_eq!(End::Stopped, net.run(&mut LastOutput::new(0)));
+    }
+    #[test]
+    fn test_threaded_packets() {
+        let program = "3,100,104,5,4,100,104,42,99";
+        let mut net = Network::new(vec![Prg::new(program); 2], Routing::Packets { size: 2 });
+        net.send(0, &[0]);
+        net.send(1, &[1]);
+        let mut nat = Nat::default();
+        assert_eq!(End::Halted, net.run_threaded(&mut nat));
+        nat.outside.sort();
+        assert_eq!(vec![(5, vec![0, 42]), (5, vec![1, 42])], nat.outside);
+        assert_eq!(0, nat.idle);
+    }
+
+    #[test]
+    fn test_threaded_idle_network_wakes_up() {
+        let program = "3,100,3,101,1008,101,-1,102,1005,102,2,104,255,4,101,4,100,1105,1,2";
+        let mut net = Network::new(vec![Prg::new(program); 2], Routing::Packets { size: 2 });
+        net.idle_input = Some(-1);
+        net.send(0, &[0]);
+        net.send(1, &[1]);
+        let mut nat = Nat::default();
+        assert_eq!(End::Stopped, net.run_threaded(&mut nat));
+        assert_eq!(vec![(255, vec![7, 0])], nat.outside);
+        assert_eq!(2, nat.idle);
+    }
+
+    #[test]
+    fn test_threaded_ring() {
+        // 2019 day 7's feedback loop: each amplifier reads its phase, then
+        // adds it to every signal passing through until the signal tops 20
+        let program = "3,20,3,21,1,20,21,21,4,21,1007,21,20,22,1005,22,2,99";
+        let phases = [1, 2, 3];
+        let mut net = Network::new(vec![Prg::new(program); 3], Routing::Ring);
+        for (id, phase) in phases.iter().enumerate() {
+            net.send(id, &[*phase]);
+        }
+        net.send(0, &[0]);
+        let mut last = LastOutput::new(2);
+        assert_eq!(End::Halted, net.run_threaded(&mut last));
+        let mut sequential = Network::new(vec![Prg::new(program); 3], Routing::Ring);
+        for (id, phase) in phases.iter().enumerate() {
+            sequential.send(id, &[*phase]);
+        }
+        sequential.send(0, &[0]);
+        let mut expected = LastOutput::new(2);
+        assert_eq!(End::Halted, sequential.run(&mut expected));
+        assert_eq!(Some(24), expected.value);
+        assert_eq!(expected.value, last.value);
+    }
+
+    #[test]
+    fn test_threaded_deadlock_stops() {
+        let mut net = Network::new(vec![Prg::new("3,0,99"); 2], Routing::Ring);
+        assert_eq!(End::Stopped, net.run_threaded(&mut LastOutput::new(0)));
+    }
+}
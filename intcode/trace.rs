@@ -0,0 +1,396 @@
+//! Recording everything a program does, and replaying a recording to check
+//! that a run comes out the same.
+//!
+//! A trace is plain text with one event per line:
+//!
+//! ```text
+//! exec 4 1002
+//! in 7
+//! write 100 14
+//! out 14
+//! ```
+//!
+//! Memory changed before the run, like 2019 day 13 putting in quarters,
+//! comes first as `poke <address> <value>` lines, and replaying applies
+//! them before running.
+
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::{Io, Prg, Queue, Stop};
+
+/// Names the file `run_and_record` and `recorder_from_env` write a trace to.
+pub const TRACE_VAR: &str = "INTCODE_TRACE";
+
+/// How many recordings this process has started.
+static RECORDINGS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// About to execute the instruction `op` at `pc`.
+    Exec {
+        pc: usize,
+        op: i64,
+    },
+    /// The instruction stored `value` at `address`.
+    Write {
+        address: usize,
+        value: i64,
+    },
+    Input(i64),
+    Output(i64),
+    /// Memory set from outside, before the program ran.
+    Poke {
+        address: usize,
+        value: i64,
+    },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Exec { pc, op } => write!(f, "exec {} {}", pc, op),
+            Event::Write { address, value } => write!(f, "write {} {}", address, value),
+            Event::Input(value) => write!(f, "in {}", value),
+            Event::Output(value) => write!(f, "out {}", value),
+            Event::Poke { address, value } => write!(f, "poke {} {}", address, value),
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Event, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let num = |ix: usize| -> Result<i64, String> {
+            words
+                .get(ix)
+                .and_then(|w| w.parse().ok())
+                .ok_or_else(|| format!("bad trace line: {}", s))
+        };
+        let event = match words.first() {
+            Some(&"exec") => Event::Exec {
+                pc: num(1)? as usize,
+                op: num(2)?,
+            },
+            Some(&"write") => Event::Write {
+                address: num(1)? as usize,
+                value: num(2)?,
+            },
+            Some(&"in") => Event::Input(num(1)?),
+            Some(&"out") => Event::Output(num(1)?),
+            Some(&"poke") => Event::Poke {
+                address: num(1)? as usize,
+                value: num(2)?,
+            },
+            _ => return Err(format!("bad trace line: {}", s)),
+        };
+        Ok(event)
+    }
+}
+
+/// Receives the events of a traced run as they happen.
+pub trait Sink {
+    fn event(&mut self, event: Event) -> Result<(), String>;
+}
+
+/// Writes events out as a trace.
+pub struct Recorder<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(out: W) -> Recorder<W> {
+        Recorder { out }
+    }
+}
+
+impl<W: Write> Sink for Recorder<W> {
+    fn event(&mut self, event: Event) -> Result<(), String> {
+        writeln!(self.out, "{}", event).map_err(|e| e.to_string())
+    }
+}
+
+/// Compares events against a recorded trace, failing at the first difference.
+pub struct Checker {
+    expected: Vec<Event>,
+    seen: usize,
+}
+
+impl Checker {
+    pub fn new(trace: &str) -> Result<Checker, String> {
+        let expected = trace
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<Event>, String>>()?;
+        Ok(Checker { expected, seen: 0 })
+    }
+
+    /// The memory the trace starts out by changing.
+    pub fn pokes(&self) -> Vec<(usize, i64)> {
+        self.expected
+            .iter()
+            .map_while(|e| match e {
+                Event::Poke { address, value } => Some((*address, *value)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The inputs the recorded run consumed, in order.
+    pub fn inputs(&self) -> Vec<i64> {
+        self.expected
+            .iter()
+            .filter_map(|e| match e {
+                Event::Input(value) => Some(*value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Fails if the trace has events that haven't been seen.
+    pub fn finish(&self) -> Result<(), String> {
+        match self.expected.get(self.seen) {
+            Some(next) => Err(format!(
+                "event {}: run ended, trace expected {}",
+                self.seen + 1,
+                next
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Sink for Checker {
+    fn event(&mut self, event: Event) -> Result<(), String> {
+        self.seen += 1;
+        match self.expected.get(self.seen - 1) {
+            Some(expected) if *expected == event => Ok(()),
+            Some(expected) => Err(format!(
+                "event {}: trace expected {}, run did {}",
+                self.seen, expected, event
+            )),
+            None => Err(format!(
+                "event {}: trace ended, run did {}",
+                self.seen, event
+            )),
+        }
+    }
+}
+
+/// Passes io through while noting what went in and out.
+struct Tap<'a> {
+    io: &'a mut dyn Io,
+    events: Vec<Event>,
+}
+
+impl Io for Tap<'_> {
+    fn read(&mut self) -> Option<i64> {
+        let value = self.io.read()?;
+        self.events.push(Event::Input(value));
+        Some(value)
+    }
+
+    fn write(&mut self, value: i64) {
+        self.events.push(Event::Output(value));
+        self.io.write(value);
+    }
+}
+
+impl Prg {
+    /// Sets a word of memory from outside the program, telling `sink`.
+    pub fn poke_traced(
+        &mut self,
+        address: usize,
+        value: i64,
+        sink: &mut dyn Sink,
+    ) -> Result<(), String> {
+        self.data.write(address, value);
+        sink.event(Event::Poke { address, value })
+    }
+
+    /// Runs like `run`, reporting every instruction, memory write, input and output to `sink`.
+    pub fn run_traced(
+        &mut self,
+        io: &mut dyn Io,
+        halt_on_write: bool,
+        sink: &mut dyn Sink,
+    ) -> Result<Stop, String> {
+        loop {
+            let pc = self.pc;
+            let op = self.data.read(pc);
+            let target = match op % 100 {
+                1 | 2 | 7 | 8 => Some(self.get_mempointer(op, 3)),
+                3 => Some(self.get_mempointer(op, 1)),
+                _ => None,
+            };
+            let mut tap = Tap {
+                io: &mut *io,
+                events: Vec::new(),
+            };
+            let stop = self.step(&mut tap);
+            if stop == Some(Stop::Blocked) {
+                return Ok(Stop::Blocked);
+            }
+            sink.event(Event::Exec { pc, op })?;
+            for event in tap.events {
+                sink.event(event)?;
+            }
+            if let Some(address) = target {
+                let value = self.data.read(address);
+                sink.event(Event::Write { address, value })?;
+            }
+            match stop {
+                None => {}
+                Some(Stop::Wrote) if !halt_on_write => {}
+                Some(stop) => return Ok(stop),
+            }
+        }
+    }
+
+    /// Runs like `run`, and if `INTCODE_TRACE` names a file, records the run
+    /// to a file of its own named after it, as `recorder_from_env` does.
+    pub fn run_and_record(&mut self, io: &mut dyn Io, halt_on_write: bool) -> Stop {
+        match recorder_from_env() {
+            Some(mut recorder) => self
+                .run_traced(io, halt_on_write, &mut recorder)
+                .unwrap_or_else(|e| panic!("could not write trace: {}", e)),
+            None => self.run(io, halt_on_write),
+        }
+    }
+}
+
+/// A recorder writing to the file `INTCODE_TRACE` names, if it is set.
+/// Every recording a process makes gets its own file, so a day running
+/// two programs keeps both: the first goes to the file named, the second
+/// to it with `.2` added, and so on. Keep hold of the recorder to record a
+/// program that is run again after blocking.
+pub fn recorder_from_env() -> Option<Recorder<BufWriter<File>>> {
+    let path = env::var(TRACE_VAR).ok()?;
+    let path = numbered(&path, RECORDINGS.fetch_add(1, Ordering::Relaxed) + 1);
+    let file = File::create(&path).unwrap_or_else(|e| panic!("could not create {}: {}", path, e));
+    Some(Recorder::new(BufWriter::new(file)))
+}
+
+/// The file the `n`th recording of a process goes to.
+fn numbered(path: &str, n: usize) -> String {
+    if n == 1 {
+        path.to_string()
+    } else {
+        format!("{}.{}", path, n)
+    }
+}
+
+/// Runs `prg` on the inputs recorded in `trace`, after making the changes
+/// to memory it starts with, and checks it does exactly what the trace
+/// says, all the way to where the recording stopped.
+pub fn replay(prg: &mut Prg, trace: &str) -> Result<Stop, String> {
+    let mut checker = Checker::new(trace)?;
+    for (address, value) in checker.pokes() {
+        prg.poke_traced(address, value, &mut checker)?;
+    }
+    let mut queue = Queue::new(&checker.inputs());
+    let stop = prg.run_traced(&mut queue, false, &mut checker)?;
+    checker.finish()?;
+    Ok(stop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reads a value, doubles it into address 11, prints it and halts
+    const DOUBLE: &str = "3,11,1002,11,2,11,4,11,99,0,0,0";
+
+    fn record(program: &str, input: &[i64]) -> String {
+        let mut out = Vec::new();
+        let mut recorder = Recorder::new(&mut out);
+        Prg::new(program)
+            .run_traced(&mut Queue::new(input), false, &mut recorder)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_record() {
+        assert_eq!(
+            "exec 0 3\nin 21\nwrite 11 21\n\
+             exec 2 1002\nwrite 11 42\n\
+             exec 6 4\nout 42\n\
+             exec 8 99\n",
+            record(DOUBLE, &[21])
+        );
+    }
+
+    #[test]
+    fn test_numbered() {
+        assert_eq!("game.trace", numbered("game.trace", 1));
+        assert_eq!("game.trace.2", numbered("game.trace", 2));
+    }
+
+    #[test]
+    fn test_replay_matches() {
+        let trace = record(DOUBLE, &[21]);
+        assert_eq!(Ok(Stop::Halted), replay(&mut Prg::new(DOUBLE), &trace));
+    }
+
+    #[test]
+    fn test_replay_finds_divergence() {
+        let trace = record(DOUBLE, &[21]);
+        // the same program, tripling instead of doubling
+        let tripling = "3,11,1002,11,3,11,4,11,99,0,0,0";
+        assert_eq!(
+            Err("event 5: trace expected write 11 42, run did write 11 63".to_string()),
+            replay(&mut Prg::new(tripling), &trace)
+        );
+    }
+
+    #[test]
+    fn test_replay_with_poke() {
+        // the same program with the doubling patched to a tripling
+        let mut out = Vec::new();
+        let mut recorder = Recorder::new(&mut out);
+        let mut prg = Prg::new(DOUBLE);
+        prg.poke_traced(4, 3, &mut recorder).unwrap();
+        prg.run_traced(&mut Queue::new(&[21]), false, &mut recorder)
+            .unwrap();
+        let trace = String::from_utf8(out).unwrap();
+        assert!(trace.starts_with("poke 4 3\nexec 0 3\n"));
+        assert!(trace.contains("write 11 63\n"));
+        assert_eq!(Ok(Stop::Halted), replay(&mut Prg::new(DOUBLE), &trace));
+        // without the poke the run doubles, and that shows
+        let unpatched = trace.replacen("poke 4 3\n", "", 1);
+        assert_eq!(
+            Err("event 5: trace expected write 11 63, run did write 11 42".to_string()),
+            replay(&mut Prg::new(DOUBLE), &unpatched)
+        );
+    }
+
+    #[test]
+    fn test_replay_trace_longer_than_run() {
+        let trace = record(DOUBLE, &[21]) + "exec 9 99\n";
+        assert_eq!(
+            Err("event 9: run ended, trace expected exec 9 99".to_string()),
+            replay(&mut Prg::new(DOUBLE), &trace)
+        );
+    }
+
+    #[test]
+    fn test_run_traced_matches_run() {
+        let program = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let mut plain = Queue::default();
+        Prg::new(program).run(&mut plain, false);
+        let mut traced = Queue::default();
+        let mut out = Vec::new();
+        Prg::new(program)
+            .run_traced(&mut traced, false, &mut Recorder::new(&mut out))
+            .unwrap();
+        assert_eq!(plain.output, traced.output);
+    }
+}
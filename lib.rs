@@ -0,0 +1,134 @@
+#[macro_use]
+extern crate lazy_static;
+
+// The day solutions predate the shared crate and were written as standalone
+// scripts. Each year allows only the lints its original code trips; the rest
+// of the lints, and the library modules below, stay on.
+#[path = "2015/mod.rs"]
+#[allow(
+    dead_code,
+    clippy::assign_op_pattern,
+    clippy::comparison_to_empty,
+    clippy::identity_op,
+    clippy::implicit_saturating_sub,
+    clippy::into_iter_on_ref,
+    clippy::items_after_test_module,
+    clippy::legacy_numeric_constants,
+    clippy::manual_map,
+    clippy::map_entry,
+    clippy::needless_borrow,
+    clippy::needless_lifetimes,
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::neg_multiply,
+    clippy::op_ref,
+    clippy::too_many_arguments,
+    clippy::useless_vec
+)]
+pub mod y2015;
+#[path = "2016/mod.rs"]
+#[allow(
+    dead_code,
+    clippy::clone_on_copy,
+    clippy::items_after_test_module,
+    clippy::legacy_numeric_constants,
+    clippy::manual_is_multiple_of,
+    clippy::manual_map,
+    clippy::manual_range_contains,
+    clippy::manual_swap,
+    clippy::map_entry,
+    clippy::map_flatten,
+    clippy::needless_borrow,
+    clippy::nonminimal_bool,
+    clippy::ptr_arg,
+    clippy::redundant_closure,
+    clippy::unwrap_or_default
+)]
+pub mod y2016;
+#[path = "2017/mod.rs"]
+#[allow(
+    clippy::items_after_test_module,
+    clippy::legacy_numeric_constants,
+    clippy::manual_is_multiple_of,
+    clippy::needless_return,
+    clippy::redundant_closure,
+    clippy::useless_conversion
+)]
+pub mod y2017;
+#[path = "2018/mod.rs"]
+#[allow(
+    dead_code,
+    unused_assignments,
+    unused_variables,
+    clippy::bool_assert_comparison,
+    clippy::char_lit_as_u8,
+    clippy::len_zero,
+    clippy::manual_contains,
+    clippy::needless_borrow,
+    clippy::needless_return,
+    clippy::useless_vec,
+    clippy::wrong_self_convention
+)]
+pub mod y2018;
+#[path = "2019/mod.rs"]
+#[allow(
+    dead_code,
+    unused_must_use,
+    unused_mut,
+    unused_variables,
+    clippy::char_lit_as_u8,
+    clippy::comparison_to_empty,
+    clippy::identity_op,
+    clippy::implicit_saturating_sub,
+    clippy::into_iter_on_ref,
+    clippy::items_after_test_module,
+    clippy::map_flatten,
+    clippy::needless_borrow,
+    clippy::needless_return,
+    clippy::neg_multiply,
+    clippy::question_mark,
+    clippy::redundant_field_names,
+    clippy::unnecessary_operation,
+    clippy::useless_vec
+)]
+pub mod y2019;
+#[path = "2023/mod.rs"]
+#[allow(
+    dead_code,
+    unused_assignments,
+    unused_variables,
+    clippy::assign_op_pattern,
+    clippy::bool_assert_comparison,
+    clippy::clone_on_copy,
+    clippy::comparison_to_empty,
+    clippy::explicit_counter_loop,
+    clippy::get_first,
+    clippy::if_same_then_else,
+    clippy::len_zero,
+    clippy::manual_find,
+    clippy::manual_is_multiple_of,
+    clippy::manual_ok_err,
+    clippy::map_entry,
+    clippy::needless_borrow,
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::op_ref,
+    clippy::ptr_arg,
+    clippy::question_mark,
+    clippy::redundant_closure,
+    clippy::unnecessary_cast,
+    clippy::unnecessary_mut_passed,
+    clippy::useless_conversion,
+    clippy::useless_vec
+)]
+pub mod y2023;
+
+pub mod assembunny;
+pub mod grid;
+pub mod intcode;
+pub mod md5;
+pub mod registry;
+pub mod search;
+pub mod solution;
+pub mod tsp;
+pub mod vm;
@@ -0,0 +1,73 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use aoc::registry;
+
+const USAGE: &str = "usage: aoc <year> <day> [--part 1|2] [--input path]";
+
+struct Args {
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+    input: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let year = args.next().ok_or("missing year")?;
+    let year = year
+        .parse()
+        .map_err(|_| format!("invalid year: {}", year))?;
+    let day = args.next().ok_or("missing day")?;
+    let day = day.parse().map_err(|_| format!("invalid day: {}", day))?;
+    let mut part = None;
+    let mut input = None;
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--part" => {
+                let p = args.next().ok_or("--part needs a value")?;
+                match p.as_str() {
+                    "1" => part = Some(1),
+                    "2" => part = Some(2),
+                    _ => return Err(format!("invalid part: {}", p)),
+                }
+            }
+            "--input" => input = Some(args.next().ok_or("--input needs a path")?),
+            _ => return Err(format!("unknown argument: {}", flag)),
+        }
+    }
+    Ok(Args {
+        year,
+        day,
+        part,
+        input,
+    })
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("{}\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let args = parse_args(env::args().skip(1)).unwrap_or_else(|e| fail(&e));
+    let day = registry::find(args.year, args.day)
+        .unwrap_or_else(|| fail(&format!("no solution for {} day {}", args.year, args.day)));
+
+    let input = match args.input.map(PathBuf::from).or(day.input_path()) {
+        Some(path) => fs::read_to_string(&path)
+            .unwrap_or_else(|e| fail(&format!("could not read {}: {}", path.display(), e))),
+        None => String::new(),
+    };
+
+    let parts = match args.part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    for (p, answer) in parts.iter().zip((day.solve)(&input, &parts)) {
+        // pictures read better starting on their own line
+        let sep = if answer.contains('\n') { '\n' } else { ' ' };
+        println!("part {}:{}{}", p, sep, answer);
+    }
+}
@@ -0,0 +1,298 @@
+//! MD5, built for puzzles that hash a fixed salt followed by a counter
+//! until the digest looks a certain way.
+//!
+//! A `Prefix` compresses the salt once, so each counter only costs its own
+//! final block or two. `search` scans counters in parallel batches.
+
+use std::ops::Range;
+use std::thread;
+
+pub type Digest = [u8; 16];
+
+/// Per-round shift amounts.
+const S: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// The table built from the sine function.
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+const INIT: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+/// Mixes one 64 byte block into the state.
+fn compress(state: &mut [u32; 4], block: &[u8; 64]) {
+    let mut x = [0u32; 16];
+    for (word, bytes) in x.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    let [mut a, mut b, mut c, mut d] = *state;
+    // each round mixes in every word once, in its own order and with its
+    // own way of combining b, c and d
+    macro_rules! round {
+        ($from:expr, |$b:ident, $c:ident, $d:ident| $f:expr, $g:expr) => {
+            for i in $from..$from + 16 {
+                let ($b, $c, $d) = (b, c, d);
+                let f = ($f)
+                    .wrapping_add(a)
+                    .wrapping_add(K[i])
+                    .wrapping_add(x[$g(i) % 16]);
+                a = d;
+                d = c;
+                c = b;
+                b = b.wrapping_add(f.rotate_left(S[i]));
+            }
+        };
+    }
+    round!(0, |b, c, d| (b & c) | (!b & d), |i| i);
+    round!(16, |b, c, d| (d & b) | (!d & c), |i| 5 * i + 1);
+    round!(32, |b, c, d| b ^ c ^ d, |i| 3 * i + 5);
+    round!(48, |b, c, d| c ^ (b | !d), |i| 7 * i);
+    for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+/// Hashes `parts` one after the other, starting from a state that has
+/// already taken in `done` bytes.
+fn finish(mut state: [u32; 4], done: u64, parts: &[&[u8]]) -> Digest {
+    let mut block = [0u8; 64];
+    let mut fill = 0;
+    let mut total = done;
+    for part in parts {
+        let mut part = *part;
+        total += part.len() as u64;
+        while !part.is_empty() {
+            let n = part.len().min(64 - fill);
+            block[fill..fill + n].copy_from_slice(&part[..n]);
+            fill += n;
+            part = &part[n..];
+            if fill == 64 {
+                compress(&mut state, &block);
+                fill = 0;
+            }
+        }
+    }
+    block[fill] = 0x80;
+    block[fill + 1..].fill(0);
+    if fill >= 56 {
+        compress(&mut state, &block);
+        block = [0; 64];
+    }
+    block[56..].copy_from_slice(&(total * 8).to_le_bytes());
+    compress(&mut state, &block);
+
+    let mut digest = [0; 16];
+    for (bytes, s) in digest.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&s.to_le_bytes());
+    }
+    digest
+}
+
+pub fn digest(data: &[u8]) -> Digest {
+    finish(INIT, 0, &[data])
+}
+
+/// The digest written out as 32 lowercase hex digits.
+pub fn hex_bytes(digest: &Digest) -> [u8; 32] {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut hex = [0; 32];
+    for (pair, byte) in hex.chunks_exact_mut(2).zip(digest) {
+        pair[0] = DIGITS[(byte >> 4) as usize];
+        pair[1] = DIGITS[(byte & 0xf) as usize];
+    }
+    hex
+}
+
+pub fn hex(digest: &Digest) -> String {
+    hex_bytes(digest).iter().map(|b| *b as char).collect()
+}
+
+/// Hashes the hex form of the digest again, `rounds` times over.
+pub fn stretch(mut digest: Digest, rounds: usize) -> Digest {
+    for _ in 0..rounds {
+        digest = finish(INIT, 0, &[&hex_bytes(&digest)]);
+    }
+    digest
+}
+
+/// A salt that many messages start with, with as much of it hashed as can
+/// be ahead of time.
+#[derive(Debug, Clone)]
+pub struct Prefix {
+    state: [u32; 4],
+    done: u64,
+    rest: Vec<u8>,
+}
+
+impl Prefix {
+    pub fn new(salt: &[u8]) -> Prefix {
+        let mut state = INIT;
+        let whole = salt.len() / 64 * 64;
+        for block in salt[..whole].chunks_exact(64) {
+            compress(&mut state, block.try_into().unwrap());
+        }
+        Prefix {
+            state,
+            done: whole as u64,
+            rest: salt[whole..].to_vec(),
+        }
+    }
+
+    /// The digest of the salt followed by `suffix`.
+    pub fn digest(&self, suffix: &[u8]) -> Digest {
+        finish(self.state, self.done, &[&self.rest, suffix])
+    }
+
+    /// The digest of the salt followed by `n` in decimal.
+    pub fn digest_number(&self, n: u64) -> Digest {
+        let mut buf = [0u8; 20];
+        let mut at = buf.len();
+        let mut n = n;
+        loop {
+            at -= 1;
+            buf[at] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        self.digest(&buf[at..])
+    }
+
+    /// The digests of every number in the range, worked out in parallel.
+    pub fn batch(&self, numbers: Range<u64>) -> Vec<Digest> {
+        par_map(numbers, |n| self.digest_number(n))
+    }
+}
+
+/// Runs `f` on every number in the range, split evenly across the cores,
+/// and gives back the results in order.
+pub fn par_map<T: Send>(numbers: Range<u64>, f: impl Fn(u64) -> T + Sync) -> Vec<T> {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get()) as u64;
+    let chunk = (numbers.end.saturating_sub(numbers.start))
+        .div_ceil(threads)
+        .max(1);
+    let f = &f;
+    thread::scope(|s| {
+        let workers: Vec<_> = (0..threads)
+            .map(|t| {
+                let from = numbers.start + t * chunk;
+                let to = (from + chunk).min(numbers.end);
+                s.spawn(move || (from..to).map(f).collect::<Vec<T>>())
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect()
+    })
+}
+
+/// How many numbers each round of `search` hashes.
+const BATCH: u64 = 1 << 14;
+
+/// Every number from `start` up whose digest, after the salt, `test` makes
+/// something of, in order.
+pub fn search<'a, T: Send + 'a>(
+    prefix: &'a Prefix,
+    start: u64,
+    test: impl Fn(&Digest) -> Option<T> + Sync + 'a,
+) -> impl Iterator<Item = (u64, T)> + 'a {
+    (start..)
+        .step_by(BATCH as usize)
+        .flat_map(move |from| {
+            par_map(from..from + BATCH, |n| {
+                test(&prefix.digest_number(n)).map(|t| (n, t))
+            })
+        })
+        .flatten()
+}
+
+/// Whether the digest starts with `zeros` zero hex digits.
+pub fn leading_zeros(digest: &Digest, zeros: usize) -> bool {
+    let (whole, half) = (zeros / 2, zeros % 2 == 1);
+    digest[..whole].iter().all(|b| *b == 0) && (!half || digest[whole] >> 4 == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest() {
+        assert_eq!("d41d8cd98f00b204e9800998ecf8427e", hex(&digest(b"")));
+        assert_eq!("900150983cd24fb0d6963f7d28e17f72", hex(&digest(b"abc")));
+        // long enough to need a second block for the length
+        let long = [b'a'; 60];
+        assert_eq!("cc7ed669cf88f201c3297c6a91e1d18d", hex(&digest(&long)));
+        let longer =
+            b"12345678901234567890123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!("57edf4a22be3c955ac49da2e2107b67a", hex(&digest(longer)));
+    }
+
+    #[test]
+    fn test_prefix() {
+        let long_salt = [b'x'; 70];
+        for salt in [&b"abc"[..], &long_salt] {
+            let prefix = Prefix::new(salt);
+            let mut whole = salt.to_vec();
+            whole.extend(b"18");
+            assert_eq!(digest(&whole), prefix.digest_number(18));
+            assert_eq!(digest(&whole), prefix.digest(b"18"));
+        }
+        assert_eq!(digest(b"abc0"), Prefix::new(b"abc").digest_number(0));
+    }
+
+    #[test]
+    fn test_stretch() {
+        let first = digest(b"abc0");
+        assert_eq!("577571be4de9dcce85a041ba0410f29f", hex(&first));
+        assert_eq!("eec80a0c92dc8a0777c619d9bb51e910", hex(&stretch(first, 1)));
+        assert_eq!(
+            "a107ff634856bb300138cac6568c0f24",
+            hex(&stretch(first, 2016))
+        );
+    }
+
+    #[test]
+    fn test_batch() {
+        let prefix = Prefix::new(b"abc");
+        let batch = prefix.batch(5..9);
+        assert_eq!(4, batch.len());
+        assert_eq!(prefix.digest_number(7), batch[2]);
+        assert_eq!(vec![1, 4, 9], par_map(1..4, |n| n * n));
+        assert!(par_map(4..4, |n| n).is_empty());
+    }
+
+    #[test]
+    fn test_search() {
+        let prefix = Prefix::new(b"abc");
+        let found: Vec<(u64, u8)> = search(&prefix, 0, |d| leading_zeros(d, 3).then_some(d[1]))
+            .take(3)
+            .collect();
+        let expected: Vec<u64> = (0..)
+            .filter(|n| leading_zeros(&prefix.digest_number(*n), 3))
+            .take(3)
+            .collect();
+        assert_eq!(expected, found.iter().map(|f| f.0).collect::<Vec<_>>());
+        assert!(found.iter().all(|f| f.1 >> 4 == 0));
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let d = [0, 0, 0x0f, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(leading_zeros(&d, 5));
+        assert!(!leading_zeros(&d, 6));
+    }
+}
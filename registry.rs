@@ -0,0 +1,603 @@
+use crate::solution::solve;
+use crate::{y2015, y2016, y2017, y2018, y2019, y2023};
+use std::path::PathBuf;
+
+/// Parses the input and answers the requested parts, in order.
+pub type Solver = fn(&str, &[u8]) -> Vec<String>;
+
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    /// Default input file relative to the repository root,
+    /// `None` for days that carry their input in the source.
+    pub input: Option<&'static str>,
+    pub solve: Solver,
+}
+
+impl Day {
+    /// Where the default input is, found from the repository root rather
+    /// than wherever the runner is started.
+    pub fn input_path(&self) -> Option<PathBuf> {
+        self.input
+            .map(|path| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(path))
+    }
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+pub static DAYS: &[Day] = &[
+    Day {
+        year: 2015,
+        day: 1,
+        input: Some("2015/1.txt"),
+        solve: solve::<y2015::day1::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 2,
+        input: Some("2015/2.txt"),
+        solve: solve::<y2015::day2::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 3,
+        input: Some("2015/3.txt"),
+        solve: solve::<y2015::day3::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 4,
+        input: Some("2015/4.txt"),
+        solve: solve::<y2015::day4::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 5,
+        input: Some("2015/5.txt"),
+        solve: solve::<y2015::day5::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 6,
+        input: Some("2015/6.txt"),
+        solve: solve::<y2015::day6::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 7,
+        input: Some("2015/7.txt"),
+        solve: solve::<y2015::day7::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 8,
+        input: Some("2015/8.txt"),
+        solve: solve::<y2015::day8::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 9,
+        input: Some("2015/9.txt"),
+        solve: solve::<y2015::day9::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 10,
+        input: Some("2015/10.txt"),
+        solve: solve::<y2015::day10::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 11,
+        input: Some("2015/11.txt"),
+        solve: solve::<y2015::day11::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 12,
+        input: Some("2015/12.txt"),
+        solve: solve::<y2015::day12::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 13,
+        input: Some("2015/thirteen/13.txt"),
+        solve: solve::<y2015::day13::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 14,
+        input: Some("2015/14.txt"),
+        solve: solve::<y2015::day14::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 15,
+        input: None,
+        solve: solve::<y2015::day15::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 16,
+        input: Some("2015/sixteen/16.txt"),
+        solve: solve::<y2015::day16::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 17,
+        input: Some("2015/17.txt"),
+        solve: solve::<y2015::day17::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 18,
+        input: Some("2015/18.txt"),
+        solve: solve::<y2015::day18::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 19,
+        input: Some("2015/19.txt"),
+        solve: solve::<y2015::day19::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 20,
+        input: Some("2015/20.txt"),
+        solve: solve::<y2015::day20::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 21,
+        input: Some("2015/21.txt"),
+        solve: solve::<y2015::day21::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 22,
+        input: Some("2015/22.txt"),
+        solve: solve::<y2015::day22::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 23,
+        input: Some("2015/23.txt"),
+        solve: solve::<y2015::day23::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 24,
+        input: Some("2015/24.txt"),
+        solve: solve::<y2015::day24::Day>,
+    },
+    Day {
+        year: 2015,
+        day: 25,
+        input: Some("2015/25.txt"),
+        solve: solve::<y2015::day25::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 1,
+        input: Some("2016/1.txt"),
+        solve: solve::<y2016::day1::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 2,
+        input: Some("2016/2.txt"),
+        solve: solve::<y2016::day2::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 3,
+        input: Some("2016/3.txt"),
+        solve: solve::<y2016::day3::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 4,
+        input: Some("2016/4.txt"),
+        solve: solve::<y2016::day4::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 5,
+        input: Some("2016/5.txt"),
+        solve: solve::<y2016::day5::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 6,
+        input: Some("2016/6.txt"),
+        solve: solve::<y2016::day6::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 7,
+        input: Some("2016/7.txt"),
+        solve: solve::<y2016::day7::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 8,
+        input: Some("2016/8.txt"),
+        solve: solve::<y2016::day8::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 9,
+        input: Some("2016/9.txt"),
+        solve: solve::<y2016::day9::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 11,
+        input: Some("2016/11.txt"),
+        solve: solve::<y2016::day11::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 12,
+        input: Some("2016/12.txt"),
+        solve: solve::<y2016::day12::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 13,
+        input: Some("2016/13.txt"),
+        solve: solve::<y2016::day13::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 14,
+        input: Some("2016/14.txt"),
+        solve: solve::<y2016::day14::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 15,
+        input: Some("2016/15.txt"),
+        solve: solve::<y2016::day15::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 16,
+        input: Some("2016/16.txt"),
+        solve: solve::<y2016::day16::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 17,
+        input: Some("2016/17.txt"),
+        solve: solve::<y2016::day17::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 18,
+        input: Some("2016/18.txt"),
+        solve: solve::<y2016::day18::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 19,
+        input: Some("2016/19.txt"),
+        solve: solve::<y2016::day19::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 20,
+        input: Some("2016/20.txt"),
+        solve: solve::<y2016::day20::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 21,
+        input: Some("2016/21.txt"),
+        solve: solve::<y2016::day21::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 22,
+        input: Some("2016/22.txt"),
+        solve: solve::<y2016::day22::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 23,
+        input: Some("2016/23.txt"),
+        solve: solve::<y2016::day23::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 24,
+        input: Some("2016/24.txt"),
+        solve: solve::<y2016::day24::Day>,
+    },
+    Day {
+        year: 2016,
+        day: 25,
+        input: Some("2016/25.txt"),
+        solve: solve::<y2016::day25::Day>,
+    },
+    Day {
+        year: 2017,
+        day: 1,
+        input: Some("2017/1.txt"),
+        solve: solve::<y2017::day1::Day>,
+    },
+    Day {
+        year: 2017,
+        day: 2,
+        input: Some("2017/2.txt"),
+        solve: solve::<y2017::day2::Day>,
+    },
+    Day {
+        year: 2017,
+        day: 3,
+        input: Some("2017/3.txt"),
+        solve: solve::<y2017::day3::Day>,
+    },
+    Day {
+        year: 2017,
+        day: 4,
+        input: Some("2017/4.txt"),
+        solve: solve::<y2017::day4::Day>,
+    },
+    Day {
+        year: 2017,
+        day: 5,
+        input: Some("2017/5.txt"),
+        solve: solve::<y2017::day5::Day>,
+    },
+    Day {
+        year: 2018,
+        day: 1,
+        input: Some("2018/1.txt"),
+        solve: solve::<y2018::day1::Day>,
+    },
+    Day {
+        year: 2018,
+        day: 2,
+        input: Some("2018/2.txt"),
+        solve: solve::<y2018::day2::Day>,
+    },
+    Day {
+        year: 2018,
+        day: 4,
+        input: Some("2018/4.txt"),
+        solve: solve::<y2018::day4::Day>,
+    },
+    Day {
+        year: 2018,
+        day: 5,
+        input: Some("2018/5.txt"),
+        solve: solve::<y2018::day5::Day>,
+    },
+    Day {
+        year: 2018,
+        day: 6,
+        input: Some("2018/6.txt"),
+        solve: solve::<y2018::day6::Day>,
+    },
+    Day {
+        year: 2018,
+        day: 7,
+        input: Some("2018/7.txt"),
+        solve: solve::<y2018::day7::Day>,
+    },
+    Day {
+        year: 2018,
+        day: 8,
+        input: Some("2018/8.txt"),
+        solve: solve::<y2018::day8::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 1,
+        input: Some("2019/1.txt"),
+        solve: solve::<y2019::day1::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 2,
+        input: Some("2019/2.txt"),
+        solve: solve::<y2019::day2::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 3,
+        input: Some("2019/3.txt"),
+        solve: solve::<y2019::day3::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 4,
+        input: Some("2019/4.txt"),
+        solve: solve::<y2019::day4::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 5,
+        input: Some("2019/5.txt"),
+        solve: solve::<y2019::day5::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 6,
+        input: Some("2019/6.txt"),
+        solve: solve::<y2019::day6::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 7,
+        input: Some("2019/7.txt"),
+        solve: solve::<y2019::day7::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 8,
+        input: Some("2019/8.txt"),
+        solve: solve::<y2019::day8::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 9,
+        input: Some("2019/9.txt"),
+        solve: solve::<y2019::day9::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 10,
+        input: Some("2019/10.txt"),
+        solve: solve::<y2019::day10::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 11,
+        input: Some("2019/11.txt"),
+        solve: solve::<y2019::day11::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 12,
+        input: Some("2019/12.txt"),
+        solve: solve::<y2019::day12::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 13,
+        input: Some("2019/13.txt"),
+        solve: solve::<y2019::day13::Day>,
+    },
+    Day {
+        year: 2019,
+        day: 14,
+        input: Some("2019/14.txt"),
+        solve: solve::<y2019::day14::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 1,
+        input: Some("2023/1.txt"),
+        solve: solve::<y2023::day1::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 2,
+        input: Some("2023/2.txt"),
+        solve: solve::<y2023::day2::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 3,
+        input: Some("2023/3.txt"),
+        solve: solve::<y2023::day3::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 4,
+        input: Some("2023/4.txt"),
+        solve: solve::<y2023::day4::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 5,
+        input: Some("2023/5.txt"),
+        solve: solve::<y2023::day5::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 6,
+        input: Some("2023/6.txt"),
+        solve: solve::<y2023::day6::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 7,
+        input: Some("2023/7.txt"),
+        solve: solve::<y2023::day7::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 8,
+        input: Some("2023/8.txt"),
+        solve: solve::<y2023::day8::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 9,
+        input: Some("2023/9.txt"),
+        solve: solve::<y2023::day9::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 10,
+        input: Some("2023/10.txt"),
+        solve: solve::<y2023::day10::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 11,
+        input: Some("2023/11.txt"),
+        solve: solve::<y2023::day11::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 12,
+        input: Some("2023/12.txt"),
+        solve: solve::<y2023::day12::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 13,
+        input: Some("2023/13.txt"),
+        solve: solve::<y2023::day13::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 14,
+        input: Some("2023/14.txt"),
+        solve: solve::<y2023::day14::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 15,
+        input: Some("2023/15.txt"),
+        solve: solve::<y2023::day15::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 16,
+        input: Some("2023/16.txt"),
+        solve: solve::<y2023::day16::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 19,
+        input: Some("2023/19.txt"),
+        solve: solve::<y2023::day19::Day>,
+    },
+    Day {
+        year: 2023,
+        day: 20,
+        input: Some("2023/20.txt"),
+        solve: solve::<y2023::day20::Day>,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inputs_exist() {
+        for day in DAYS {
+            if let Some(path) = day.input_path() {
+                assert!(path.is_file(), "{} is missing", path.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Searching graphs that are only known through a start state and a way to
+//! list the states that follow each one.
+//!
+//! States can be anything hashable. Each search remembers the states it has
+//! seen, so reaching one again by a longer route costs nothing.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A route found by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    /// Every state along the way, from the start to the goal.
+    pub states: Vec<S>,
+    pub cost: usize,
+}
+
+/// The states a search has reached, each with the best known way there.
+struct Visited<S> {
+    states: Vec<S>,
+    parents: Vec<Option<usize>>,
+    costs: Vec<usize>,
+    ids: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Visited<S> {
+    fn new(start: S) -> Visited<S> {
+        Visited {
+            ids: HashMap::from([(start.clone(), 0)]),
+            states: vec![start],
+            parents: vec![None],
+            costs: vec![0],
+        }
+    }
+
+    /// Records reaching `state` from the state `parent` at `cost`, unless it
+    /// was already reached as cheaply. Returns its id if this is an improvement.
+    fn reach(&mut self, state: S, parent: usize, cost: usize) -> Option<usize> {
+        match self.ids.get(&state) {
+            Some(&id) if self.costs[id] <= cost => None,
+            Some(&id) => {
+                self.costs[id] = cost;
+                self.parents[id] = Some(parent);
+                Some(id)
+            }
+            None => {
+                let id = self.states.len();
+                self.ids.insert(state.clone(), id);
+                self.states.push(state);
+                self.parents.push(Some(parent));
+                self.costs.push(cost);
+                Some(id)
+            }
+        }
+    }
+
+    fn path(&self, goal: usize) -> Path<S> {
+        let mut states = vec![self.states[goal].clone()];
+        let mut at = goal;
+        while let Some(parent) = self.parents[at] {
+            states.push(self.states[parent].clone());
+            at = parent;
+        }
+        states.reverse();
+        Path {
+            states,
+            cost: self.costs[goal],
+        }
+    }
+}
+
+/// The shortest path, counting steps, to the first state that is a goal.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([0]);
+    while let Some(id) = queue.pop_front() {
+        if is_goal(&visited.states[id]) {
+            return Some(visited.path(id));
+        }
+        let cost = visited.costs[id] + 1;
+        for next in successors(&visited.states[id]) {
+            if !visited.ids.contains_key(&next) {
+                queue.extend(visited.reach(next, id, cost));
+            }
+        }
+    }
+    None
+}
+
+/// The cheapest path to a goal, where `successors` lists each following
+/// state along with the cost of the step there.
+pub fn dijkstra<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    a_star(start, successors, |_| 0, is_goal)
+}
+
+/// Like `dijkstra`, but exploring first where `heuristic` thinks the goal
+/// is close. The heuristic must never guess more than the real remaining
+/// cost, or the path found may not be the cheapest.
+pub fn a_star<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut open = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut visited = Visited::new(start);
+    while let Some(Reverse((_, cost, id))) = open.pop() {
+        if cost > visited.costs[id] {
+            // queued before a cheaper way there turned up
+            continue;
+        }
+        if is_goal(&visited.states[id]) {
+            return Some(visited.path(id));
+        }
+        for (next, step) in successors(&visited.states[id]) {
+            let guess = heuristic(&next);
+            if let Some(next_id) = visited.reach(next, id, cost + step) {
+                open.push(Reverse((cost + step + guess, cost + step, next_id)));
+            }
+        }
+    }
+    None
+}
+
+/// The number of steps on the shortest path from `start` to `goal`,
+/// searching out from both ends at once. `predecessors` lists the states
+/// that step to a given one; where every step can be undone it does the
+/// same as `successors`. States with the same `key` count as the same state.
+pub fn bidirectional<S, K, I, J>(
+    start: S,
+    goal: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut predecessors: impl FnMut(&S) -> J,
+    mut key: impl FnMut(&S) -> K,
+) -> Option<usize>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = S>,
+    J: IntoIterator<Item = S>,
+{
+    let (start_key, goal_key) = (key(&start), key(&goal));
+    if start_key == goal_key {
+        return Some(0);
+    }
+    let mut ahead = (vec![start], HashMap::from([(start_key, 0)]), 0);
+    let mut behind = (vec![goal], HashMap::from([(goal_key, 0)]), 0);
+    while !ahead.0.is_empty() && !behind.0.is_empty() {
+        // grow whichever side has less to look at
+        let meet = if ahead.0.len() <= behind.0.len() {
+            grow(&mut ahead, &behind.1, &mut successors, &mut key)
+        } else {
+            grow(&mut behind, &ahead.1, &mut predecessors, &mut key)
+        };
+        if meet.is_some() {
+            return meet;
+        }
+    }
+    None
+}
+
+/// Takes one side of a bidirectional search a layer further. Returns the
+/// shortest whole path through the new layer that meets the other side.
+fn grow<S, K, I>(
+    (layer, seen, depth): &mut (Vec<S>, HashMap<K, usize>, usize),
+    other: &HashMap<K, usize>,
+    mut next: impl FnMut(&S) -> I,
+    key: &mut impl FnMut(&S) -> K,
+) -> Option<usize>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    *depth += 1;
+    let mut meet = None;
+    let mut grown = Vec::new();
+    for state in layer.iter() {
+        for state in next(state) {
+            let k = key(&state);
+            if seen.contains_key(&k) {
+                continue;
+            }
+            if let Some(rest) = other.get(&k) {
+                let total = *depth + rest;
+                meet = Some(meet.map_or(total, |m: usize| m.min(total)));
+            }
+            seen.insert(k, *depth);
+            grown.push(state);
+        }
+    }
+    *layer = grown;
+    meet
+}
+
+/// Every state reachable from the start, in order of how many steps away
+/// it is, along with that number.
+pub struct BreadthFirst<S, K, F, G> {
+    queue: VecDeque<(S, usize)>,
+    seen: HashSet<K>,
+    successors: F,
+    key: G,
+}
+
+/// Walks the graph breadth first, telling states apart by the states themselves.
+pub fn breadth_first<S, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+) -> BreadthFirst<S, S, impl FnMut(&S) -> I, impl FnMut(&S) -> S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    BreadthFirst::with_key(start, successors, S::clone)
+}
+
+impl<S, K, I, F, G> BreadthFirst<S, K, F, G>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> K,
+{
+    /// Walks the graph breadth first, counting states with the same `key`
+    /// as the same state.
+    pub fn with_key(start: S, successors: F, mut key: G) -> BreadthFirst<S, K, F, G> {
+        BreadthFirst {
+            seen: HashSet::from([key(&start)]),
+            queue: VecDeque::from([(start, 0)]),
+            successors,
+            key,
+        }
+    }
+}
+
+impl<S, K, I, F, G> Iterator for BreadthFirst<S, K, F, G>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> K,
+{
+    type Item = (S, usize);
+
+    fn next(&mut self) -> Option<(S, usize)> {
+        let (state, depth) = self.queue.pop_front()?;
+        for next in (self.successors)(&state) {
+            if self.seen.insert((self.key)(&next)) {
+                self.queue.push_back((next, depth + 1));
+            }
+        }
+        Some((state, depth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a ring of 10 numbers, stepping one either way for 1 or three forward for 2
+    fn ring(n: &u8) -> Vec<(u8, usize)> {
+        vec![((n + 1) % 10, 1), ((n + 9) % 10, 1), ((n + 3) % 10, 2)]
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs(0u8, |n| ring(n).into_iter().map(|(n, _)| n), |n| *n == 6).unwrap();
+        assert_eq!(vec![0, 3, 6], path.states);
+        assert_eq!(2, path.cost);
+        assert_eq!(None, bfs(0u8, |n| vec![(n + 2) % 10], |n| *n == 5));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let path = dijkstra(0u8, ring, |n| *n == 6).unwrap();
+        assert_eq!(4, path.cost);
+        assert_eq!(Some(0), path.states.first().copied());
+        assert_eq!(Some(6), path.states.last().copied());
+        let path = dijkstra(0u8, ring, |n| *n == 8).unwrap();
+        assert_eq!(vec![0, 9, 8], path.states);
+    }
+
+    #[test]
+    fn test_a_star() {
+        // walking a 5x5 open field from corner to corner
+        let steps = |&(x, y): &(i8, i8)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..5).contains(x) && (0..5).contains(y))
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        };
+        let distance = |&(x, y): &(i8, i8)| (4 - x + 4 - y) as usize;
+        let path = a_star((0, 0), steps, distance, |p| *p == (4, 4)).unwrap();
+        assert_eq!(8, path.cost);
+        assert_eq!(9, path.states.len());
+    }
+
+    #[test]
+    fn test_bidirectional() {
+        let step = |n: &u8| ring(n).into_iter().map(|(n, _)| n);
+        assert_eq!(Some(3), bidirectional(0u8, 5, step, step, |n| *n));
+        assert_eq!(Some(0), bidirectional(4u8, 4, step, step, |n| *n));
+        // only ever counting up, so looking back means counting down
+        let up = |n: &u8| vec![n + 1, n * 2];
+        let down = |n: &u8| {
+            let mut before: Vec<u8> = n.checked_sub(1).into_iter().collect();
+            before.extend(n.is_multiple_of(2).then_some(n / 2));
+            before
+        };
+        assert_eq!(Some(4), bidirectional(1u8, 10, up, down, |n| *n));
+        assert_eq!(None, bidirectional(1u8, 9, |_| Vec::new(), down, |n| *n));
+    }
+
+    #[test]
+    fn test_breadth_first() {
+        let order: Vec<(u8, usize)> =
+            breadth_first(0u8, |n| vec![(n + 1) % 5, (n + 4) % 5]).collect();
+        assert_eq!(vec![(0, 0), (1, 1), (4, 1), (2, 2), (3, 2)], order);
+        // keyed on parity only two states are different
+        let order = BreadthFirst::with_key(1u8, |n| vec![n + 1, n + 2], |n| n % 2);
+        assert_eq!(vec![(1, 0), (2, 1)], order.collect::<Vec<_>>());
+    }
+}
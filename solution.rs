@@ -0,0 +1,54 @@
+use std::fmt::Display;
+
+/// A puzzle day: parsed once from its input, then asked for each part's answer.
+pub trait Solution: Sized {
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Self;
+    fn part1(&self) -> Self::Part1;
+    fn part2(&self) -> Self::Part2;
+}
+
+/// Parses `input` as `S` and returns the answers of the requested parts, in order.
+pub fn solve<S: Solution>(input: &str, parts: &[u8]) -> Vec<String> {
+    let solution = S::parse(input);
+    parts
+        .iter()
+        .map(|part| match part {
+            1 => solution.part1().to_string(),
+            2 => solution.part2().to_string(),
+            _ => panic!("no part {}", part),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum(Vec<u32>);
+
+    impl Solution for Sum {
+        type Part1 = u32;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> Self {
+            Sum(input.lines().map(|l| l.parse().unwrap()).collect())
+        }
+
+        fn part1(&self) -> u32 {
+            self.0.iter().sum()
+        }
+
+        fn part2(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    #[test]
+    fn test_solve_answers_requested_parts_in_order() {
+        assert_eq!(vec!["3", "6"], solve::<Sum>("1\n2\n3", &[2, 1]));
+        assert_eq!(vec!["6"], solve::<Sum>("1\n2\n3", &[1]));
+    }
+}
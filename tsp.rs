@@ -0,0 +1,176 @@
+//! The best order to visit every one of a handful of places, solved with the
+//! Held–Karp dynamic programme.
+//!
+//! Takes time around `n² 2ⁿ` and memory around `n 2ⁿ`, so twenty places or
+//! so is comfortable where trying every order would not be.
+
+/// Whether the route has to come back to where it started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Open,
+    Closed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tour {
+    /// Every place once, in the order visited. A closed tour returns to the
+    /// first place after the last.
+    pub order: Vec<usize>,
+    pub cost: i64,
+}
+
+/// The cheapest route through every place, where `dist[a][b]` is the cost of
+/// going from `a` to `b`. Costs may be negative, to find the best route by
+/// some measure that should be as high as possible.
+///
+/// The route begins at `start` if given. A closed tour without a start
+/// is listed from place 0. Of several equally good orders, the first in
+/// numeric order is picked.
+pub fn shortest_tour(dist: &[Vec<i64>], start: Option<usize>, shape: Shape) -> Tour {
+    let n = dist.len();
+    assert!(
+        dist.iter().all(|row| row.len() == n),
+        "distances must be a square table"
+    );
+    assert!(
+        n < usize::BITS as usize,
+        "too many places for a set of them to fit in a usize"
+    );
+    if n == 0 {
+        return Tour {
+            order: Vec::new(),
+            cost: 0,
+        };
+    }
+    let start = match (start, shape) {
+        (Some(s), _) => {
+            assert!(s < n, "start {} of {} places", s, n);
+            Some(s)
+        }
+        (None, Shape::Closed) => Some(0),
+        (None, Shape::Open) => None,
+    };
+
+    // rest[set][last] is the cheapest way on from last to visit everything
+    // outside the set, and back to the start for a closed tour
+    let sets = 1usize << n;
+    let full = sets - 1;
+    let mut rest = vec![vec![0; n]; sets];
+    if let (Shape::Closed, Some(start)) = (shape, start) {
+        for last in 0..n {
+            rest[full][last] = dist[last][start];
+        }
+    }
+    for set in (1..full).rev() {
+        for last in (0..n).filter(|l| set & 1 << l != 0) {
+            rest[set][last] = (0..n)
+                .filter(|next| set & 1 << next == 0)
+                .map(|next| dist[last][next] + rest[set | 1 << next][next])
+                .min()
+                .unwrap();
+        }
+    }
+
+    // walking forward, taking the lowest numbered place among equally good
+    // ones, gives the first best order
+    let mut firsts = match start {
+        Some(s) => s..s + 1,
+        None => 0..n,
+    };
+    let cost = firsts.clone().map(|f| rest[1 << f][f]).min().unwrap();
+    let mut last = firsts.find(|f| rest[1 << f][*f] == cost).unwrap();
+    let mut order = vec![last];
+    let mut set = 1 << last;
+    while set != full {
+        let left = rest[set][last];
+        last = (0..n)
+            .filter(|next| set & 1 << next == 0)
+            .find(|next| dist[last][*next] + rest[set | 1 << next][*next] == left)
+            .unwrap();
+        order.push(last);
+        set |= 1 << last;
+    }
+    Tour { order, cost }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // four places in a row, a step apart
+    fn line() -> Vec<Vec<i64>> {
+        (0..4i64)
+            .map(|a| (0..4i64).map(|b| (a - b).abs()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_open() {
+        let tour = shortest_tour(&line(), None, Shape::Open);
+        assert_eq!(3, tour.cost);
+        assert_eq!(vec![0, 1, 2, 3], tour.order);
+        let tour = shortest_tour(&line(), Some(1), Shape::Open);
+        assert_eq!(
+            Tour {
+                order: vec![1, 0, 2, 3],
+                cost: 4
+            },
+            tour
+        );
+    }
+
+    #[test]
+    fn test_closed() {
+        let tour = shortest_tour(&line(), Some(2), Shape::Closed);
+        assert_eq!(
+            Tour {
+                order: vec![2, 0, 1, 3],
+                cost: 6
+            },
+            tour
+        );
+        let tour = shortest_tour(&line(), None, Shape::Closed);
+        assert_eq!(
+            Tour {
+                order: vec![0, 1, 2, 3],
+                cost: 6
+            },
+            tour
+        );
+    }
+
+    #[test]
+    fn test_one_way() {
+        // going round one way is cheap, the other way dear
+        let dist = vec![vec![0, 1, 9], vec![9, 0, 1], vec![1, 9, 0]];
+        let tour = shortest_tour(&dist, None, Shape::Closed);
+        assert_eq!(
+            Tour {
+                order: vec![0, 1, 2],
+                cost: 3
+            },
+            tour
+        );
+        let tour = shortest_tour(&dist, Some(1), Shape::Open);
+        assert_eq!(
+            Tour {
+                order: vec![1, 2, 0],
+                cost: 2
+            },
+            tour
+        );
+    }
+
+    #[test]
+    fn test_tiny() {
+        assert_eq!(0, shortest_tour(&[], None, Shape::Closed).cost);
+        let tour = shortest_tour(&[vec![0]], None, Shape::Closed);
+        assert_eq!(
+            Tour {
+                order: vec![0],
+                cost: 0
+            },
+            tour
+        );
+    }
+}
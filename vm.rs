@@ -0,0 +1,212 @@
+//! A small framework for the toy CPUs some puzzles describe: a program of
+//! instructions read from text, a register file, a program counter and a
+//! loop stepping through it all.
+//!
+//! An instruction set is a type implementing `Instruction`, parsed from one
+//! line of the program. Registers are named by letter from `a`.
+
+use std::str::FromStr;
+
+/// Where the program counter goes after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    /// Moves the program counter by this much.
+    Jump(isize),
+}
+
+pub trait Instruction: FromStr<Err = String> {
+    /// What a register holds.
+    type Word: Copy + Default;
+    /// How many registers the machine has.
+    const REGISTERS: usize;
+    /// The names of the operations, for counting how often each runs.
+    const OPCODES: &'static [&'static str];
+
+    /// The index of this instruction's operation in `OPCODES`.
+    fn opcode(&self) -> usize;
+
+    /// Does what the instruction says. It may change itself as well as the
+    /// registers.
+    fn execute(&mut self, registers: &mut [Self::Word]) -> Flow;
+}
+
+/// The register named by `name`, for a machine with `count` registers.
+pub fn register(name: &str, count: usize) -> Result<usize, String> {
+    match name.as_bytes() {
+        [c @ b'a'..=b'z'] if ((c - b'a') as usize) < count => Ok((c - b'a') as usize),
+        _ => Err(format!("no register {}", name)),
+    }
+}
+
+/// A jump offset like `+4` or `-7`.
+pub fn offset(s: &str) -> Result<isize, String> {
+    s.parse().map_err(|_| format!("bad offset: {}", s))
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<I: Instruction> {
+    pub program: Vec<I>,
+    pub registers: Vec<I::Word>,
+    pub pc: usize,
+    /// How many instructions have run.
+    pub steps: usize,
+    counts: Vec<usize>,
+}
+
+impl<I: Instruction> Machine<I> {
+    pub fn new(program: Vec<I>) -> Machine<I> {
+        Machine {
+            program,
+            registers: vec![I::Word::default(); I::REGISTERS],
+            pc: 0,
+            steps: 0,
+            counts: vec![0; I::OPCODES.len()],
+        }
+    }
+
+    /// Reads a program with one instruction per line, skipping blank lines.
+    pub fn parse(text: &str) -> Result<Machine<I>, String> {
+        let program = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(n, l)| {
+                l.trim()
+                    .parse()
+                    .map_err(|e| format!("line {}: {}", n + 1, e))
+            })
+            .collect::<Result<Vec<I>, String>>()?;
+        Ok(Machine::new(program))
+    }
+
+    /// The machine halts when the program counter leaves the program.
+    pub fn halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Runs one instruction. Returns false, doing nothing, once halted.
+    pub fn step(&mut self) -> bool {
+        let Some(instruction) = self.program.get_mut(self.pc) else {
+            return false;
+        };
+        self.counts[instruction.opcode()] += 1;
+        self.steps += 1;
+        self.pc = match instruction.execute(&mut self.registers) {
+            Flow::Next => self.pc + 1,
+            // jumping before the start halts just like jumping past the end
+            Flow::Jump(by) => self.pc.checked_add_signed(by).unwrap_or(usize::MAX),
+        };
+        true
+    }
+
+    pub fn run(&mut self) {
+        while self.step() {}
+    }
+
+    /// Runs at most `limit` more instructions, returning whether it halted.
+    pub fn run_for(&mut self, limit: usize) -> bool {
+        for _ in 0..limit {
+            if !self.step() {
+                return true;
+            }
+        }
+        self.halted()
+    }
+
+    /// How many times each operation has run.
+    pub fn counts(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        I::OPCODES.iter().copied().zip(self.counts.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Adding constants, and jumping if a register isn't zero.
+    #[derive(Debug, Clone, PartialEq)]
+    enum Toy {
+        Add(usize, i64),
+        Jnz(usize, isize),
+    }
+
+    impl FromStr for Toy {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Toy, String> {
+            let words: Vec<&str> = s.split_whitespace().collect();
+            match words[..] {
+                ["add", r, n] => Ok(Toy::Add(
+                    register(r, 2)?,
+                    n.parse().map_err(|_| format!("bad number: {}", n))?,
+                )),
+                ["jnz", r, by] => Ok(Toy::Jnz(register(r, 2)?, offset(by)?)),
+                _ => Err(format!("unknown instruction: {}", s)),
+            }
+        }
+    }
+
+    impl Instruction for Toy {
+        type Word = i64;
+        const REGISTERS: usize = 2;
+        const OPCODES: &'static [&'static str] = &["add", "jnz"];
+
+        fn opcode(&self) -> usize {
+            match self {
+                Toy::Add(..) => 0,
+                Toy::Jnz(..) => 1,
+            }
+        }
+
+        fn execute(&mut self, registers: &mut [i64]) -> Flow {
+            match *self {
+                Toy::Add(r, n) => registers[r] += n,
+                Toy::Jnz(r, by) if registers[r] != 0 => return Flow::Jump(by),
+                Toy::Jnz(..) => {}
+            }
+            Flow::Next
+        }
+    }
+
+    // counts a down from 3, adding 2 to b each time
+    const COUNTDOWN: &str = "add a 3\nadd b 2\nadd a -1\njnz a -2\n";
+
+    #[test]
+    fn test_run() {
+        let mut m: Machine<Toy> = Machine::parse(COUNTDOWN).unwrap();
+        m.run();
+        assert!(m.halted());
+        assert_eq!(vec![0, 6], m.registers);
+        assert_eq!(10, m.steps);
+        assert_eq!(vec![("add", 7), ("jnz", 3)], m.counts().collect::<Vec<_>>());
+        assert!(!m.step());
+    }
+
+    #[test]
+    fn test_run_for() {
+        let mut m: Machine<Toy> = Machine::parse(COUNTDOWN).unwrap();
+        assert!(!m.run_for(4));
+        assert_eq!((1, vec![2, 2]), (m.pc, m.registers.clone()));
+        assert!(m.run_for(100));
+    }
+
+    #[test]
+    fn test_jump_before_start() {
+        let mut m: Machine<Toy> = Machine::parse("add a 1\njnz a -5\nadd b 1").unwrap();
+        m.run();
+        assert!(m.halted());
+        assert_eq!(vec![1, 0], m.registers);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |s: &str| Machine::<Toy>::parse(s).unwrap_err();
+        assert_eq!(
+            "line 2: unknown instruction: mul a 2",
+            parse("add a 1\nmul a 2")
+        );
+        assert_eq!("line 1: no register c", parse("add c 1"));
+        assert_eq!("line 3: bad offset: x", parse("add a 1\n\njnz a x"));
+    }
+}